@@ -0,0 +1,70 @@
+//! Opens a window showing a mesh defined outside of the library: a
+//! five-pointed star.
+//!
+//! Run with `cargo run --example custom_mesh`.
+
+use std::f32::consts::PI;
+
+use dragonfly::{
+    dragonfly::Dragonfly,
+    vertex::{Mesh, Vertex, FLAT_NORMAL},
+};
+
+/// A star with the given number of points, centered on the origin.
+struct Star {
+    points: u16,
+    outer_radius: f32,
+    inner_radius: f32,
+}
+
+impl Mesh for Star {
+    /// The center, then the outer and inner corners, alternating
+    /// counter-clockwise from the top.
+    fn get_vertices(&self) -> Vec<Vertex> {
+        let corners = 2 * self.points;
+        let center = Vertex {
+            position: [0.0, 0.0, 0.0],
+            color: [1.0, 1.0, 1.0, 1.0],
+            normal: FLAT_NORMAL,
+            tex_coords: [0.0, 0.0],
+        };
+
+        std::iter::once(center)
+            .chain((0..corners).map(|i| {
+                let angle = PI / 2.0 + i as f32 * PI / self.points as f32;
+                let (radius, color) = if i % 2 == 0 {
+                    (self.outer_radius, [1.0, 0.8, 0.0, 1.0])
+                } else {
+                    (self.inner_radius, [1.0, 0.3, 0.0, 1.0])
+                };
+                Vertex {
+                    position: [radius * angle.cos(), radius * angle.sin(), 0.0],
+                    color,
+                    normal: FLAT_NORMAL,
+                    tex_coords: [0.0, 0.0],
+                }
+            }))
+            .collect()
+    }
+
+    /// A fan of triangles around the center.
+    fn get_indices(&self) -> Vec<u16> {
+        let corners = 2 * self.points;
+        (0..corners)
+            .flat_map(|i| [0, 1 + i, 1 + (i + 1) % corners])
+            .collect()
+    }
+}
+
+fn main() {
+    let star = Star {
+        points: 5,
+        outer_radius: 0.8,
+        inner_radius: 0.35,
+    };
+
+    if let Err(e) = Dragonfly::default().with_mesh(&star).run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
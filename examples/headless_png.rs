@@ -0,0 +1,31 @@
+//! Renders a circle without a window and saves it as a PNG image.
+//!
+//! Run with `cargo run --example headless_png [output.png]`.
+
+use std::path::PathBuf;
+
+use dragonfly::{snapshot, vertex::Figure};
+
+const WIDTH: u32 = 512;
+const HEIGHT: u32 = 512;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("snapshot.png"), PathBuf::from);
+
+    let pixels =
+        match snapshot::render_snapshot(&Figure::Circle(64), WIDTH, HEIGHT, wgpu::Color::BLACK) {
+            Ok(pixels) => pixels,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+    if let Err(e) = snapshot::write_png(&path, WIDTH, HEIGHT, &pixels) {
+        eprintln!("Error: failed to write {:?}: {}", path, e);
+        std::process::exit(1);
+    }
+    println!("Saved {:?}", path);
+}
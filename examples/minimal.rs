@@ -0,0 +1,13 @@
+//! Opens a window showing a pentagon.
+//!
+//! Run with `cargo run --example minimal`, and press the space bar to cycle
+//! through the other figures.
+
+use dragonfly::{dragonfly::Dragonfly, vertex::Figure};
+
+fn main() {
+    if let Err(e) = Dragonfly::default().with_mesh(&Figure::Pentagon).run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
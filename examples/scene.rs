@@ -0,0 +1,92 @@
+//! Lays out a draw list of figures on a grid, recolors them from a
+//! palette and saves it as a PNG image, without a window.
+//!
+//! Run with `cargo run --example scene [output.png]`.
+
+use std::path::PathBuf;
+
+use dragonfly::{
+    dragonfly::BACKGROUND_PRESETS,
+    snapshot,
+    spec::{FigureSpec, SceneSpec},
+    vertex::{self, Figure, Mesh, MeshData},
+};
+
+const WIDTH: u32 = 768;
+const HEIGHT: u32 = 768;
+
+/// The fill colors of the figures, cycled through in order.
+const PALETTE: &[[f32; 4]] = &[
+    [0.90, 0.30, 0.24, 1.0],
+    [0.95, 0.61, 0.07, 1.0],
+    [0.18, 0.80, 0.44, 1.0],
+    [0.20, 0.60, 0.86, 1.0],
+    [0.61, 0.35, 0.71, 1.0],
+];
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("scene.png"), PathBuf::from);
+
+    let scene = SceneSpec {
+        figures: vec![
+            FigureSpec::Triangle,
+            FigureSpec::Pentagon,
+            FigureSpec::Rectangle,
+            FigureSpec::Circle { segments: 48 },
+            FigureSpec::Trapezoid,
+            FigureSpec::Parallelogram,
+            FigureSpec::Plane { subdivisions: 3 },
+            FigureSpec::Torus {
+                major_radius: 0.35,
+                minor_radius: 0.15,
+                major_segments: 48,
+                minor_segments: 24,
+            },
+            FigureSpec::Cylinder { segments: 32 },
+        ],
+    };
+
+    // Place the figures on a 3x3 grid, from the top-left, and fill each
+    // one with a color of the palette.
+    let draw_list: Vec<MeshData> = scene
+        .figures
+        .into_iter()
+        .enumerate()
+        .map(|(i, spec)| {
+            let mut mesh = Figure::from(spec).mesh_data().scaled(0.55).translated([
+                (i % 3) as f32 * 0.65 - 0.65,
+                0.65 - (i / 3) as f32 * 0.65,
+                0.0,
+            ]);
+            for vertex in &mut mesh.vertices {
+                vertex.color = PALETTE[i % PALETTE.len()];
+            }
+            mesh
+        })
+        .collect();
+
+    // Merge the draw list into a single mesh, drawn in one call.
+    let meshes: Vec<&dyn Mesh> = draw_list.iter().map(|mesh| mesh as &dyn Mesh).collect();
+    let Some(mesh) = vertex::merge(&meshes).map(MeshData::from) else {
+        eprintln!("Error: the scene has too many vertices");
+        std::process::exit(1);
+    };
+
+    // The charcoal background preset.
+    let background = BACKGROUND_PRESETS[3];
+    let pixels = match snapshot::render_snapshot(&mesh, WIDTH, HEIGHT, background) {
+        Ok(pixels) => pixels,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = snapshot::write_png(&path, WIDTH, HEIGHT, &pixels) {
+        eprintln!("Error: failed to write {:?}: {}", path, e);
+        std::process::exit(1);
+    }
+    println!("Saved {:?}", path);
+}
@@ -1,16 +1,60 @@
+// Uniforms
+struct TimeUniform {
+    time: f32,
+    delta_time: f32,
+    wave: u32,
+    angle: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> time: TimeUniform;
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(1)
+var<uniform> camera: CameraUniform;
+
+struct TransformUniform {
+    transform: mat4x4<f32>,
+};
+
+@group(0) @binding(2)
+var<uniform> transform: TransformUniform;
+
 // Vertex shader
 struct VertexInput {
     @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) normal: vec3<f32>,
+    @location(3) tex_coords: vec2<f32>,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
-    @location(0) color: vec3<f32>,
+    @location(0) color: vec4<f32>,
 };
 
 @vertex
 fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    var position = model.position;
+    if (time.wave != 0u) {
+        position.x += sin(time.time + position.y) * 0.05;
+    }
+    let c = cos(time.angle);
+    let s = sin(time.angle);
+    position = vec3<f32>(c * position.x - s * position.y, s * position.x + c * position.y, position.z);
+    out.clip_position = camera.view_proj * transform.transform * vec4<f32>(position, 1.0);
+    out.color = model.color;
+    return out;
+}
+
+// Grid vertex shader, keeping the grid still while the figure waves or the
+// camera moves
+@vertex
+fn vs_grid(model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.clip_position = vec4<f32>(model.position, 1.0);
     out.color = model.color;
@@ -20,5 +64,11 @@ fn vs_main(model: VertexInput) -> VertexOutput {
 // Fragment shader
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(in.color, 1.0);
+    return in.color;
+}
+
+// Outline fragment shader, inverting the color to contrast with the fill
+@fragment
+fn fs_edge(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0 - in.color.rgb, in.color.a);
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use crate::vertex::{Figure, Mesh, Vertex};
+
+/// The curve the progress of an animation follows over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EasingFn {
+    /// Progresses at a constant speed.
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+}
+
+impl EasingFn {
+    /// Returns the eased progress at `t`, clamped between 0 and 1.
+    ///
+    /// Every curve goes from 0 at `t = 0` to 1 at `t = 1`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EasingFn::Linear => t,
+            EasingFn::EaseIn => t * t,
+            EasingFn::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            EasingFn::EaseInOut if t < 0.5 => 2.0 * t * t,
+            EasingFn::EaseInOut => 1.0 - 2.0 * (1.0 - t) * (1.0 - t),
+        }
+    }
+}
+
+/// An animation pulsing the colors of a figure to white and back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// The figure whose colors pulse.
+    pub figure: Figure,
+    /// The duration of a pulse from the colors of the figure to white, in
+    /// seconds. The colors come back over the same duration.
+    pub duration_secs: f32,
+    /// The curve the colors follow.
+    pub easing: EasingFn,
+}
+
+impl Animation {
+    /// Returns the vertices of the figure at the progress `t`, clamped
+    /// between 0 and 1.
+    ///
+    /// At 0, the colors are those of the figure, and at 1 they are all
+    /// white. In between, they are blended along the easing curve. The
+    /// positions and the alpha are left unchanged.
+    pub fn tick(&self, t: f32) -> Vec<Vertex> {
+        let blend = self.easing.apply(t);
+        self.figure
+            .get_vertices()
+            .into_iter()
+            .map(|mut vertex| {
+                for channel in &mut vertex.color[..3] {
+                    *channel += (1.0 - *channel) * blend;
+                }
+                vertex
+            })
+            .collect()
+    }
+
+    /// Returns the progress of the pulse at the given time in seconds,
+    /// rising from 0 to 1 over `duration_secs`, then falling back to 0 over
+    /// the same duration, and so on.
+    ///
+    /// An animation without duration stays at 0.
+    pub fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration_secs <= 0.0 {
+            return 0.0;
+        }
+        let phase = (elapsed / self.duration_secs).rem_euclid(2.0);
+        if phase > 1.0 {
+            2.0 - phase
+        } else {
+            phase
+        }
+    }
+}
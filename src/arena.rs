@@ -0,0 +1,95 @@
+use crate::vertex::Vertex;
+
+/// A bump arena for the transient CPU mesh data of a frame.
+///
+/// The per-frame mesh utilities allocate their vertices and indices in the
+/// arena instead of fresh `Vec`s, and the arena is reset at the start of
+/// each frame. The memory is kept across resets, so once the arena has grown
+/// to fit a frame, the following frames of the same size perform no heap
+/// allocation.
+#[derive(Debug, Default)]
+pub struct FrameArena {
+    /// The vertices allocated during the current frame.
+    vertices: Vec<Vertex>,
+    /// The indices allocated during the current frame.
+    indices: Vec<u16>,
+    /// The number of times the arena had to grow.
+    growths: usize,
+}
+
+impl FrameArena {
+    /// Creates an arena able to hold the given number of vertices and
+    /// indices per frame without growing.
+    pub fn with_capacity(vertices: usize, indices: usize) -> Self {
+        Self {
+            vertices: Vec::with_capacity(vertices),
+            indices: Vec::with_capacity(indices),
+            growths: 0,
+        }
+    }
+
+    /// Releases everything allocated during the frame, keeping the memory
+    /// for the next frame.
+    pub fn reset(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Allocates `len` zeroed vertices and returns them.
+    ///
+    /// The arena grows if the frame needs more vertices than its capacity.
+    pub fn alloc_vertices(&mut self, len: usize) -> &mut [Vertex] {
+        Self::alloc(&mut self.vertices, &mut self.growths, len)
+    }
+
+    /// Allocates `len` zeroed indices and returns them.
+    ///
+    /// The arena grows if the frame needs more indices than its capacity.
+    pub fn alloc_indices(&mut self, len: usize) -> &mut [u16] {
+        Self::alloc(&mut self.indices, &mut self.growths, len)
+    }
+
+    /// Returns every vertex allocated during the frame, in allocation order.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Returns every index allocated during the frame, in allocation order.
+    pub fn indices(&self) -> &[u16] {
+        &self.indices
+    }
+
+    /// Returns the number of vertices the arena can hold without growing.
+    pub fn vertex_capacity(&self) -> usize {
+        self.vertices.capacity()
+    }
+
+    /// Returns the number of indices the arena can hold without growing.
+    pub fn index_capacity(&self) -> usize {
+        self.indices.capacity()
+    }
+
+    /// Returns the number of times the arena had to grow.
+    ///
+    /// A counter that stops increasing means the steady-state frames perform
+    /// no heap allocation in the arena-aware paths.
+    pub fn growths(&self) -> usize {
+        self.growths
+    }
+
+    /// Appends `len` zeroed elements to the storage and returns them, at
+    /// least doubling the capacity when it is exceeded.
+    fn alloc<'a, T: bytemuck::Zeroable + Copy>(
+        storage: &'a mut Vec<T>,
+        growths: &mut usize,
+        len: usize,
+    ) -> &'a mut [T] {
+        let start = storage.len();
+        if start + len > storage.capacity() {
+            *growths += 1;
+            storage.reserve(len.max(storage.capacity()));
+        }
+        storage.resize(start + len, T::zeroed());
+        &mut storage[start..]
+    }
+}
@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use crate::{
+    pool::CachedMesh,
+    vertex::{Figure, Vertex},
+};
+
+/// The ranges of a mesh in the buffers of a `FigureAtlas`.
+///
+/// The indices of the mesh are not rebased: they are drawn with the start of
+/// `vertices` as the base vertex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FigureRange {
+    /// The range of the vertices of the mesh in the vertex buffer.
+    pub vertices: Range<u32>,
+    /// The range of the indices of the triangles in the index buffer.
+    pub indices: Range<u32>,
+    /// The range of the indices of the outline in the index buffer.
+    pub edge_indices: Range<u32>,
+    /// The range of the indices of the wireframe in the index buffer.
+    pub wireframe_indices: Range<u32>,
+}
+
+impl FigureRange {
+    /// Returns the vertex added to the indices of the mesh when it is drawn.
+    pub fn base_vertex(&self) -> i32 {
+        self.vertices.start as i32
+    }
+}
+
+/// The meshes of several figures concatenated into one vertex buffer and one
+/// index buffer, to be uploaded once.
+///
+/// Switching between the figures then only changes the ranges drawn, without
+/// writing to the GPU. Each mesh has its triangles, then its outline, then
+/// its wireframe in the index buffer.
+#[derive(Debug, Clone, Default)]
+pub struct FigureAtlas {
+    /// The vertices of all the meshes.
+    pub vertices: Vec<Vertex>,
+    /// The 16-bit indices of all the meshes, relative to the first vertex
+    /// of their mesh.
+    pub indices: Vec<u16>,
+    /// The ranges of each mesh, in the order they were given.
+    pub ranges: Vec<FigureRange>,
+}
+
+impl FigureAtlas {
+    /// Concatenates the given meshes.
+    pub fn new(meshes: &[CachedMesh]) -> Self {
+        let mut atlas = Self::default();
+        for mesh in meshes {
+            let vertices = atlas.push_vertices(&mesh.vertices);
+            let indices = atlas.push_indices(&mesh.indices);
+            let edge_indices = atlas.push_indices(&mesh.edge_indices);
+            let wireframe_indices = atlas.push_indices(&mesh.wireframe_indices);
+            atlas.ranges.push(FigureRange {
+                vertices,
+                indices,
+                edge_indices,
+                wireframe_indices,
+            });
+        }
+        atlas
+    }
+
+    /// Returns the ranges of the mesh at the given index, or `None` if the
+    /// index is out of range.
+    pub fn range(&self, index: usize) -> Option<&FigureRange> {
+        self.ranges.get(index)
+    }
+
+    /// Returns the vertices of a mesh of the atlas.
+    pub fn mesh_vertices(&self, range: &FigureRange) -> &[Vertex] {
+        &self.vertices[range.vertices.start as usize..range.vertices.end as usize]
+    }
+
+    /// Returns the size of the vertex and index buffers together, in bytes.
+    pub fn byte_size(&self) -> usize {
+        size_of_val(self.vertices.as_slice()) + size_of_val(self.indices.as_slice())
+    }
+
+    /// Appends vertices and returns their range.
+    fn push_vertices(&mut self, vertices: &[Vertex]) -> Range<u32> {
+        let start = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(vertices);
+        start..self.vertices.len() as u32
+    }
+
+    /// Appends indices and returns their range.
+    fn push_indices(&mut self, indices: &[u16]) -> Range<u32> {
+        let start = self.indices.len() as u32;
+        self.indices.extend_from_slice(indices);
+        start..self.indices.len() as u32
+    }
+}
+
+/// Concatenates the meshes of the figures of `Figure::ALL`, at the same
+/// indices.
+pub fn figure_atlas() -> FigureAtlas {
+    let meshes: Vec<CachedMesh> = Figure::ALL
+        .iter()
+        .map(|figure| CachedMesh::from_mesh(figure))
+        .collect();
+    FigureAtlas::new(&meshes)
+}
@@ -0,0 +1,114 @@
+/// The smallest zoom factor of the camera, 0.1×.
+pub const MIN_ZOOM: f32 = 0.1;
+/// The largest zoom factor of the camera, 50×.
+pub const MAX_ZOOM: f32 = 50.0;
+/// The zoom factor applied by one step of the mouse wheel.
+pub const ZOOM_STEP: f32 = 1.1;
+/// The fraction of the viewport a fitted figure fills on the narrower axis.
+pub const FIT_FRACTION: f32 = 0.8;
+
+/// A 2D camera looking down the Z axis at the figures.
+///
+/// The point at `center` is shown at the center of the window, and the
+/// figures are magnified by `zoom`. The default camera shows the clip space
+/// unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    /// The point shown at the center of the window.
+    pub center: [f32; 2],
+    /// The magnification, between `MIN_ZOOM` and `MAX_ZOOM`.
+    pub zoom: f32,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            center: [0.0, 0.0],
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Camera2D {
+    /// Returns the camera framing the given bounding box in a viewport of the
+    /// given physical size.
+    ///
+    /// The camera is centered on the box, and zoomed so that the box fills
+    /// `FIT_FRACTION` of the narrower axis of the viewport. The box is
+    /// fitted along the other axis if it is flat along the narrower one, and
+    /// the zoom is kept at 1 if it is a single point.
+    pub fn fit(bounds: ([f32; 3], [f32; 3]), (width, height): (u32, u32)) -> Self {
+        let (min, max) = bounds;
+        let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+        let extent = [max[0] - min[0], max[1] - min[1]];
+
+        // The clip space spans 2 units along each axis.
+        let (narrow, wide) = if width <= height { (0, 1) } else { (1, 0) };
+        let zoom = [extent[narrow], extent[wide]]
+            .into_iter()
+            .find(|&extent| extent > 0.0)
+            .map_or(1.0, |extent| {
+                (2.0 * FIT_FRACTION / extent).clamp(MIN_ZOOM, MAX_ZOOM)
+            });
+
+        Self { center, zoom }
+    }
+
+    /// Multiplies the zoom by the given factor, clamped to the range
+    /// `MIN_ZOOM..=MAX_ZOOM`.
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Multiplies the zoom by the given factor, clamped to the range
+    /// `MIN_ZOOM..=MAX_ZOOM`, keeping the point shown at the given normalized
+    /// device coordinates in place.
+    pub fn zoom_at(&mut self, factor: f32, ndc: [f32; 2]) {
+        let anchor = self.to_world(ndc);
+        self.zoom_by(factor);
+        self.center = [
+            anchor[0] - ndc[0] / self.zoom,
+            anchor[1] - ndc[1] / self.zoom,
+        ];
+    }
+
+    /// Moves the camera so that the figures follow a cursor moved by the
+    /// given offset, in normalized device coordinates.
+    pub fn drag(&mut self, delta: [f32; 2]) {
+        self.pan([-delta[0] / self.zoom, -delta[1] / self.zoom]);
+    }
+
+    /// Centers the camera back on the origin, with a zoom of 1.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Moves the center of the camera by the given offset.
+    pub fn pan(&mut self, delta: [f32; 2]) {
+        self.center[0] += delta[0];
+        self.center[1] += delta[1];
+    }
+
+    /// Returns the view-projection matrix of the camera, in column-major
+    /// order.
+    ///
+    /// The depth is left unchanged.
+    pub fn view_proj(&self) -> [[f32; 4]; 4] {
+        let [x, y] = self.center;
+        [
+            [self.zoom, 0.0, 0.0, 0.0],
+            [0.0, self.zoom, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [-x * self.zoom, -y * self.zoom, 0.0, 1.0],
+        ]
+    }
+
+    /// Returns the point of the figures shown at the given normalized device
+    /// coordinates.
+    pub fn to_world(&self, ndc: [f32; 2]) -> [f32; 2] {
+        [
+            ndc[0] / self.zoom + self.center[0],
+            ndc[1] / self.zoom + self.center[1],
+        ]
+    }
+}
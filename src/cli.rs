@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+/// The arguments of the `verify` subcommand.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VerifyArgs {
+    /// The file of the expected frame hashes (`--expect path`).
+    pub expect: PathBuf,
+    /// The file of the frame hashes of the run to verify (`--actual path`).
+    pub actual: PathBuf,
+}
+
+/// The command-line arguments of the application.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Args {
+    /// Whether commands are read from the standard input
+    /// (`--control stdin`).
+    pub control_stdin: bool,
+    /// The background color as RGBA components (`--background r g b a`).
+    pub background: Option<[f64; 4]>,
+    /// The chrome://tracing file the profile is written to on exit
+    /// (`--trace-profile path`).
+    pub trace_profile: Option<PathBuf>,
+    /// Whether the hash of each frame is printed (`--frame-hash`).
+    pub frame_hash: bool,
+    /// Whether the frame statistics are printed (`--stats`).
+    pub stats: bool,
+    /// The present mode of the surface
+    /// (`--present-mode mailbox|fifo|immediate`).
+    pub present_mode: Option<wgpu::PresentMode>,
+    /// The preferred kind of adapter (`--power low|high`).
+    pub power_preference: Option<wgpu::PowerPreference>,
+    /// Whether the defects of the opened meshes are repaired (`--repair`).
+    pub repair: bool,
+    /// The JSON file of the figure displayed at startup (`--figure path`),
+    /// requiring the `serialize` feature.
+    pub figure: Option<PathBuf>,
+    /// The arguments of the `verify` subcommand, comparing frame hashes
+    /// instead of running the application.
+    pub verify: Option<VerifyArgs>,
+}
+
+impl Args {
+    /// Parses the command-line arguments, without the program name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if an argument is unknown or if the values of
+    /// an argument are missing or invalid.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().peekable();
+
+        if args.peek().map(String::as_str) == Some("verify") {
+            args.next();
+            let (mut expect, mut actual) = (None, None);
+            while let Some(arg) = args.next() {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                match arg.as_str() {
+                    "--expect" => expect = Some(PathBuf::from(value)),
+                    "--actual" => actual = Some(PathBuf::from(value)),
+                    _ => return Err(format!("Unknown argument '{}'", arg)),
+                }
+            }
+            parsed.verify = Some(VerifyArgs {
+                expect: expect.ok_or("Missing --expect for verify")?,
+                actual: actual.ok_or("Missing --actual for verify")?,
+            });
+            return Ok(parsed);
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--control" => match args.next().as_deref() {
+                    Some("stdin") => parsed.control_stdin = true,
+                    Some(value) => return Err(format!("Unknown control source '{}'", value)),
+                    None => return Err("Missing value for --control".to_string()),
+                },
+                "--background" => {
+                    let mut color = [0.0; 4];
+                    for component in color.iter_mut() {
+                        let value = args
+                            .next()
+                            .ok_or("--background expects 4 values: r g b a")?;
+                        *component = value
+                            .parse()
+                            .map_err(|_| format!("Invalid color component '{}'", value))?;
+                    }
+                    parsed.background = Some(color);
+                }
+                "--frame-hash" => parsed.frame_hash = true,
+                "--stats" => parsed.stats = true,
+                "--repair" => parsed.repair = true,
+                "--present-mode" => {
+                    parsed.present_mode = Some(match args.next().as_deref() {
+                        Some("mailbox") => wgpu::PresentMode::Mailbox,
+                        Some("fifo") => wgpu::PresentMode::Fifo,
+                        Some("immediate") => wgpu::PresentMode::Immediate,
+                        Some(value) => return Err(format!("Unknown present mode '{}'", value)),
+                        None => return Err("Missing value for --present-mode".to_string()),
+                    });
+                }
+                "--power" => {
+                    parsed.power_preference = Some(match args.next().as_deref() {
+                        Some("low") => wgpu::PowerPreference::LowPower,
+                        Some("high") => wgpu::PowerPreference::HighPerformance,
+                        Some(value) => return Err(format!("Unknown power preference '{}'", value)),
+                        None => return Err("Missing value for --power".to_string()),
+                    });
+                }
+                "--figure" => {
+                    let path = args.next().ok_or("Missing value for --figure")?;
+                    parsed.figure = Some(PathBuf::from(path));
+                }
+                "--trace-profile" => {
+                    let path = args.next().ok_or("Missing value for --trace-profile")?;
+                    parsed.trace_profile = Some(PathBuf::from(path));
+                }
+                _ => return Err(format!("Unknown argument '{}'", arg)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
@@ -0,0 +1,31 @@
+/// The options the graphics context is created with, by `Context::new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextConfig {
+    /// Whether an integrated, low-power adapter or a discrete,
+    /// high-performance one is preferred.
+    pub power_preference: wgpu::PowerPreference,
+    /// The graphics backends the adapter may use.
+    pub backends: wgpu::Backends,
+    /// The present mode the surface is configured with, if the surface
+    /// supports it. `Fifo`, always supported, is used otherwise.
+    pub present_mode: wgpu::PresentMode,
+    /// The number of samples per pixel for multisample antialiasing, 1 to
+    /// disable it.
+    ///
+    /// An unsupported count falls back to the largest supported count below
+    /// it, down to 1, with a warning.
+    pub msaa_samples: u32,
+}
+
+impl Default for ContextConfig {
+    /// Returns the configuration of the default adapter on the primary
+    /// backends, with vertical synchronization and 4 samples per pixel.
+    fn default() -> Self {
+        Self {
+            power_preference: wgpu::PowerPreference::default(),
+            backends: wgpu::Backends::PRIMARY,
+            present_mode: wgpu::PresentMode::Fifo,
+            msaa_samples: 4,
+        }
+    }
+}
@@ -1,6 +1,25 @@
-use std::sync::Arc;
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-use dragonfly::vertex::{self, Mesh, Vertex};
+use crate::{
+    animation::Animation,
+    atlas::{self, FigureAtlas, FigureRange},
+    camera::Camera2D,
+    config::ContextConfig,
+    error::DragonflyError,
+    frame_hash,
+    grid::Grid,
+    pool::CachedMesh,
+    snapshot,
+    stats::{FrameStats, FrameStatsHistory, GpuTimer, StatsSummary},
+    uniform::{CameraUniform, TimeUniform, TransformUniform},
+    vertex::{self, Gradient, Mesh, Vertex},
+};
 use wgpu::util::DeviceExt;
 use winit::window::Window;
 
@@ -18,23 +37,616 @@ pub struct Context {
     pub queue: wgpu::Queue,
     /// The surface configuration.
     pub config: wgpu::SurfaceConfiguration,
+    /// The depth buffer, of the size of the surface.
+    pub depth_texture: wgpu::Texture,
+    /// The number of samples per pixel of the multisample antialiasing, 1
+    /// without antialiasing.
+    pub sample_count: u32,
+    /// The numbers of samples per pixel the surface supports, in increasing
+    /// order.
+    pub sample_counts: Vec<u32>,
+    /// The multisampled texture the frames are rendered to, then resolved to
+    /// the surface, if there are several samples per pixel.
+    msaa_texture: Option<wgpu::Texture>,
+    /// The layout shared by the render pipelines, kept to rebuild them.
+    pipeline_layout: wgpu::PipelineLayout,
+    /// The built-in shader module, kept to rebuild the render pipelines.
+    shader: wgpu::ShaderModule,
+    /// The shader set with `set_shader`, if any, kept to rebuild the render
+    /// pipeline.
+    custom_shader: Option<wgpu::ShaderModule>,
+    /// The faces of the triangles of the figure that are not drawn, set with
+    /// `set_cull_mode`.
+    pub cull_mode: Option<wgpu::Face>,
     /// The size of the window.
     pub size: winit::dpi::PhysicalSize<u32>,
     /// The render pipeline.
     pub render_pipeline: wgpu::RenderPipeline,
+    /// The pipeline drawing the outline of the figure as a line list.
+    pub edge_pipeline: wgpu::RenderPipeline,
+    /// The pipeline drawing the edges of the figure as a line list, in the
+    /// colors of its vertices.
+    pub wireframe_pipeline: wgpu::RenderPipeline,
+    /// The pipeline drawing the triangles of the figure with
+    /// `PolygonMode::Line`, if the device supports it. The wireframe is
+    /// drawn with it rather than with `wireframe_pipeline` when it exists.
+    pub polygon_line_pipeline: Option<wgpu::RenderPipeline>,
+    /// The pipeline drawing the grid as a line list.
+    pub grid_pipeline: wgpu::RenderPipeline,
+    /// The WGSL file the render pipeline is reloaded from when it changes,
+    /// with `try_reload_shader`.
+    #[cfg(feature = "hot-reload")]
+    pub shader_path: Option<std::path::PathBuf>,
+    /// The modification time of the shader file when it was last read.
+    #[cfg(feature = "hot-reload")]
+    shader_modified: Option<std::time::SystemTime>,
+    /// Whether the shader file was missing when last polled, so that it is
+    /// only reported once.
+    #[cfg(feature = "hot-reload")]
+    shader_missing: bool,
 
-    /// The index of the current figure.
-    pub fig_idx: u8,
+    /// The time data uploaded to the shaders.
+    pub time_uniform: TimeUniform,
+    /// The time uniform buffer.
+    pub time_buffer: wgpu::Buffer,
+    /// The camera looking at the figure.
+    pub camera: Camera2D,
+    /// The camera uniform buffer.
+    pub camera_buffer: wgpu::Buffer,
+    /// The transform applied to the figure before the camera.
+    pub transform: TransformUniform,
+    /// The transform uniform buffer.
+    pub transform_buffer: wgpu::Buffer,
+    /// The bind group holding the uniforms.
+    pub uniform_bind_group: wgpu::BindGroup,
+    /// The layout of the bind group holding the uniforms.
+    pub uniform_bind_group_layout: wgpu::BindGroupLayout,
+
+    /// The color the render target is cleared with.
+    pub background: wgpu::Color,
+
+    /// Whether the device has been lost.
+    ///
+    /// The flag is raised by the device lost callback, the context must then
+    /// be rebuilt from scratch.
+    pub device_lost: Arc<AtomicBool>,
+
+    /// The figure displayed, as tessellated for the window, or `None` if a
+    /// mesh set with `set_mesh` is displayed.
+    figure: Option<vertex::Figure>,
+    /// The animation pulsing the colors of the figure, if any.
+    pub animation: Option<Animation>,
 
     /// The vertex buffer.
     pub vertex_buffer: wgpu::Buffer,
     /// The number of vertices in the vertex buffer.
     pub num_vertices: u32,
+    /// The vertices of the current mesh, with their own colors.
+    pub vertices: Vec<Vertex>,
+    /// The corners of the bounding box of the current mesh, for hit testing.
+    pub bounds: ([f32; 3], [f32; 3]),
+    /// Whether the figure is drawn in `HIGHLIGHT_COLOR`.
+    pub highlight: bool,
+    /// The gradient the figure is colored with instead of its own colors.
+    pub gradient: Option<Gradient>,
+    /// Whether the meshes set with `set_mesh` are fitted to the view, see
+    /// `MeshData::normalized`.
+    pub fit_meshes: bool,
 
     /// The index buffer.
     pub index_buffer: wgpu::Buffer,
     /// The number of indices in the index buffer.
     pub num_indices: u32,
+    /// The format of the indices in the index buffer.
+    pub index_format: wgpu::IndexFormat,
+
+    /// The index buffer of the outline, as pairs of indices.
+    pub edge_index_buffer: wgpu::Buffer,
+    /// The number of indices in the outline index buffer.
+    pub num_edge_indices: u32,
+    /// Whether the outline is drawn on top of the figure.
+    pub outline_enabled: bool,
+
+    /// The index buffer of the wireframe, as pairs of indices.
+    pub wireframe_index_buffer: wgpu::Buffer,
+    /// The number of indices in the wireframe index buffer.
+    pub num_wireframe_indices: u32,
+    /// Whether the figure is drawn as a wireframe instead of being filled.
+    pub wireframe_enabled: bool,
+
+    /// The meshes of every figure of `Figure::ALL`, uploaded once to
+    /// `atlas_vertex_buffer` and `atlas_index_buffer`.
+    pub atlas: FigureAtlas,
+    /// The vertex buffer of the meshes of the atlas.
+    pub atlas_vertex_buffer: wgpu::Buffer,
+    /// The index buffer of the meshes of the atlas.
+    pub atlas_index_buffer: wgpu::Buffer,
+    /// The ranges of the atlas drawn instead of the vertex and index buffers,
+    /// if the current figure is drawn from the atlas.
+    pub atlas_range: Option<FigureRange>,
+
+    /// The grid drawn behind the figure, if any.
+    pub grid: Option<Grid>,
+    /// The vertex buffer of the grid lines.
+    pub grid_vertex_buffer: wgpu::Buffer,
+    /// The number of vertices in the grid vertex buffer.
+    pub num_grid_vertices: u32,
+
+    /// The information of the adapter the device was requested from, kept
+    /// once the adapter is dropped.
+    adapter_info: wgpu::AdapterInfo,
+    /// The features supported by the adapter, which the device may be
+    /// requested with.
+    adapter_features: wgpu::Features,
+    /// The features enabled on the device.
+    device_features: wgpu::Features,
+
+    /// The limits of the device.
+    ///
+    /// The surface cannot be larger than `max_texture_dimension_2d`.
+    pub limits: wgpu::Limits,
+
+    /// The present modes supported by the surface.
+    pub present_modes: Vec<wgpu::PresentMode>,
+
+    /// Whether the rendered frames are copied back to the CPU.
+    pub frame_capture: bool,
+    /// The pixels of the last rendered frame, if the frames are captured.
+    pub captured_frame: Option<Vec<u8>>,
+
+    /// The statistics of the last rendered frames.
+    pub frame_stats: FrameStatsHistory,
+    /// The timer measuring the render pass on the GPU, if enabled.
+    gpu_timer: Option<GpuTimer>,
+}
+
+/// The color of the vertices of a highlighted figure, yellow, keeping their
+/// alpha.
+pub const HIGHLIGHT_COLOR: [f32; 3] = [1.0, 1.0, 0.0];
+
+/// Clamps a window size to the maximum texture dimension supported by the
+/// device.
+pub fn clamp_surface_size(
+    size: winit::dpi::PhysicalSize<u32>,
+    max_dimension: u32,
+) -> winit::dpi::PhysicalSize<u32> {
+    winit::dpi::PhysicalSize {
+        width: size.width.min(max_dimension),
+        height: size.height.min(max_dimension),
+    }
+}
+
+/// Returns whether a point in normalized device coordinates falls inside the
+/// XY projection of a bounding box, borders included.
+pub fn point_in_bounds(ndc: [f32; 2], bounds: ([f32; 3], [f32; 3])) -> bool {
+    let (min, max) = bounds;
+    (0..2).all(|i| min[i] <= ndc[i] && ndc[i] <= max[i])
+}
+
+/// Writes data to the start of a buffer with `queue.write_buffer`, replacing
+/// the buffer first by one twice as large, or as large as the data, if the
+/// data does not fit.
+///
+/// The buffer is only grown, so that switching between meshes of different
+/// sizes stops allocating once the largest one has been written. The data is
+/// padded to `wgpu::COPY_BUFFER_ALIGNMENT`, and the new buffer is created
+/// with the given usage and `COPY_DST`.
+pub fn write_growing_buffer(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    buffer: &mut wgpu::Buffer,
+    label: &str,
+    usage: wgpu::BufferUsages,
+    contents: &[u8],
+) {
+    if contents.is_empty() {
+        return;
+    }
+
+    let size = (contents.len() as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+    if size > buffer.size() {
+        *buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: size.max(buffer.size() * 2),
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+    }
+    if size == contents.len() as u64 {
+        queue.write_buffer(buffer, 0, contents);
+    } else {
+        let mut padded = contents.to_vec();
+        padded.resize(size as usize, 0);
+        queue.write_buffer(buffer, 0, &padded);
+    }
+}
+
+/// Creates the layout of the bind group holding the uniforms: the time at
+/// binding 0 and the camera at binding 1.
+pub fn create_uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    let uniform_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::VERTEX,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Uniform Bind Group Layout"),
+        entries: &[uniform_entry(0), uniform_entry(1), uniform_entry(2)],
+    })
+}
+
+/// The format of the depth buffer the figures are drawn with.
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Creates the depth buffer of a render target of the given size and number
+/// of samples per pixel.
+pub(crate) fn create_depth_texture(
+    device: &wgpu::Device,
+    width: u32,
+    height: u32,
+    sample_count: u32,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// The numbers of samples per pixel a render target may have, in increasing
+/// order.
+const SAMPLE_COUNTS: [u32; 5] = [1, 2, 4, 8, 16];
+
+/// Returns the numbers of samples per pixel supported by both the color
+/// format and the depth buffer, in increasing order. 1 is always supported.
+///
+/// The counts specific to the adapter are only used if the device enables
+/// `TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`, the guaranteed ones
+/// otherwise.
+pub fn supported_sample_counts(
+    adapter: &wgpu::Adapter,
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+) -> Vec<u32> {
+    let flags = |format: wgpu::TextureFormat| {
+        if device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            adapter.get_texture_format_features(format).flags
+        } else {
+            format.guaranteed_format_features(device.features()).flags
+        }
+    };
+    let (color, depth) = (flags(format), flags(DEPTH_FORMAT));
+    SAMPLE_COUNTS
+        .into_iter()
+        .filter(|&count| {
+            count == 1
+                || (color.sample_count_supported(count) && depth.sample_count_supported(count))
+        })
+        .collect()
+}
+
+/// Returns the largest supported number of samples per pixel that does not
+/// exceed the requested one, or 1 if there is none.
+pub fn choose_sample_count(requested: u32, supported: &[u32]) -> u32 {
+    supported
+        .iter()
+        .copied()
+        .filter(|&count| count <= requested)
+        .max()
+        .unwrap_or(1)
+}
+
+/// Creates the multisampled color texture the frames are rendered to before
+/// being resolved to the surface, or returns `None` with one sample per
+/// pixel.
+fn create_msaa_texture(
+    device: &wgpu::Device,
+    config: &wgpu::SurfaceConfiguration,
+    sample_count: u32,
+) -> Option<wgpu::Texture> {
+    (sample_count > 1).then(|| {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Multisample Texture"),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    })
+}
+
+/// Returns the depth attachment of a render pass, cleared to the far plane.
+pub(crate) fn depth_attachment(
+    view: &wgpu::TextureView,
+) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+    wgpu::RenderPassDepthStencilAttachment {
+        view,
+        depth_ops: Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(1.0),
+            store: wgpu::StoreOp::Discard,
+        }),
+        stencil_ops: None,
+    }
+}
+
+/// Creates the render pipeline drawing the outlines of the figures as line
+/// lists, in a color contrasting with the fill.
+pub(crate) fn create_edge_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+) -> wgpu::RenderPipeline {
+    create_pipeline(
+        device,
+        layout,
+        format,
+        sample_count,
+        shader,
+        primitive_state(wgpu::PrimitiveTopology::LineList),
+        "vs_main",
+        "fs_edge",
+        true,
+    )
+}
+
+/// Creates the render pipeline drawing the wireframes of the figures as line
+/// lists.
+pub(crate) fn create_wireframe_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+) -> wgpu::RenderPipeline {
+    create_pipeline(
+        device,
+        layout,
+        format,
+        sample_count,
+        shader,
+        primitive_state(wgpu::PrimitiveTopology::LineList),
+        "vs_main",
+        "fs_main",
+        true,
+    )
+}
+
+/// Creates the render pipeline drawing the edges of the triangles of the
+/// figures with `PolygonMode::Line`, front and back faces alike, or returns
+/// `None` if the device does not enable `Features::POLYGON_MODE_LINE`.
+pub fn create_polygon_line_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+) -> Option<wgpu::RenderPipeline> {
+    if !device
+        .features()
+        .contains(wgpu::Features::POLYGON_MODE_LINE)
+    {
+        return None;
+    }
+    Some(create_pipeline(
+        device,
+        layout,
+        format,
+        sample_count,
+        shader,
+        wgpu::PrimitiveState {
+            cull_mode: None,
+            polygon_mode: wgpu::PolygonMode::Line,
+            ..primitive_state(wgpu::PrimitiveTopology::TriangleList)
+        },
+        "vs_main",
+        "fs_main",
+        true,
+    ))
+}
+
+/// Creates the render pipeline drawing the grid as line lists.
+///
+/// The grid is not animated.
+pub(crate) fn create_grid_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+) -> wgpu::RenderPipeline {
+    create_pipeline(
+        device,
+        layout,
+        format,
+        sample_count,
+        shader,
+        primitive_state(wgpu::PrimitiveTopology::LineList),
+        "vs_grid",
+        "fs_main",
+        false,
+    )
+}
+
+/// Compiles the render pipeline drawing the figures from the source of a
+/// WGSL shader, with the same entry points as `shaders/shader.wgsl`, on a
+/// target with one sample per pixel.
+///
+/// # Errors
+///
+/// Returns the validation error if the shader does not compile or does not
+/// match the pipeline, in which case no pipeline is created.
+pub fn compile_render_pipeline(
+    device: &wgpu::Device,
+    uniform_bind_group_layout: &wgpu::BindGroupLayout,
+    format: wgpu::TextureFormat,
+    source: &str,
+) -> Result<wgpu::RenderPipeline, wgpu::Error> {
+    let shader = Context::with_custom_shader(device, source)?;
+    Ok(create_figure_pipeline(
+        device,
+        &create_pipeline_layout(device, uniform_bind_group_layout),
+        format,
+        1,
+        &shader,
+        Some(wgpu::Face::Back),
+    ))
+}
+
+/// Creates the render pipeline drawing the figures with the given shader,
+/// through its `vs_main` and `fs_main` entry points, without the triangles
+/// facing the given way.
+pub(crate) fn create_figure_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+    cull_mode: Option<wgpu::Face>,
+) -> wgpu::RenderPipeline {
+    create_pipeline(
+        device,
+        layout,
+        format,
+        sample_count,
+        shader,
+        wgpu::PrimitiveState {
+            cull_mode,
+            ..primitive_state(wgpu::PrimitiveTopology::TriangleList)
+        },
+        "vs_main",
+        "fs_main",
+        true,
+    )
+}
+
+/// Creates the layout of the render pipelines, binding the uniforms of the
+/// given layout.
+///
+/// The layout is shared by all the pipelines, which all read the same
+/// uniforms.
+pub fn create_pipeline_layout(
+    device: &wgpu::Device,
+    uniform_bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::PipelineLayout {
+    device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Render Pipeline Layout"),
+        bind_group_layouts: &[uniform_bind_group_layout],
+        push_constant_ranges: &[],
+    })
+}
+
+/// Creates the shader module of `shaders/shader.wgsl`, embedded in the
+/// binary.
+pub(crate) fn create_shader(device: &wgpu::Device) -> wgpu::ShaderModule {
+    device.create_shader_module(wgpu::include_wgsl!("../shaders/shader.wgsl"))
+}
+
+/// Returns the primitive state of the given topology, filled, with the back
+/// faces of the triangles culled.
+fn primitive_state(topology: wgpu::PrimitiveTopology) -> wgpu::PrimitiveState {
+    wgpu::PrimitiveState {
+        topology,
+        strip_index_format: None,
+        front_face: wgpu::FrontFace::Ccw,
+        // Only the triangles can be culled.
+        cull_mode: match topology {
+            wgpu::PrimitiveTopology::TriangleList => Some(wgpu::Face::Back),
+            _ => None,
+        },
+        polygon_mode: wgpu::PolygonMode::Fill,
+        unclipped_depth: false,
+        conservative: false,
+    }
+}
+
+/// Creates a render pipeline with the given shader, primitive state and
+/// entry points.
+///
+/// The fragments are blended over the render target with their alpha. With
+/// `depth_test`, they are only drawn if they are not behind the fragments
+/// drawn before them, and write their depth. Otherwise, they are always
+/// drawn, and leave the depth buffer unchanged.
+#[allow(clippy::too_many_arguments)]
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shader: &wgpu::ShaderModule,
+    primitive: wgpu::PrimitiveState,
+    vertex_entry_point: &str,
+    fragment_entry_point: &str,
+    depth_test: bool,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: Some(layout),
+        // Read vertex shader
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: vertex_entry_point,
+            buffers: &[Vertex::desc()],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        // Read fragment shader
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: fragment_entry_point,
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive,
+        // The fragments at the same depth are all drawn, in order, so the
+        // flat figures and their outlines are drawn as without depth.
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: depth_test,
+            depth_compare: if depth_test {
+                wgpu::CompareFunction::LessEqual
+            } else {
+                wgpu::CompareFunction::Always
+            },
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
 }
 
 impl Context {
@@ -45,45 +657,90 @@ impl Context {
     /// pipeline layout, and render pipeline.
     ///
     /// The context is configured for the initial window size and the first
-    /// figure.
-    pub async fn new(window: &Arc<Window>) -> Self {
+    /// figure, with the adapter, backends and present mode of the
+    /// configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the surface, the adapter or the device cannot be
+    /// created, or if the surface supports no texture format.
+    pub async fn new(
+        window: &Arc<Window>,
+        context_config: ContextConfig,
+    ) -> Result<Self, DragonflyError> {
         let size = window.inner_size();
 
-        // Create a new instance that take the default backend for the device.
+        // Create a new instance on the backends of the configuration.
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::PRIMARY,
+            backends: context_config.backends,
             ..Default::default()
         });
 
-        // Create a new surface for rendering.
+        // Create a new surface for rendering, on the canvas of the window on
+        // the web.
+        #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+        let target = wgpu::SurfaceTarget::from(window.clone());
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        let target = wgpu::SurfaceTarget::Canvas(crate::web::canvas(window)?);
         let surface = instance
-            .create_surface(window.clone())
-            .expect("Failed to create surface");
+            .create_surface(target)
+            .map_err(|e| DragonflyError::SurfaceCreation(e.to_string()))?;
 
-        // Request a graphics adapter from the wgpu instance.
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
+        // Request a graphics adapter from the wgpu instance, falling back to
+        // any adapter if none matches the power preference.
+        let request_adapter = |power_preference| {
+            instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference,
                 compatible_surface: Some(&surface),
                 force_fallback_adapter: false,
             })
-            .await
-            .expect("Failed to create adapter");
+        };
+        let adapter = match request_adapter(context_config.power_preference).await {
+            Some(adapter) => adapter,
+            None if context_config.power_preference != wgpu::PowerPreference::None => {
+                log::warn!(
+                    "No adapter matches the power preference {:?}, falling back to any adapter",
+                    context_config.power_preference
+                );
+                request_adapter(wgpu::PowerPreference::None)
+                    .await
+                    .ok_or(DragonflyError::AdapterRequest)?
+            }
+            None => return Err(DragonflyError::AdapterRequest),
+        };
 
         // Request a logical device and command queue from the adapter with
-        // no extra features and default limits.
+        // default limits, and the timestamp queries if they are supported to
+        // measure the frames. The sample counts specific to the adapter are
+        // enabled for the multisampling if they are supported, and the line
+        // polygon mode for the wireframe.
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    required_features: wgpu::Features::empty(),
+                    required_features: adapter.features()
+                        & (wgpu::Features::TIMESTAMP_QUERY
+                            | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
+                            | wgpu::Features::POLYGON_MODE_LINE),
                     required_limits: wgpu::Limits::default(),
                     label: None,
                     memory_hints: wgpu::MemoryHints::default(),
                 },
                 None, // Trace path
             )
-            .await
-            .unwrap();
+            .await?;
+
+        // Raise a flag when the device is lost so that the application can
+        // rebuild the context. Dropping the device also invokes the callback,
+        // which is not a loss.
+        let device_lost = Arc::new(AtomicBool::new(false));
+        let device_lost_flag = device_lost.clone();
+        device.set_device_lost_callback(move |reason, message| match reason {
+            wgpu::DeviceLostReason::Dropped | wgpu::DeviceLostReason::ReplacedCallback => {}
+            _ => {
+                log::error!("Device lost ({:?}): {}", reason, message);
+                device_lost_flag.store(true, Ordering::SeqCst);
+            }
+        });
 
         // Extract the supported/prefered format for the surface.
         let capabilities = surface.get_capabilities(&adapter);
@@ -93,7 +750,26 @@ impl Context {
             .copied()
             .find(wgpu::TextureFormat::is_srgb)
             .or_else(|| capabilities.formats.first().copied())
-            .expect("Failed to get preferred format");
+            .ok_or(DragonflyError::SurfaceConfiguration)?;
+
+        // Every surface supports Fifo.
+        let present_mode = if capabilities
+            .present_modes
+            .contains(&context_config.present_mode)
+        {
+            context_config.present_mode
+        } else {
+            log::error!(
+                "The present mode {:?} is not supported, using Fifo",
+                context_config.present_mode
+            );
+            wgpu::PresentMode::Fifo
+        };
+
+        // The surface cannot be larger than the largest texture.
+        let limits = device.limits();
+        let device_features = device.features();
+        let size = clamp_surface_size(size, limits.max_texture_dimension_2d);
 
         // Configures the surface with the correct format for rendering.
         let config = wgpu::SurfaceConfiguration {
@@ -101,113 +777,1068 @@ impl Context {
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: wgpu::PresentMode::default(),
+            present_mode,
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
             view_formats: vec![],
             desired_maximum_frame_latency: 1,
         };
 
-        // Create a shader module from a shader written in WGSL.
-        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/shader.wgsl"));
-
-        // Create the render pipeline layout.
-        let render_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &[],
-                push_constant_ranges: &[],
-            });
-
-        // Create the render pipeline.
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: None,
-            layout: Some(&render_pipeline_layout),
-            // Read vertex shader
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc()],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            // Read fragment shader
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            // Set the topology
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
+        // Create the time uniform buffer and its bind group.
+        let time_uniform = TimeUniform::new();
+        let time_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Time Buffer"),
+            contents: bytemuck::bytes_of(&time_uniform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // Create the camera uniform buffer.
+        let camera = Camera2D::default();
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::bytes_of(&CameraUniform::new(&camera)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // Create the transform uniform buffer, starting at the identity.
+        let transform = TransformUniform::default();
+        let transform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Transform Buffer"),
+            contents: bytemuck::bytes_of(&transform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let uniform_bind_group_layout = create_uniform_bind_group_layout(&device);
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Uniform Bind Group"),
+            layout: &uniform_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: time_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: camera_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: transform_buffer.as_entire_binding(),
+                },
+            ],
         });
 
+        // Pick the number of samples per pixel closest to the requested one.
+        let sample_counts = supported_sample_counts(&adapter, &device, config.format);
+        let sample_count = choose_sample_count(context_config.msaa_samples, &sample_counts);
+        if sample_count != context_config.msaa_samples {
+            log::warn!(
+                "{} samples per pixel are not supported, rendering with {}",
+                context_config.msaa_samples,
+                sample_count
+            );
+        }
+
+        // Create the render pipelines for the surface format.
+        // The pipelines share their layout and shader module.
+        let pipeline_layout = create_pipeline_layout(&device, &uniform_bind_group_layout);
+        let (layout, format) = (&pipeline_layout, config.format);
+        let shader = create_shader(&device);
+        let cull_mode = Some(wgpu::Face::Back);
+        let render_pipeline =
+            create_figure_pipeline(&device, layout, format, sample_count, &shader, cull_mode);
+        let edge_pipeline = create_edge_pipeline(&device, layout, format, sample_count, &shader);
+        let wireframe_pipeline =
+            create_wireframe_pipeline(&device, layout, format, sample_count, &shader);
+        let polygon_line_pipeline =
+            create_polygon_line_pipeline(&device, layout, format, sample_count, &shader);
+        let grid_pipeline = create_grid_pipeline(&device, layout, format, sample_count, &shader);
+        let msaa_texture = create_msaa_texture(&device, &config, sample_count);
+        let depth_texture =
+            create_depth_texture(&device, config.width, config.height, sample_count);
+
         // Set the initial figure
         let fig_idx = 0;
-        let figure = vertex::Figure::get_figure(fig_idx);
+        let figure = vertex::Figure::ALL[fig_idx as usize].clone();
         let vertices = figure.get_vertices();
-        let indices = figure.get_indices();
+        let indices = figure.get_index_data();
+        let edge_indices = figure.get_edge_indices();
+        let wireframe_indices = figure.get_wireframe_indices();
 
-        // Create the vertex and index buffers
+        // Create the vertex and index buffers, the vertex colors being
+        // rewritten to highlight the figure.
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Vertex Buffer"),
             contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
         });
 
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Index Buffer"),
-            contents: bytemuck::cast_slice(&indices),
+            contents: indices.as_bytes(),
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let edge_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Edge Index Buffer"),
+            contents: bytemuck::cast_slice(&edge_indices),
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let wireframe_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Wireframe Index Buffer"),
+            contents: bytemuck::cast_slice(&wireframe_indices),
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // Every figure is uploaded once, to switch between them without
+        // writing to the buffers.
+        let atlas = atlas::figure_atlas();
+        let atlas_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Atlas Vertex Buffer"),
+            contents: bytemuck::cast_slice(&atlas.vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let atlas_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Atlas Index Buffer"),
+            contents: bytemuck::cast_slice(&atlas.indices),
             usage: wgpu::BufferUsages::INDEX,
         });
+        let atlas_range = atlas.range(fig_idx as usize).cloned();
 
-        Self {
+        // The grid is disabled until set.
+        let grid_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grid Vertex Buffer"),
+            size: 0,
+            usage: wgpu::BufferUsages::VERTEX,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
             surface,
             device,
             queue,
             config,
+            depth_texture,
+            sample_count,
+            sample_counts,
+            msaa_texture,
+            pipeline_layout,
+            shader,
+            custom_shader: None,
+            cull_mode,
             size,
             render_pipeline,
+            edge_pipeline,
+            wireframe_pipeline,
+            polygon_line_pipeline,
+            grid_pipeline,
+            #[cfg(feature = "hot-reload")]
+            shader_path: None,
+            #[cfg(feature = "hot-reload")]
+            shader_modified: None,
+            #[cfg(feature = "hot-reload")]
+            shader_missing: false,
+
+            time_uniform,
+            time_buffer,
+            camera,
+            camera_buffer,
+            transform,
+            transform_buffer,
+            uniform_bind_group,
+            uniform_bind_group_layout,
+
+            background: wgpu::Color::WHITE,
 
-            fig_idx,
+            device_lost,
+
+            figure: Some(figure.clone()),
+            animation: None,
 
             vertex_buffer,
             num_vertices: vertices.len() as u32,
+            bounds: figure.bounds(),
+            vertices,
+            highlight: false,
+            gradient: None,
+            fit_meshes: true,
 
             index_buffer,
             num_indices: indices.len() as u32,
-        }
+            index_format: indices.format(),
+
+            edge_index_buffer,
+            num_edge_indices: edge_indices.len() as u32,
+            outline_enabled: false,
+
+            wireframe_index_buffer,
+            num_wireframe_indices: wireframe_indices.len() as u32,
+            wireframe_enabled: false,
+
+            atlas,
+            atlas_vertex_buffer,
+            atlas_index_buffer,
+            atlas_range,
+
+            grid: None,
+            grid_vertex_buffer,
+            num_grid_vertices: 0,
+
+            adapter_info: adapter.get_info(),
+            adapter_features: adapter.features(),
+            device_features,
+
+            limits,
+            present_modes: capabilities.present_modes,
+
+            frame_capture: false,
+            captured_frame: None,
+
+            frame_stats: FrameStatsHistory::default(),
+            gpu_timer: None,
+        })
+    }
+
+    /// Returns the information of the adapter the device was requested from:
+    /// its name, vendor, driver and backend.
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// Returns whether the adapter supports all the given features, for
+    /// instance to enable a rendering mode only where it is available.
+    pub fn supports_feature(&self, feature: wgpu::Features) -> bool {
+        self.adapter_features.contains(feature)
+    }
+
+    /// Returns the features enabled on the device.
+    pub fn device_features(&self) -> wgpu::Features {
+        self.device_features
+    }
+
+    /// Returns the limits of the device.
+    pub fn device_limits(&self) -> wgpu::Limits {
+        self.limits.clone()
     }
 
     /// Resizes the graphics context for the given window size.
     ///
-    /// The `device` and `surface` fields are updated for the new window size.
+    /// The `device` and `surface` fields are updated for the new window size,
+    /// and the grid is regenerated to fill the new viewport. A size larger
+    /// than the maximum texture dimension of the device is clamped, and an
+    /// error is logged, instead of failing the validation.
     pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+        let max_dimension = self.limits.max_texture_dimension_2d;
+        let clamped_size = clamp_surface_size(new_size, max_dimension);
+        if clamped_size != new_size {
+            log::error!(
+                "The window size {}x{} exceeds the maximum texture dimension {}, the surface is clamped to {}x{}",
+                new_size.width,
+                new_size.height,
+                max_dimension,
+                clamped_size.width,
+                clamped_size.height
+            );
+        }
+        let new_size = clamped_size;
+
         // Update config and surface for new window size.
         if new_size.width > 0 && new_size.height > 0 {
             self.size = new_size;
             self.config.width = new_size.width;
             self.config.height = new_size.height;
             self.surface.configure(&self.device, &self.config);
+            self.create_targets();
+            self.update_grid();
+            self.update_circle_detail();
+        }
+    }
+
+    /// Returns the diameter in pixels of a circle figure on the window, along
+    /// the wider axis, as seen through the camera.
+    fn circle_pixel_diameter(&self) -> u32 {
+        // A circle is 1 unit across, and the clip space 2 units.
+        let window = self.size.width.max(self.size.height) as f32;
+        (self.camera.zoom * window / 2.0).round() as u32
+    }
+
+    /// Returns the figure tessellated for the window: a circle with enough
+    /// segments for its size on screen, or any other figure unchanged.
+    fn level_of_detail(&self, figure: vertex::Figure) -> vertex::Figure {
+        match figure {
+            vertex::Figure::Circle(_) => {
+                vertex::Figure::circle_for_resolution(self.circle_pixel_diameter())
+            }
+            figure => figure,
+        }
+    }
+
+    /// Tessellates the current figure again if it is a circle whose
+    /// segments do not suit the size of the window anymore.
+    fn update_circle_detail(&mut self) {
+        if let Some(figure @ vertex::Figure::Circle(_)) = &self.figure {
+            let detailed = self.level_of_detail(figure.clone());
+            if detailed != *figure {
+                self.set_mesh_unfitted(&detailed);
+                self.figure = Some(detailed);
+            }
+        }
+    }
+
+    /// Compiles a WGSL shader for the figures, to draw them with
+    /// `set_shader`.
+    ///
+    /// The shader must have the `vs_main` vertex entry point and the
+    /// `fs_main` fragment entry point of `shaders/shader.wgsl`, taking the
+    /// same vertices and uniforms. A pipeline is built to check them, so the
+    /// shader can be set on any context of the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns the validation error if the shader does not compile, or if an
+    /// entry point is missing or does not match the vertices, the uniforms
+    /// or the render target.
+    pub fn with_custom_shader(
+        device: &wgpu::Device,
+        source: &str,
+    ) -> Result<wgpu::ShaderModule, wgpu::Error> {
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Custom Shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        create_figure_pipeline(
+            device,
+            &create_pipeline_layout(device, &create_uniform_bind_group_layout(device)),
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            1,
+            &shader,
+            Some(wgpu::Face::Back),
+        );
+        match pollster::block_on(device.pop_error_scope()) {
+            Some(e) => Err(e),
+            None => Ok(shader),
+        }
+    }
+
+    /// Draws the figures with the given shader, compiled by
+    /// `with_custom_shader`.
+    ///
+    /// Only the shader of the render pipeline changes; its topology,
+    /// blending and culling are kept, and the outline, wireframe and grid
+    /// keep the built-in shader.
+    pub fn set_shader(&mut self, shader: wgpu::ShaderModule) {
+        self.render_pipeline = create_figure_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            self.config.format,
+            self.sample_count,
+            &shader,
+            self.cull_mode,
+        );
+        self.custom_shader = Some(shader);
+    }
+
+    /// Sets the faces of the triangles of the figure that are not drawn,
+    /// the back faces by default, or `None` to draw both faces.
+    ///
+    /// The render pipeline is rebuilt with its current shader module and the
+    /// shared layout, neither of which is created again.
+    pub fn set_cull_mode(&mut self, cull_mode: Option<wgpu::Face>) {
+        self.cull_mode = cull_mode;
+        self.render_pipeline = create_figure_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            self.config.format,
+            self.sample_count,
+            self.custom_shader.as_ref().unwrap_or(&self.shader),
+            cull_mode,
+        );
+    }
+
+    /// Sets the number of samples per pixel of the multisample antialiasing,
+    /// 1 to disable it, and returns the number used.
+    ///
+    /// An unsupported count falls back to the largest supported count below
+    /// it, down to 1, and a warning is logged. The pipelines and the render
+    /// targets are rebuilt if the count changes.
+    pub fn set_sample_count(&mut self, requested: u32) -> u32 {
+        let sample_count = choose_sample_count(requested, &self.sample_counts);
+        if sample_count != requested {
+            log::warn!(
+                "{} samples per pixel are not supported, rendering with {}",
+                requested,
+                sample_count
+            );
+        }
+        if sample_count == self.sample_count {
+            return sample_count;
+        }
+
+        self.sample_count = sample_count;
+        let (device, layout, format, shader) = (
+            &self.device,
+            &self.pipeline_layout,
+            self.config.format,
+            &self.shader,
+        );
+        let figure_shader = self.custom_shader.as_ref().unwrap_or(shader);
+        self.render_pipeline = create_figure_pipeline(
+            device,
+            layout,
+            format,
+            sample_count,
+            figure_shader,
+            self.cull_mode,
+        );
+        self.edge_pipeline = create_edge_pipeline(device, layout, format, sample_count, shader);
+        self.wireframe_pipeline =
+            create_wireframe_pipeline(device, layout, format, sample_count, shader);
+        self.polygon_line_pipeline =
+            create_polygon_line_pipeline(device, layout, format, sample_count, shader);
+        self.grid_pipeline = create_grid_pipeline(device, layout, format, sample_count, shader);
+        self.create_targets();
+        sample_count
+    }
+
+    /// Creates the multisampled texture and the depth buffer again, for the
+    /// current size of the surface and number of samples.
+    fn create_targets(&mut self) {
+        self.msaa_texture = create_msaa_texture(&self.device, &self.config, self.sample_count);
+        self.depth_texture = create_depth_texture(
+            &self.device,
+            self.config.width,
+            self.config.height,
+            self.sample_count,
+        );
+    }
+
+    /// Rebuilds the render pipeline from `shader_path` if the file changed
+    /// since it was last read, and returns whether the pipeline was rebuilt.
+    ///
+    /// The file is polled through its modification time. If it cannot be
+    /// read or does not compile, the error is logged and the current pipeline
+    /// is kept until the file changes again. The shader is set as with
+    /// `set_shader`.
+    #[cfg(feature = "hot-reload")]
+    pub fn try_reload_shader(&mut self) -> bool {
+        let Some(path) = self.shader_path.clone() else {
+            return false;
+        };
+        let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified());
+        match modified {
+            Ok(modified) if self.shader_modified != Some(modified) => {
+                self.shader_modified = Some(modified);
+                self.shader_missing = false;
+            }
+            Ok(_) => return false,
+            Err(e) => {
+                if !self.shader_missing {
+                    log::error!("Failed to read the shader {:?}: {}", path, e);
+                    self.shader_missing = true;
+                }
+                return false;
+            }
+        }
+
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                log::error!("Failed to read the shader {:?}: {}", path, e);
+                return false;
+            }
+        };
+        match Context::with_custom_shader(&self.device, &source) {
+            Ok(module) => {
+                log::info!("Reloaded the shader {:?}", path);
+                self.set_shader(module);
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to compile the shader {:?}: {}", path, e);
+                false
+            }
+        }
+    }
+
+    /// Sets the present mode of the surface, to enable or disable the vertical
+    /// synchronization at runtime.
+    ///
+    /// The surface is reconfigured immediately. A mode the surface does not
+    /// support is ignored, and an error is logged, instead of failing the
+    /// validation.
+    pub fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
+        if !self.present_modes.contains(&mode) {
+            log::error!(
+                "The present mode {:?} is not supported, keeping {:?}",
+                mode,
+                self.config.present_mode
+            );
+            return;
+        }
+
+        self.config.present_mode = mode;
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    /// Copies every rendered frame back to the CPU, in `captured_frame`.
+    ///
+    /// The surface is reconfigured so that its textures can be copied.
+    pub fn enable_frame_capture(&mut self) {
+        self.frame_capture = true;
+        self.config.usage |= wgpu::TextureUsages::COPY_SRC;
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    /// Renders a frame and returns its pixels in 8-bit RGBA, row by row from
+    /// the top.
+    ///
+    /// The frame is presented as usual. The frames after it are only
+    /// captured if `enable_frame_capture` was called.
+    pub fn capture_frame(&mut self) -> Result<Vec<u8>, DragonflyError> {
+        let frame_capture = self.frame_capture;
+        if !frame_capture {
+            self.enable_frame_capture();
+        }
+        let result = self.render();
+        self.frame_capture = frame_capture;
+        result?;
+
+        let mut pixels = self.captured_frame.take().unwrap_or_default();
+        if matches!(
+            self.config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        ) {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Renders a frame and saves it as a PNG image at the given path.
+    pub fn save_screenshot(&mut self, path: &Path) -> Result<(), DragonflyError> {
+        let pixels = self.capture_frame()?;
+        snapshot::write_png(path, self.config.width, self.config.height, &pixels)?;
+        Ok(())
+    }
+
+    /// Renders a mesh and returns the frame as an image, to be saved with
+    /// `RgbaImage::save`.
+    ///
+    /// The mesh replaces the current figure, as with `set_mesh`. The frame
+    /// is captured as with `capture_frame`.
+    #[cfg(feature = "image")]
+    pub fn render_headless_to_image(
+        &mut self,
+        figure: &dyn Mesh,
+    ) -> Result<image::RgbaImage, DragonflyError> {
+        self.set_mesh(figure);
+        let pixels = self.capture_frame()?;
+        Ok(
+            image::RgbaImage::from_raw(self.config.width, self.config.height, pixels)
+                .expect("the captured frame has the size of the surface"),
+        )
+    }
+
+    /// Measures the time the GPU spends rendering each frame, in the
+    /// `gpu_ms` of the frame statistics.
+    ///
+    /// Each frame then waits for the GPU to read back the timestamps.
+    /// Returns whether the device supports the timestamp queries, the GPU
+    /// time being left out otherwise.
+    pub fn enable_gpu_timing(&mut self) -> bool {
+        self.gpu_timer = GpuTimer::new(&self.device, &self.queue);
+        self.gpu_timer.is_some()
+    }
+
+    /// Returns the minimum, maximum and average statistics of the last
+    /// rendered frames.
+    pub fn stats_summary(&self) -> StatsSummary {
+        self.frame_stats.summary()
+    }
+
+    /// Returns the number of draw calls of the last rendered frame, or 0
+    /// before the first one.
+    pub fn num_draw_calls(&self) -> u32 {
+        self.frame_stats.last().map_or(0, |stats| stats.draw_calls)
+    }
+
+    /// Sets the color the render target is cleared with.
+    pub fn set_background(&mut self, r: f64, g: f64, b: f64, a: f64) {
+        self.background = wgpu::Color { r, g, b, a };
+    }
+
+    /// Returns whether the device has been lost.
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::SeqCst)
+    }
+
+    /// Sets the figure at the given index as the current figure.
+    ///
+    /// The vertices and indices of the figure are generated and written to
+    /// the vertex and index buffers. A circle is tessellated with enough
+    /// segments for the size of the window, see
+    /// `Figure::circle_for_resolution`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no figure at this index, in which case
+    /// the current figure is left unchanged.
+    pub fn set_figure(&mut self, fig_idx: u8) -> Result<(), vertex::InvalidFigureIndex> {
+        let figure = self.level_of_detail(vertex::Figure::try_from(fig_idx)?);
+        self.animation = None;
+        if !self.show_from_atlas(fig_idx, &figure) {
+            self.set_mesh_unfitted(&figure);
+        }
+        self.figure = Some(figure);
+        Ok(())
+    }
+
+    /// Sets the mesh rendered on the window, which may be any type
+    /// implementing `Mesh`.
+    ///
+    /// The vertices and indices of the mesh are generated and written to the
+    /// vertex and index buffers, which are only reallocated when the mesh
+    /// does not fit, see `write_growing_buffer`. The current figure is
+    /// cleared, so the mesh is not tessellated again when the window is
+    /// resized.
+    ///
+    /// Unless `fit_meshes` is disabled, the mesh is scaled and translated to
+    /// fit the view, as with `MeshData::normalized`, so that a mesh imported
+    /// in any units shows up on screen.
+    ///
+    /// The outline and the wireframe are only generated for a mesh with
+    /// 16-bit indices.
+    pub fn set_mesh(&mut self, mesh: &dyn Mesh) {
+        self.upload_from(mesh, self.fit_meshes);
+    }
+
+    /// Sets the mesh rendered on the window as `set_mesh`, but never fits it
+    /// to the view, for a mesh already in the space of the figures, such as
+    /// a morph between two figures.
+    pub fn set_mesh_unfitted(&mut self, mesh: &dyn Mesh) {
+        self.upload_from(mesh, false);
+    }
+
+    /// Enables or disables the fitting of the meshes set with `set_mesh` to
+    /// the view. The current mesh is left unchanged.
+    pub fn set_fit_meshes(&mut self, enabled: bool) {
+        self.fit_meshes = enabled;
+    }
+
+    /// Generates the vertices and indices of a mesh, fitted to the view or
+    /// not, and uploads them.
+    fn upload_from(&mut self, mesh: &dyn Mesh, fit: bool) {
+        self.figure = None;
+        let (mut vertices, indices) = {
+            crate::profile_scope!("mesh");
+            (mesh.get_vertices(), mesh.get_index_data())
+        };
+        let mut bounds = mesh.bounds();
+        if fit {
+            // A uniform positive scale leaves the normals unchanged.
+            let (offset, factor) = vertex::fit_transform(bounds);
+            let transform = |p: [f32; 3]| std::array::from_fn(|i| (p[i] + offset[i]) * factor);
+            for vertex in &mut vertices {
+                vertex.position = transform(vertex.position);
+            }
+            bounds = (transform(bounds.0), transform(bounds.1));
+        }
+        let (edge_indices, wireframe_indices) = match &indices {
+            vertex::Indices::U16(indices) => {
+                #[cfg(debug_assertions)]
+                if let Err(e) = mesh.validate() {
+                    panic!("Invalid mesh: {}", e);
+                }
+                (
+                    vertex::boundary_edges(&vertices, indices),
+                    mesh.get_wireframe_indices(),
+                )
+            }
+            vertex::Indices::U32(_) => (Vec::new(), Vec::new()),
+        };
+
+        self.upload_mesh(
+            vertices,
+            bounds,
+            indices.as_bytes(),
+            indices.format(),
+            &edge_indices,
+            &wireframe_indices,
+        );
+    }
+
+    /// Sets the figure at the given index as the current figure, from its
+    /// precomputed mesh.
+    ///
+    /// Unlike `set_figure`, only the vertices are copied, to recolor them,
+    /// and the mesh is not validated again. A circle is tessellated for the
+    /// size of the window instead, as in `set_figure`.
+    pub fn set_cached_figure(&mut self, fig_idx: u8, mesh: &CachedMesh) {
+        let figure = vertex::Figure::try_from_index(fig_idx);
+        if let Some(vertex::Figure::Circle(_)) = figure {
+            let _ = self.set_figure(fig_idx);
+            return;
+        }
+
+        if !figure
+            .as_ref()
+            .is_some_and(|figure| self.show_from_atlas(fig_idx, figure))
+        {
+            self.upload_mesh(
+                mesh.vertices.to_vec(),
+                mesh.bounds,
+                bytemuck::cast_slice(&mesh.indices),
+                wgpu::IndexFormat::Uint16,
+                &mesh.edge_indices,
+                &mesh.wireframe_indices,
+            );
+        }
+        self.figure = figure;
+    }
+
+    /// Draws the figure at the given index from the atlas, without writing
+    /// to the GPU, and returns whether it could.
+    ///
+    /// The atlas is not used for a figure tessellated differently from the
+    /// one of `Figure::ALL`, or whose vertices are recolored with the
+    /// highlight or a gradient.
+    fn show_from_atlas(&mut self, fig_idx: u8, figure: &vertex::Figure) -> bool {
+        if self.highlight
+            || self.gradient.is_some()
+            || vertex::Figure::ALL.get(fig_idx as usize) != Some(figure)
+        {
+            return false;
+        }
+        let Some(range) = self.atlas.range(fig_idx as usize).cloned() else {
+            return false;
+        };
+
+        self.vertices = self.atlas.mesh_vertices(&range).to_vec();
+        self.bounds = (self.vertices.as_slice(), &[] as &[u16]).bounds();
+        self.num_vertices = range.vertices.len() as u32;
+        self.num_indices = range.indices.len() as u32;
+        self.index_format = wgpu::IndexFormat::Uint16;
+        self.num_edge_indices = range.edge_indices.len() as u32;
+        self.num_wireframe_indices = range.wireframe_indices.len() as u32;
+        self.atlas_range = Some(range);
+        true
+    }
+
+    /// Copies the figure drawn from the atlas, if any, to the vertex and
+    /// index buffers, so that its vertices can be rewritten.
+    fn leave_atlas(&mut self) {
+        let Some(range) = self.atlas_range.take() else {
+            return;
+        };
+        let indices = |range: &std::ops::Range<u32>| {
+            self.atlas.indices[range.start as usize..range.end as usize].to_vec()
+        };
+        let (triangles, edges, wireframe) = (
+            indices(&range.indices),
+            indices(&range.edge_indices),
+            indices(&range.wireframe_indices),
+        );
+        self.upload_mesh(
+            self.vertices.clone(),
+            self.bounds,
+            bytemuck::cast_slice(&triangles),
+            wgpu::IndexFormat::Uint16,
+            &edges,
+            &wireframe,
+        );
+    }
+
+    /// Writes the vertices and indices of a mesh to the buffers, growing them
+    /// if needed.
+    fn upload_mesh(
+        &mut self,
+        vertices: Vec<Vertex>,
+        bounds: ([f32; 3], [f32; 3]),
+        index_bytes: &[u8],
+        index_format: wgpu::IndexFormat,
+        edge_indices: &[u16],
+        wireframe_indices: &[u16],
+    ) {
+        crate::profile_scope!("upload");
+
+        self.atlas_range = None;
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.vertex_buffer,
+            "Vertex Buffer",
+            wgpu::BufferUsages::VERTEX,
+            bytemuck::cast_slice(&vertices),
+        );
+        self.num_vertices = vertices.len() as u32;
+        self.bounds = bounds;
+        self.vertices = vertices;
+        if self.highlight || self.gradient.is_some() {
+            self.write_vertex_colors();
+        }
+
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.index_buffer,
+            "Index Buffer",
+            wgpu::BufferUsages::INDEX,
+            index_bytes,
+        );
+        let index_size = match index_format {
+            wgpu::IndexFormat::Uint16 => size_of::<u16>(),
+            wgpu::IndexFormat::Uint32 => size_of::<u32>(),
+        };
+        self.num_indices = (index_bytes.len() / index_size) as u32;
+        self.index_format = index_format;
+
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.edge_index_buffer,
+            "Edge Index Buffer",
+            wgpu::BufferUsages::INDEX,
+            bytemuck::cast_slice(edge_indices),
+        );
+        self.num_edge_indices = edge_indices.len() as u32;
+
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.wireframe_index_buffer,
+            "Wireframe Index Buffer",
+            wgpu::BufferUsages::INDEX,
+            bytemuck::cast_slice(wireframe_indices),
+        );
+        self.num_wireframe_indices = wireframe_indices.len() as u32;
+    }
+
+    /// Replaces the vertices of the current mesh, keeping its indices, by
+    /// writing them to its vertex buffer without touching the index buffers,
+    /// for a mesh animated frame by frame.
+    ///
+    /// Returns `false`, leaving the mesh unchanged, if the number of vertices
+    /// differs from the current mesh.
+    pub fn write_vertices(&mut self, vertices: Vec<Vertex>) -> bool {
+        if vertices.len() != self.vertices.len() || self.atlas_range.is_some() {
+            return false;
+        }
+
+        self.bounds = (vertices.as_slice(), &[] as &[u16]).bounds();
+        self.vertices = vertices;
+        self.write_vertex_colors();
+        true
+    }
+
+    /// Displays the figure of an animation, pulsing its colors with
+    /// `update_animation`, or stops the animation in progress.
+    ///
+    /// The animation stops when a figure is selected.
+    pub fn set_animation(&mut self, animation: Option<Animation>) {
+        if let Some(animation) = &animation {
+            self.set_mesh_unfitted(&animation.figure);
+        }
+        self.animation = animation;
+    }
+
+    /// Writes the colors of the animation in progress, if any, at the given
+    /// time in seconds.
+    ///
+    /// The colors are left unchanged if the mesh has been replaced.
+    pub fn update_animation(&mut self, time: f32) {
+        let Some(animation) = &self.animation else {
+            return;
+        };
+        let step = animation.tick(animation.progress(time));
+        if step.len() != self.vertices.len() || self.atlas_range.is_some() {
+            return;
+        }
+        for (vertex, step) in self.vertices.iter_mut().zip(step) {
+            vertex.color = step.color;
         }
+        self.write_vertex_colors();
+    }
+
+    /// Returns whether a point in normalized device coordinates falls inside
+    /// the bounding box of the current mesh, as seen through the camera.
+    ///
+    /// The wave animation is ignored.
+    pub fn hit_test(&self, ndc: [f32; 2]) -> bool {
+        !self.vertices.is_empty() && point_in_bounds(self.camera.to_world(ndc), self.bounds)
+    }
+
+    /// Draws the figure in `HIGHLIGHT_COLOR`, or in its own colors.
+    ///
+    /// The highlight is kept when the mesh changes.
+    pub fn set_highlight(&mut self, highlight: bool) {
+        self.highlight = highlight;
+        self.leave_atlas();
+        self.write_vertex_colors();
+    }
+
+    /// Colors the figure with the given gradient, or with its own colors.
+    ///
+    /// The gradient is kept when the mesh changes.
+    pub fn set_gradient(&mut self, gradient: Option<Gradient>) {
+        self.gradient = gradient;
+        self.leave_atlas();
+        self.write_vertex_colors();
+    }
+
+    /// Writes the vertices to the vertex buffer, colored with the gradient if
+    /// any, and in `HIGHLIGHT_COLOR` if the figure is highlighted.
+    fn write_vertex_colors(&self) {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        let mut colored = self.vertices.clone();
+        if let Some(gradient) = &self.gradient {
+            gradient.apply(&mut colored);
+        }
+        let vertices: Vec<Vertex> = colored
+            .iter()
+            .map(|vertex| Vertex {
+                position: vertex.position,
+                color: if self.highlight {
+                    let [r, g, b] = HIGHLIGHT_COLOR;
+                    [r, g, b, vertex.color[3]]
+                } else {
+                    vertex.color
+                },
+                normal: vertex.normal,
+                tex_coords: vertex.tex_coords,
+            })
+            .collect();
+        self.queue
+            .write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+    }
+
+    /// Enables or disables the outline drawn on top of the figure.
+    pub fn set_outline_enabled(&mut self, enabled: bool) {
+        self.outline_enabled = enabled;
+    }
+
+    /// Draws the figure as a wireframe of its edges, or filled.
+    ///
+    /// The wireframe is drawn with `PolygonMode::Line` if the device
+    /// supports it, and from the line list of the edges otherwise.
+    pub fn set_wireframe_enabled(&mut self, enabled: bool) {
+        self.wireframe_enabled = enabled;
+    }
+
+    /// Sets the grid drawn behind the figure, or removes it with `None`.
+    ///
+    /// The lines of the grid are generated for the current size of the
+    /// surface.
+    pub fn set_grid(&mut self, grid: Option<Grid>) {
+        self.grid = grid;
+        self.update_grid();
+    }
+
+    /// Regenerates the lines of the grid for the current size of the surface
+    /// and uploads them to a new vertex buffer.
+    ///
+    /// The indices of the lines are sequential, so the vertices are drawn
+    /// without an index buffer.
+    fn update_grid(&mut self) {
+        let vertices = match &self.grid {
+            Some(grid) => {
+                grid.generate_mesh(self.size.width as f32, self.size.height as f32)
+                    .0
+            }
+            None => Vec::new(),
+        };
+
+        self.grid_vertex_buffer =
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Grid Vertex Buffer"),
+                    contents: bytemuck::cast_slice(&vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+        self.num_grid_vertices = vertices.len() as u32;
+    }
+
+    /// Multiplies the zoom of the camera by the given factor, within
+    /// `MIN_ZOOM..=MAX_ZOOM`, and writes the camera to the GPU.
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.camera.zoom_by(factor);
+        self.update_camera();
+    }
+
+    /// Centers the camera on the current figure and zooms so that it fills
+    /// `FIT_FRACTION` of the narrower axis of the window, then writes the
+    /// camera to the GPU.
+    ///
+    /// An empty figure resets the camera.
+    pub fn fit_figure_to_window(&mut self) {
+        self.camera = if self.vertices.is_empty() {
+            Camera2D::default()
+        } else {
+            Camera2D::fit(self.bounds, (self.size.width, self.size.height))
+        };
+        self.update_camera();
+    }
+
+    /// Resets the camera to its default state, centered on the origin with a
+    /// zoom of 1, and writes it to the GPU.
+    pub fn reset_camera(&mut self) {
+        self.camera.reset();
+        self.update_camera();
+    }
+
+    /// Moves the center of the camera by the given offset and writes the
+    /// camera to the GPU.
+    pub fn pan(&mut self, delta: [f32; 2]) {
+        self.camera.pan(delta);
+        self.update_camera();
+    }
+
+    /// Zooms the camera by the given factor around the given normalized
+    /// device coordinates, then writes it to the GPU.
+    pub fn zoom_at(&mut self, factor: f32, ndc: [f32; 2]) {
+        self.camera.zoom_at(factor, ndc);
+        self.update_camera();
+    }
+
+    /// Moves the camera with a cursor dragged by the given offset, in
+    /// normalized device coordinates, then writes it to the GPU.
+    pub fn drag(&mut self, delta: [f32; 2]) {
+        self.camera.drag(delta);
+        self.update_camera();
+    }
+
+    /// Sets the matrix applied to the figure before the camera, in
+    /// column-major order, and writes it to the GPU.
+    ///
+    /// The hit test ignores the transform.
+    pub fn set_transform(&mut self, mat: [[f32; 4]; 4]) {
+        self.transform = TransformUniform::new(mat);
+        self.queue.write_buffer(
+            &self.transform_buffer,
+            0,
+            bytemuck::bytes_of(&self.transform),
+        );
+    }
+
+    /// Writes the view-projection matrix of the camera to the GPU.
+    pub fn update_camera(&mut self) {
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::bytes_of(&CameraUniform::new(&self.camera)),
+        );
+    }
+
+    /// Updates the time uniform with the given time and delta time, in
+    /// seconds, and writes it to the GPU.
+    pub fn update_time(&mut self, time: f32, dt: f32) {
+        self.time_uniform.update(time, dt);
+        self.queue
+            .write_buffer(&self.time_buffer, 0, bytemuck::bytes_of(&self.time_uniform));
     }
 
     /// Renders the current figure on the window.
@@ -220,51 +1851,152 @@ impl Context {
     ///
     /// Returns an error if the current frame could not be acquired from the
     /// window.
-    pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        // Get current frame.
-        let frame = self
-            .surface
-            .get_current_texture()
-            .expect("Failed to get texture");
-
-        // Get current texture view.
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        // Create a command encoder to transfer operations.
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-
-        // Clear render
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
-
-            // Render the figure
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
-        }
+    pub fn render(&mut self) -> Result<(), DragonflyError> {
+        let start = crate::Instant::now();
+        let mut stats = FrameStats::default();
+
+        // Get current frame, waiting for the presentation engine.
+        let frame = {
+            crate::profile_scope!("acquire");
+            self.surface.get_current_texture()?
+        };
+
+        // Encode the render commands.
+        let command_buffer = {
+            crate::profile_scope!("encode");
+
+            // Get current texture view.
+            let view = frame
+                .texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            let depth_view = self
+                .depth_texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+
+            // Render to the multisampled texture, resolved to the frame, if
+            // there are several samples per pixel.
+            let msaa_view = self
+                .msaa_texture
+                .as_ref()
+                .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+            let (target, resolve_target, store) = match &msaa_view {
+                Some(msaa_view) => (msaa_view, Some(&view), wgpu::StoreOp::Discard),
+                None => (&view, None, wgpu::StoreOp::Store),
+            };
+
+            // Create a command encoder to transfer operations.
+            let mut encoder = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+            // Clear render
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: target,
+                        resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(self.background),
+                            store,
+                        },
+                    })],
+                    depth_stencil_attachment: Some(depth_attachment(&depth_view)),
+                    occlusion_query_set: None,
+                    timestamp_writes: self.gpu_timer.as_ref().map(GpuTimer::timestamp_writes),
+                });
+
+                render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+
+                // Render the grid behind the figure
+                if self.grid.is_some() && self.num_grid_vertices > 0 {
+                    render_pass.set_pipeline(&self.grid_pipeline);
+                    render_pass.set_vertex_buffer(0, self.grid_vertex_buffer.slice(..));
+                    render_pass.draw(0..self.num_grid_vertices, 0..1);
+                    stats.draw_calls += 1;
+                    stats.total_vertices += self.num_grid_vertices;
+                }
+
+                // Render the figure, filled or as a wireframe, from the atlas
+                // or from its own buffers
+                let atlas = self.atlas_range.as_ref();
+                let (vertex_buffer, base_vertex) = match atlas {
+                    Some(range) => (&self.atlas_vertex_buffer, range.base_vertex()),
+                    None => (&self.vertex_buffer, 0),
+                };
+                let (edge_index_buffer, edge_indices) = match atlas {
+                    Some(range) => (&self.atlas_index_buffer, range.edge_indices.clone()),
+                    None => (&self.edge_index_buffer, 0..self.num_edge_indices),
+                };
+                render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                if let (true, Some(pipeline)) =
+                    (self.wireframe_enabled, &self.polygon_line_pipeline)
+                {
+                    let (index_buffer, indices) = match atlas {
+                        Some(range) => (&self.atlas_index_buffer, range.indices.clone()),
+                        None => (&self.index_buffer, 0..self.num_indices),
+                    };
+                    render_pass.set_pipeline(pipeline);
+                    render_pass.set_index_buffer(index_buffer.slice(..), self.index_format);
+                    render_pass.draw_indexed(indices, base_vertex, 0..1);
+                    stats.total_indices += self.num_indices;
+                } else if self.wireframe_enabled {
+                    let (index_buffer, indices) = match atlas {
+                        Some(range) => (&self.atlas_index_buffer, range.wireframe_indices.clone()),
+                        None => (&self.wireframe_index_buffer, 0..self.num_wireframe_indices),
+                    };
+                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                    render_pass.draw_indexed(indices, base_vertex, 0..1);
+                    stats.total_indices += self.num_wireframe_indices;
+                } else {
+                    let (index_buffer, indices) = match atlas {
+                        Some(range) => (&self.atlas_index_buffer, range.indices.clone()),
+                        None => (&self.index_buffer, 0..self.num_indices),
+                    };
+                    render_pass.set_pipeline(&self.render_pipeline);
+                    render_pass.set_index_buffer(index_buffer.slice(..), self.index_format);
+                    render_pass.draw_indexed(indices, base_vertex, 0..1);
+                    stats.total_indices += self.num_indices;
+                }
+                stats.draw_calls += 1;
+                stats.total_vertices += self.num_vertices;
+
+                // Render the outline on top
+                if self.outline_enabled && self.num_edge_indices > 0 {
+                    render_pass.set_pipeline(&self.edge_pipeline);
+                    render_pass
+                        .set_index_buffer(edge_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+                    render_pass.draw_indexed(edge_indices, base_vertex, 0..1);
+                    stats.draw_calls += 1;
+                    stats.total_vertices += self.num_vertices;
+                    stats.total_indices += self.num_edge_indices;
+                }
+            }
+            if let Some(gpu_timer) = &self.gpu_timer {
+                gpu_timer.resolve(&mut encoder);
+            }
+
+            encoder.finish()
+        };
 
         // Submit the operations
-        self.queue.submit(std::iter::once(encoder.finish()));
+        crate::profile_scope!("present");
+        self.queue.submit(std::iter::once(command_buffer));
+        if self.frame_capture {
+            self.captured_frame = Some(frame_hash::read_texture(
+                &self.device,
+                &self.queue,
+                &frame.texture,
+            ));
+        }
+        if let Some(gpu_timer) = &self.gpu_timer {
+            stats.gpu_ms = gpu_timer.read_ms(&self.device);
+        }
         frame.present();
 
+        stats.frame_ms = start.elapsed().as_secs_f32() * 1000.0;
+        self.frame_stats.push(stats);
         Ok(())
     }
 }
@@ -0,0 +1,132 @@
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use winit::event_loop::EventLoopProxy;
+
+use crate::dialog::{DialogKind, DialogOutcome};
+
+/// A command sent to the application by another process.
+///
+/// Commands are newline-delimited JSON objects tagged by their `command`
+/// field, for example `{"command": "set_figure", "index": 2}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Switches to the next figure.
+    NextFigure,
+    /// Switches to the figure at the given index.
+    SetFigure { index: u8 },
+    /// Exits the application.
+    Quit,
+    /// Delivers the outcome of a file dialog to the event loop.
+    ///
+    /// Sent by the dialog thread, it cannot be read from another process.
+    #[serde(skip)]
+    DialogClosed {
+        kind: DialogKind,
+        outcome: DialogOutcome,
+    },
+}
+
+impl Command {
+    /// Returns the name of the command, as written in the JSON object.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::NextFigure => "next_figure",
+            Command::SetFigure { .. } => "set_figure",
+            Command::Quit => "quit",
+            Command::DialogClosed { .. } => "dialog_closed",
+        }
+    }
+}
+
+/// The result of a command, written as JSON on the standard output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    /// The command was executed.
+    Ok { command: String },
+    /// The command could not be parsed or executed.
+    Error { message: String },
+}
+
+impl Response {
+    /// Returns the JSON representation of the response.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize response")
+    }
+}
+
+/// A destination for the commands read by the listener.
+///
+/// The application uses the event loop proxy so that the commands are
+/// processed on the main thread.
+pub trait CommandSink {
+    /// Sends a command.
+    ///
+    /// Returns `false` if the receiving end is closed.
+    fn send(&self, command: Command) -> bool;
+}
+
+impl CommandSink for EventLoopProxy<Command> {
+    fn send(&self, command: Command) -> bool {
+        self.send_event(command).is_ok()
+    }
+}
+
+/// Parses a command from a line of JSON.
+///
+/// # Errors
+///
+/// Returns an error response if the line is not a valid JSON object or if
+/// the command is unknown.
+pub fn parse_command(line: &str) -> Result<Command, Response> {
+    serde_json::from_str(line).map_err(|e| Response::Error {
+        message: e.to_string(),
+    })
+}
+
+/// Reads commands from the input until it is closed, and sends them to the
+/// sink.
+///
+/// Blank lines are skipped. The commands that cannot be parsed are answered
+/// with an error object on the output. Stops when the sink is closed.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or the output written.
+pub fn listen(
+    input: impl BufRead,
+    sink: &impl CommandSink,
+    mut output: impl Write,
+) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_command(&line) {
+            Ok(command) => {
+                if !sink.send(command) {
+                    break;
+                }
+            }
+            Err(response) => {
+                writeln!(output, "{}", response.to_json())?;
+                output.flush()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawns a thread listening for commands on the standard input.
+pub fn spawn_stdin_listener(proxy: EventLoopProxy<Command>) {
+    std::thread::spawn(move || {
+        if let Err(e) = listen(io::stdin().lock(), &proxy, io::stdout()) {
+            log::error!("Failed to read commands: {:?}", e);
+        }
+    });
+}
@@ -0,0 +1,328 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    control::{Command, CommandSink},
+    spec::{FigureSpec, SceneSpec},
+    vertex::{self, Figure, ImportReport, Mesh, MeshData},
+};
+
+/// A file format the application can read or write.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileFormat {
+    /// The name of the format, displayed in the file dialogs.
+    pub name: &'static str,
+    /// The extensions of the files of the format, without the leading dot.
+    pub extensions: &'static [&'static str],
+}
+
+/// The Wavefront OBJ meshes, read with `Figure::from_obj`.
+pub const OBJ: FileFormat = FileFormat {
+    name: "Wavefront OBJ",
+    extensions: &["obj"],
+};
+
+/// The binary and ASCII STL meshes, read with `MeshData::from_stl`.
+pub const STL: FileFormat = FileFormat {
+    name: "Stereolithography",
+    extensions: &["stl"],
+};
+
+/// The ASCII PLY meshes, read with `MeshData::from_ply`.
+pub const PLY: FileFormat = FileFormat {
+    name: "Polygon File Format",
+    extensions: &["ply"],
+};
+
+/// The figures and scenes saved as JSON, requiring the `serialize` feature.
+pub const JSON: FileFormat = FileFormat {
+    name: "JSON",
+    extensions: &["json"],
+};
+
+/// The outlines of the flat figures, written with `Figure::to_svg`.
+pub const SVG: FileFormat = FileFormat {
+    name: "Scalable Vector Graphics",
+    extensions: &["svg"],
+};
+
+/// The size of the exported SVG documents, in pixels.
+pub const SVG_SIZE_PX: u32 = 512;
+
+/// The meshes, scenes and images that can be opened.
+///
+/// Each importer registers its format here to appear in the open dialog.
+pub const IMPORT_FORMATS: &[FileFormat] = &[OBJ, STL, PLY, JSON];
+
+/// The formats a scene can be saved to.
+pub const SCENE_FORMATS: &[FileFormat] = &[JSON];
+
+/// The formats the current mesh can be exported to.
+///
+/// Each exporter registers its format here to appear in the export dialog.
+pub const EXPORT_FORMATS: &[FileFormat] = &[SVG, JSON];
+
+/// A filter of a file dialog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+    /// The name of the filter.
+    pub name: String,
+    /// The extensions matched by the filter, without the leading dot.
+    pub extensions: Vec<&'static str>,
+}
+
+/// Builds the filters of a file dialog, one per format.
+///
+/// If `include_all` is set and there are several formats, the filters start
+/// with a filter matching every supported extension.
+pub fn file_filters(formats: &[FileFormat], include_all: bool) -> Vec<FileFilter> {
+    let mut filters: Vec<FileFilter> = formats
+        .iter()
+        .map(|format| FileFilter {
+            name: format.name.to_string(),
+            extensions: format.extensions.to_vec(),
+        })
+        .collect();
+
+    if include_all && formats.len() > 1 {
+        let all = FileFilter {
+            name: "All supported files".to_string(),
+            extensions: formats
+                .iter()
+                .flat_map(|format| format.extensions.iter().copied())
+                .collect(),
+        };
+        filters.insert(0, all);
+    }
+
+    filters
+}
+
+/// Returns the format of the given path, according to its extension.
+///
+/// The extension is compared case-insensitively.
+pub fn format_of<'a>(formats: &'a [FileFormat], path: &Path) -> Option<&'a FileFormat> {
+    let extension = path.extension()?.to_str()?;
+    formats.iter().find(|format| {
+        format
+            .extensions
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+    })
+}
+
+/// The file dialogs of the application.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DialogKind {
+    /// Opens a mesh, a scene or an image (`Ctrl+O`).
+    Open,
+    /// Saves the scene (`Ctrl+S`).
+    SaveScene,
+    /// Exports the current mesh (`Ctrl+Shift+S`).
+    ExportMesh,
+}
+
+impl DialogKind {
+    /// Returns the title of the dialog.
+    pub fn title(&self) -> &'static str {
+        match self {
+            DialogKind::Open => "Open",
+            DialogKind::SaveScene => "Save scene",
+            DialogKind::ExportMesh => "Export mesh",
+        }
+    }
+
+    /// Returns the formats the dialog can pick.
+    pub fn formats(&self) -> &'static [FileFormat] {
+        match self {
+            DialogKind::Open => IMPORT_FORMATS,
+            DialogKind::SaveScene => SCENE_FORMATS,
+            DialogKind::ExportMesh => EXPORT_FORMATS,
+        }
+    }
+
+    /// Returns the filters of the dialog.
+    ///
+    /// Only the open dialog has a filter matching every supported extension,
+    /// the save dialogs need a single format.
+    pub fn filters(&self) -> Vec<FileFilter> {
+        file_filters(self.formats(), *self == DialogKind::Open)
+    }
+}
+
+/// The outcome of a file dialog.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogOutcome {
+    /// A file was picked.
+    Picked(PathBuf),
+    /// The dialog was closed without picking a file.
+    Cancelled,
+    /// The dialog could not be shown.
+    Failed(String),
+}
+
+/// Resolves the outcome of a dialog to the picked file and its format.
+///
+/// Returns `Ok(None)` if the dialog was cancelled.
+///
+/// # Errors
+///
+/// Returns an error message if the dialog failed or if the extension of the
+/// picked file is not supported by the dialog.
+pub fn resolve_outcome(
+    kind: DialogKind,
+    outcome: DialogOutcome,
+) -> Result<Option<(PathBuf, FileFormat)>, String> {
+    match outcome {
+        DialogOutcome::Cancelled => Ok(None),
+        DialogOutcome::Failed(message) => Err(message),
+        DialogOutcome::Picked(path) => match format_of(kind.formats(), &path) {
+            Some(format) => Ok(Some((path, *format))),
+            None => Err(format!("Unsupported file {:?}", path)),
+        },
+    }
+}
+
+/// Reads the mesh of a file picked in the open dialog, in the given format.
+///
+/// A JSON file holds a figure, or a scene whose first figure is read.
+///
+/// The mesh is validated, and repaired if `repair` is set. The report of
+/// the mesh as read from the file is returned along with it.
+///
+/// # Errors
+///
+/// Returns an error message if the file cannot be read or parsed, or if the
+/// format cannot be opened.
+pub fn read_mesh(
+    path: &Path,
+    format: &FileFormat,
+    repair: bool,
+) -> Result<(MeshData, ImportReport), String> {
+    let open = || std::fs::File::open(path).map_err(|e| e.to_string());
+    let mesh = if *format == OBJ {
+        Figure::from_obj(path).map_err(|e| e.to_string())?
+    } else if *format == STL {
+        MeshData::from_stl(open()?).map_err(|e| e.to_string())?
+    } else if *format == PLY {
+        MeshData::from_ply(open()?).map_err(|e| e.to_string())?
+    } else if *format == JSON {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        figure_from_json(&json)?.mesh_data()
+    } else {
+        return Err(format!("{} files cannot be opened", format.name));
+    };
+    Ok(vertex::check_import(mesh, repair))
+}
+
+/// Saves the figure as a scene, in the given format.
+///
+/// # Errors
+///
+/// Returns an error message if the file cannot be written, or if the scene
+/// cannot be saved in the format.
+pub fn write_scene(figure: &Figure, path: &Path, format: &FileFormat) -> Result<(), String> {
+    let contents = if *format == JSON {
+        scene_to_json(&SceneSpec {
+            figures: vec![FigureSpec::from(figure.clone())],
+        })?
+    } else {
+        return Err(format!("Scenes cannot be saved as {} files", format.name));
+    };
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Exports the figure, in the given format.
+///
+/// # Errors
+///
+/// Returns an error message if the file cannot be written, or if the figure
+/// cannot be exported in the format.
+pub fn write_figure(figure: &Figure, path: &Path, format: &FileFormat) -> Result<(), String> {
+    let contents = if *format == SVG {
+        figure.to_svg(SVG_SIZE_PX).map_err(|e| e.to_string())?
+    } else if *format == JSON {
+        figure_to_json(figure)?
+    } else {
+        return Err(format!(
+            "Figures cannot be exported as {} files",
+            format.name
+        ));
+    };
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Parses a figure, or the first figure of a scene, from its JSON
+/// representation.
+#[cfg(feature = "serialize")]
+fn figure_from_json(json: &str) -> Result<Figure, String> {
+    Figure::from_json(json).or_else(|e| {
+        SceneSpec::from_json(json)
+            .map_err(|_| e.to_string())?
+            .figures
+            .into_iter()
+            .next()
+            .map(Figure::from)
+            .ok_or_else(|| "The scene has no figure".to_string())
+    })
+}
+
+#[cfg(not(feature = "serialize"))]
+fn figure_from_json(_json: &str) -> Result<Figure, String> {
+    Err(NO_SERIALIZE.to_string())
+}
+
+#[cfg(feature = "serialize")]
+fn figure_to_json(figure: &Figure) -> Result<String, String> {
+    Ok(figure.to_json())
+}
+
+#[cfg(not(feature = "serialize"))]
+fn figure_to_json(_figure: &Figure) -> Result<String, String> {
+    Err(NO_SERIALIZE.to_string())
+}
+
+#[cfg(feature = "serialize")]
+fn scene_to_json(scene: &SceneSpec) -> Result<String, String> {
+    Ok(scene.to_json())
+}
+
+#[cfg(not(feature = "serialize"))]
+fn scene_to_json(_scene: &SceneSpec) -> Result<String, String> {
+    Err(NO_SERIALIZE.to_string())
+}
+
+/// The error of the JSON files without the `serialize` feature.
+#[cfg(not(feature = "serialize"))]
+const NO_SERIALIZE: &str = "The application is built without the serialize feature";
+
+/// Runs a dialog on a separate thread and sends its outcome to the sink as
+/// a `Command::DialogClosed`, so that the render loop never blocks.
+///
+/// `show` displays the dialog and waits for its outcome.
+pub fn spawn_dialog<S, F>(kind: DialogKind, sink: S, show: F) -> std::thread::JoinHandle<()>
+where
+    S: CommandSink + Send + 'static,
+    F: FnOnce(DialogKind) -> DialogOutcome + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let outcome = show(kind);
+        sink.send(Command::DialogClosed { kind, outcome });
+    })
+}
+
+/// Shows the native file dialog of the given kind and waits for its outcome.
+///
+/// The dialog lists the filters of its kind. Closing it without picking a
+/// file is reported as `DialogOutcome::Cancelled`.
+#[cfg(feature = "dialogs")]
+pub fn show_native_dialog(kind: DialogKind) -> DialogOutcome {
+    let dialog = kind.filters().into_iter().fold(
+        rfd::FileDialog::new().set_title(kind.title()),
+        |dialog, filter| dialog.add_filter(filter.name, &filter.extensions),
+    );
+    let path = match kind {
+        DialogKind::Open => dialog.pick_file(),
+        DialogKind::SaveScene | DialogKind::ExportMesh => dialog.save_file(),
+    };
+    path.map_or(DialogOutcome::Cancelled, DialogOutcome::Picked)
+}
@@ -1,17 +1,154 @@
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
 
-use dragonfly::vertex::{self, Mesh};
-use pollster;
-
-use wgpu::util::DeviceExt;
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
-    event_loop::ActiveEventLoop,
+    event::{StartCause, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
     window::{Window, WindowId},
 };
 
-use crate::context::Context;
+use crate::{
+    arena::FrameArena,
+    config::ContextConfig,
+    context::Context,
+    control::{Command, Response},
+    dialog::{self, DialogKind, DialogOutcome},
+    error::DragonflyError,
+    frame_hash,
+    grid::Grid,
+    input::InputEvent,
+    pool::{self, CachedMesh, ObjectPool},
+    profiler,
+    state::{AppEvent, AppState},
+    stats::FRAME_STATS_CAPACITY,
+    vertex::{self, Figure, Gradient, GradientDirection, Mesh, MeshData},
+    Instant,
+};
+
+/// The background colors cycled through with the `C` key: white, black,
+/// navy, charcoal and light grey.
+pub const BACKGROUND_PRESETS: &[wgpu::Color] = &[
+    wgpu::Color::WHITE,
+    wgpu::Color::BLACK,
+    wgpu::Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.5,
+        a: 1.0,
+    },
+    wgpu::Color {
+        r: 0.21,
+        g: 0.27,
+        b: 0.31,
+        a: 1.0,
+    },
+    wgpu::Color {
+        r: 0.83,
+        g: 0.83,
+        b: 0.83,
+        a: 1.0,
+    },
+];
+
+/// The gradients cycled through with `Shift+G`, after the own colors of
+/// the figures: blue to orange from bottom to top, and white to purple from
+/// the center.
+pub const GRADIENT_PRESETS: &[Gradient] = &[
+    Gradient {
+        from: [0.1, 0.2, 0.9],
+        to: [0.9, 0.6, 0.1],
+        direction: GradientDirection::Vertical,
+    },
+    Gradient {
+        from: [1.0, 1.0, 1.0],
+        to: [0.5, 0.1, 0.6],
+        direction: GradientDirection::Radial,
+    },
+];
+
+/// The faces of the triangles not drawn, cycled through with `Shift+C`:
+/// the back faces, the front faces, then none.
+pub const CULL_MODES: &[Option<wgpu::Face>] =
+    &[Some(wgpu::Face::Back), Some(wgpu::Face::Front), None];
+
+/// The duration of a morph between two figures, in seconds.
+pub const MORPH_DURATION: f32 = 1.0;
+
+/// The duration of the morph between two figures cycled through with
+/// `Space` in the transition mode, in seconds.
+pub const TRANSITION_DURATION: f32 = 0.3;
+
+/// The speed the figure spins at while the spin is enabled, in radians per
+/// second.
+pub const SPIN_SPEED: f32 = std::f32::consts::FRAC_PI_2;
+
+/// Returns the rotation of the figure after spinning for the given duration
+/// in seconds, wrapped between 0 and 2π.
+pub fn spin_angle(elapsed: f32) -> f32 {
+    (elapsed * SPIN_SPEED).rem_euclid(std::f32::consts::TAU)
+}
+
+/// The default distance the camera pans per frame while an arrow key is held.
+pub const DEFAULT_PAN_SPEED: f32 = 0.01;
+
+/// The frame rate the pan speed is expressed at.
+const PAN_FRAME_RATE: f32 = 60.0;
+
+/// A morph from a mesh to a figure, driven by the animation time.
+#[derive(Debug)]
+struct Morph {
+    /// The mesh displayed when the morph started.
+    from: MeshData,
+    /// The index of the figure displayed when the morph ends.
+    to_idx: u8,
+    /// The animation time of the first frame of the morph, in seconds.
+    start: Option<f32>,
+    /// The duration of the morph, in seconds.
+    duration: f32,
+}
+
+/// The number of frames averaged by the FPS counter.
+const FPS_SAMPLES: usize = 60;
+
+/// A frame rate counter averaging the duration of the last frames.
+///
+/// The rolling average prevents the displayed value from flickering.
+#[derive(Debug, Default)]
+pub struct FpsCounter {
+    /// The durations of the last frames, oldest first.
+    frame_times: VecDeque<Duration>,
+}
+
+impl FpsCounter {
+    /// Records the duration of a frame, forgetting the oldest one if the
+    /// counter is full.
+    pub fn record(&mut self, frame_time: Duration) {
+        if self.frame_times.len() == FPS_SAMPLES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frame_times.len()
+    }
+
+    /// Returns whether no frame has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frame_times.is_empty()
+    }
+
+    /// Returns the average number of frames per second over the recorded
+    /// frames, or `None` if the recorded duration is zero.
+    pub fn fps(&self) -> Option<f32> {
+        let total: Duration = self.frame_times.iter().sum();
+        if total.is_zero() {
+            return None;
+        }
+        Some(self.frame_times.len() as f32 / total.as_secs_f32())
+    }
+}
 
 /// The application state.
 ///
@@ -28,69 +165,883 @@ pub struct Dragonfly {
     /// The window is the platform-specific structure that holds the window
     /// and its associated resources.
     window: Option<Arc<Window>>,
+
+    /// The instant the application was started.
+    start_time: Option<Instant>,
+
+    /// The instant the last frame was rendered.
+    last_frame: Option<Instant>,
+
+    /// The target duration of a frame.
+    ///
+    /// If `None`, the frames are rendered as fast as possible.
+    frame_duration: Option<Duration>,
+
+    /// The FPS counter displayed in the title bar, if enabled.
+    fps_counter: Option<FpsCounter>,
+
+    /// The number of frames rendered since the frame statistics were last
+    /// printed, if they are printed.
+    stats_frames: Option<usize>,
+
+    /// The meshes of the figures, built once when the application is
+    /// resumed so that cycling through the figures does not rebuild them.
+    figure_pool: Option<ObjectPool<CachedMesh>>,
+
+    /// The state of the application, updated from the inputs.
+    state: AppState,
+
+    /// The arena of the transient CPU mesh data, reset at the start of each
+    /// frame.
+    frame_arena: FrameArena,
+
+    /// The proxy the file dialogs send their outcome to.
+    proxy: Option<EventLoopProxy<Command>>,
+
+    /// Whether the hash of each frame is printed, with a fixed timestep.
+    frame_hash: bool,
+
+    /// The index of the next frame.
+    frame_index: u64,
+
+    /// The error the application exited with, if any.
+    error: Option<DragonflyError>,
+
+    /// The options the context is created with.
+    context_config: ContextConfig,
+    /// The window and the context created in the background on the web,
+    /// until they are picked up.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    #[allow(clippy::type_complexity)]
+    pending_context:
+        std::rc::Rc<std::cell::RefCell<Option<(Arc<Window>, Result<Context, DragonflyError>)>>>,
+    /// The WGSL file the shader of the figures is reloaded from.
+    #[cfg(feature = "hot-reload")]
+    shader_path: Option<std::path::PathBuf>,
+
+    /// The mesh displayed instead of the figures, until the next figure is
+    /// selected.
+    ///
+    /// The mesh is kept to be uploaded again if the context is rebuilt.
+    mesh: Option<MeshData>,
+
+    /// The morph in progress, if any.
+    morph: Option<Morph>,
+
+    /// The animation time spent spinning the figure, in seconds.
+    spin_elapsed: f32,
+
+    /// The distance the camera pans per frame while an arrow key is held.
+    ///
+    /// If `None`, `DEFAULT_PAN_SPEED` is used.
+    pan_speed: Option<f32>,
+
+    /// Whether the defects of the opened meshes are repaired.
+    repair: bool,
+}
+
+impl Dragonfly {
+    /// Limits the frame rate to the given number of frames per second.
+    ///
+    /// A target of 0 frames per second leaves the frame rate unlimited.
+    pub fn with_target_fps(mut self, fps: u32) -> Self {
+        self.frame_duration = match fps {
+            0 => None,
+            fps => Some(Duration::from_secs_f64(1.0 / fps as f64)),
+        };
+        self
+    }
+
+    /// Sets the background color of the window.
+    pub fn with_background(mut self, background: wgpu::Color) -> Self {
+        self.state.background = Some(background);
+        self
+    }
+
+    /// Displays the given mesh instead of the figures, until the space bar
+    /// selects the next figure.
+    pub fn with_mesh(mut self, mesh: &dyn Mesh) -> Self {
+        self.mesh = Some(mesh.mesh_data());
+        self
+    }
+
+    /// Creates an event loop and runs the application until its window is
+    /// closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event loop fails, or the fatal error the
+    /// application exited with.
+    pub fn run(mut self) -> Result<(), DragonflyError> {
+        let event_loop = EventLoop::<Command>::with_user_event()
+            .build()
+            .map_err(|e| DragonflyError::EventLoop(e.to_string()))?;
+        if self.proxy.is_none() {
+            self.proxy = Some(event_loop.create_proxy());
+        }
+
+        event_loop
+            .run_app(&mut self)
+            .map_err(|e| DragonflyError::EventLoop(e.to_string()))?;
+        self.take_error().map_or(Ok(()), Err)
+    }
+
+    /// Sets the distance the camera pans per frame while an arrow key is
+    /// held, at 60 frames per second.
+    ///
+    /// The panning follows the elapsed time, so its speed does not depend on
+    /// the actual frame rate.
+    pub fn with_pan_speed(mut self, speed: f32) -> Self {
+        self.pan_speed = Some(speed);
+        self
+    }
+
+    /// Sets the present mode of the surface.
+    pub fn with_present_mode(mut self, mode: wgpu::PresentMode) -> Self {
+        self.context_config.present_mode = mode;
+        self
+    }
+
+    /// Sets whether a low-power or a high-performance adapter is preferred.
+    pub fn with_power_preference(mut self, preference: wgpu::PowerPreference) -> Self {
+        self.context_config.power_preference = preference;
+        self
+    }
+
+    /// Sets the number of samples per pixel of the multisample antialiasing,
+    /// 1 to disable it.
+    pub fn with_msaa_samples(mut self, samples: u32) -> Self {
+        self.context_config.msaa_samples = samples;
+        self
+    }
+
+    /// Returns the options the context is created with.
+    pub fn context_config(&self) -> &ContextConfig {
+        &self.context_config
+    }
+
+    /// Reloads the shader of the figures from the given WGSL file whenever
+    /// it changes, polled before each frame.
+    #[cfg(feature = "hot-reload")]
+    pub fn with_shader_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.shader_path = Some(path.into());
+        self
+    }
+
+    /// Sets the proxy of the event loop, used to deliver the outcome of the
+    /// file dialogs.
+    pub fn with_proxy(mut self, proxy: EventLoopProxy<Command>) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Enables or disables the repair of the opened meshes.
+    ///
+    /// Every opened mesh is validated and its report is logged. With the
+    /// repair enabled, the defects found are also repaired, see
+    /// `vertex::repair`.
+    pub fn with_repair(mut self, enabled: bool) -> Self {
+        self.repair = enabled;
+        self
+    }
+
+    /// Enables or disables the frame hash mode.
+    ///
+    /// In this mode, the animation advances by a fixed timestep of 1/60 s per
+    /// frame and the hash of each rendered frame is printed with its index
+    /// and the view state, so that runs can be compared bit for bit.
+    pub fn with_frame_hash(mut self, enabled: bool) -> Self {
+        self.frame_hash = enabled;
+        self
+    }
+
+    /// Returns the state of the view the frames depend on, as printed along
+    /// the frame hashes.
+    pub fn view_state(&self) -> String {
+        let wgpu::Color { r, g, b, a } = self.state.background.unwrap_or(wgpu::Color::WHITE);
+        format!(
+            "figure={} background={},{},{},{}",
+            self.state.fig_idx(),
+            r,
+            g,
+            b,
+            a
+        )
+    }
+
+    /// Returns the title of the window: the name of the displayed figure, or
+    /// only the name of the application while a mesh is displayed.
+    pub fn title(&self) -> String {
+        match &self.mesh {
+            None => format!("Dragonfly — {}", self.state.figure.name()),
+            Some(_) => "Dragonfly".to_string(),
+        }
+    }
+
+    /// Enables or disables the FPS counter displayed in the title bar.
+    pub fn with_fps_counter(mut self, enabled: bool) -> Self {
+        self.fps_counter = enabled.then(FpsCounter::default);
+        self
+    }
+
+    /// Enables or disables the printing of the frame statistics, summarized
+    /// every `FRAME_STATS_CAPACITY` frames, with the GPU time if the device
+    /// supports timestamp queries.
+    pub fn with_stats(mut self, enabled: bool) -> Self {
+        self.stats_frames = enabled.then_some(0);
+        self
+    }
+
+    /// Returns whether the frame statistics are printed.
+    pub fn stats_enabled(&self) -> bool {
+        self.stats_frames.is_some()
+    }
+
+    /// Returns the FPS counter, if enabled.
+    pub fn fps_counter(&self) -> Option<&FpsCounter> {
+        self.fps_counter.as_ref()
+    }
+
+    /// Returns the target duration of a frame, if the frame rate is limited.
+    pub fn frame_duration(&self) -> Option<Duration> {
+        self.frame_duration
+    }
+
+    /// Returns the arena of the transient CPU mesh data of the frame.
+    pub fn frame_arena(&mut self) -> &mut FrameArena {
+        &mut self.frame_arena
+    }
+
+    /// Returns the state of the application.
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    /// Takes the error the application exited with, if any.
+    pub fn take_error(&mut self) -> Option<DragonflyError> {
+        self.error.take()
+    }
+
+    /// Records a fatal error and exits the event loop.
+    ///
+    /// The error is reported once the event loop has returned.
+    fn fail(&mut self, event_loop: &ActiveEventLoop, error: DragonflyError) {
+        self.error = Some(error);
+        event_loop.exit();
+    }
+
+    /// Pans the camera in the direction of the held arrow keys, for the given
+    /// duration in seconds.
+    fn update_pan(&mut self, dt: f32) {
+        let [x, y] = self.state.pan_direction();
+        let Some(context) = self.context.as_mut() else {
+            return;
+        };
+        if x == 0.0 && y == 0.0 {
+            return;
+        }
+
+        let distance = self.pan_speed.unwrap_or(DEFAULT_PAN_SPEED) * dt * PAN_FRAME_RATE;
+        context.pan([x * distance, y * distance]);
+    }
+
+    /// Displays the next step of the morph in progress, if any, at the given
+    /// animation time.
+    ///
+    /// The figure the morph ends with is set once the morph is over.
+    fn update_morph(&mut self, time: f32) {
+        let (Some(mut morph), Some(context)) = (self.morph.take(), self.context.as_mut()) else {
+            return;
+        };
+
+        let start = *morph.start.get_or_insert(time);
+        let t = (time - start) / morph.duration;
+        if t >= 1.0 {
+            show_figure(context, self.figure_pool.as_ref(), morph.to_idx);
+            return;
+        }
+
+        let to = match Figure::try_from(morph.to_idx) {
+            Ok(to) => to.mesh_data(),
+            Err(e) => {
+                log::warn!("Cannot morph: {}", e);
+                return;
+            }
+        };
+        // The steps of a morph share their indices, only the vertices are
+        // written again.
+        let step = vertex::morph(&morph.from, &to, t);
+        if !context.write_vertices(step.vertices.clone()) {
+            context.set_mesh_unfitted(&step);
+        }
+        self.morph = Some(morph);
+    }
+
+    /// Displays the current mesh, or the current figure, subdivided once.
+    ///
+    /// The mesh is left unchanged, and a warning is logged, if it cannot be
+    /// subdivided further.
+    fn subdivide(&mut self) {
+        self.morph = None;
+        let mesh = match &self.mesh {
+            Some(mesh) => mesh.clone(),
+            None => self.state.figure.mesh_data(),
+        };
+
+        let subdivided = match vertex::subdivide(&mesh.vertices, &mesh.indices, 1) {
+            Ok((vertices, indices)) => MeshData { vertices, indices },
+            Err(e) => {
+                log::warn!("Cannot subdivide: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = subdivided.validate() {
+            log::warn!("Cannot subdivide: {}", e);
+            return;
+        }
+        log::info!("Subdivided: {}", subdivided.stats());
+        if let Some(context) = self.context.as_mut() {
+            context.set_mesh(&subdivided);
+        }
+        self.mesh = Some(subdivided);
+    }
+
+    /// Opens a file dialog on a separate thread.
+    ///
+    /// The outcome is delivered to `user_event` through the event loop proxy.
+    #[cfg(feature = "dialogs")]
+    fn open_dialog(&self, kind: DialogKind) {
+        match self.proxy.clone() {
+            Some(proxy) => {
+                dialog::spawn_dialog(kind, proxy, dialog::show_native_dialog);
+            }
+            None => log::error!("{}: no event loop proxy to deliver the file", kind.title()),
+        }
+    }
+
+    /// Handles the outcome of a file dialog.
+    ///
+    /// The picked file is opened and displayed, or the scene or the current
+    /// figure is written to it. A cancelled dialog is silently ignored, and
+    /// a failure is logged.
+    pub fn dialog_closed(&mut self, kind: DialogKind, outcome: DialogOutcome) {
+        let (path, format) = match dialog::resolve_outcome(kind, outcome) {
+            Ok(Some(picked)) => picked,
+            Ok(None) => return,
+            Err(message) => {
+                log::error!("{}: {}", kind.title(), message);
+                return;
+            }
+        };
+
+        let result = match kind {
+            DialogKind::Open => {
+                dialog::read_mesh(&path, &format, self.repair).map(|(mesh, report)| {
+                    log::info!("Opened {:?}: {}", path, mesh.stats());
+                    if !report.is_clean() {
+                        let action = if self.repair {
+                            "repaired"
+                        } else {
+                            "left as is"
+                        };
+                        log::warn!("{}The mesh is {}", report, action);
+                    }
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_mesh(&mesh);
+                    }
+                    self.mesh = Some(mesh);
+                    if let Some(window) = self.window.as_ref() {
+                        window.set_title(&self.title());
+                        window.request_redraw();
+                    }
+                })
+            }
+            DialogKind::SaveScene | DialogKind::ExportMesh if self.mesh.is_some() => {
+                Err("Only the figures can be written, not the opened meshes".to_string())
+            }
+            DialogKind::SaveScene => dialog::write_scene(&self.state.figure, &path, &format),
+            DialogKind::ExportMesh => dialog::write_figure(&self.state.figure, &path, &format),
+        };
+        if let Err(message) = result {
+            log::error!("{}: cannot handle {:?}: {}", kind.title(), path, message);
+        }
+    }
+
+    /// Returns the mesh displayed instead of the figures, if any.
+    pub fn mesh(&self) -> Option<&MeshData> {
+        self.mesh.as_ref()
+    }
+
+    /// Exits the application after the device has been lost on the web,
+    /// where the context cannot be rebuilt without blocking the browser.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn recover_context(&mut self, event_loop: &ActiveEventLoop) {
+        log::error!("The GPU device was lost, reload the page to restart the application.");
+        event_loop.exit();
+    }
+
+    /// Rebuilds the graphics context after the device has been lost.
+    ///
+    /// All the GPU resources of the lost context are dropped, a new adapter
+    /// and device are requested (possibly a different adapter), and the
+    /// current figure and uniforms are uploaded again.
+    ///
+    /// The application exits if the context cannot be rebuilt.
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    fn recover_context(&mut self, event_loop: &ActiveEventLoop) {
+        let old_context = self.context.take().unwrap();
+        let time_uniform = old_context.time_uniform;
+        let background = old_context.background;
+        let grid = old_context.grid;
+        let highlight = old_context.highlight;
+        let gradient = old_context.gradient;
+        let camera = old_context.camera;
+        let transform = old_context.transform;
+        drop(old_context);
+
+        let window = self.window.clone().unwrap();
+        let mut context = match pollster::block_on(Context::new(&window, self.context_config)) {
+            Ok(context) => context,
+            Err(e) => return self.fail(event_loop, e),
+        };
+        show_figure(
+            &mut context,
+            self.figure_pool.as_ref(),
+            self.state.fig_idx(),
+        );
+        if let Some(mesh) = &self.mesh {
+            context.set_mesh(mesh);
+        }
+        #[cfg(feature = "hot-reload")]
+        {
+            context.shader_path = self.shader_path.clone();
+        }
+        context.time_uniform = time_uniform;
+        context.background = background;
+        context.set_grid(grid);
+        context.set_highlight(highlight);
+        context.set_gradient(gradient);
+        context.camera = camera;
+        context.update_camera();
+        context.set_transform(transform.transform);
+        context.set_wireframe_enabled(self.state.wireframe);
+        if self.frame_hash {
+            context.enable_frame_capture();
+        }
+        if self.stats_frames.is_some() && !context.enable_gpu_timing() {
+            log::warn!("Timestamp queries are not supported, the GPU time is not measured");
+        }
+        self.context = Some(context);
+
+        log::warn!("The GPU device was lost, the graphics context has been rebuilt.");
+        window.request_redraw();
+    }
+
+    /// Creates the graphics context of the window and sets it up, or exits
+    /// the application if it cannot be created.
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    fn create_context(&mut self, event_loop: &ActiveEventLoop, window: Arc<Window>) {
+        match pollster::block_on(Context::new(&window, self.context_config)) {
+            Ok(context) => self.init_context(window, context),
+            Err(e) => self.fail(event_loop, e),
+        }
+    }
+
+    /// Starts creating the graphics context of the window.
+    ///
+    /// The browser cannot be blocked on while the adapter and the device
+    /// are requested, so the context is created in the background, then
+    /// picked up by `poll_pending_context` on the next window event.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn create_context(&mut self, _event_loop: &ActiveEventLoop, window: Arc<Window>) {
+        let pending = std::rc::Rc::clone(&self.pending_context);
+        let context_config = self.context_config;
+        self.window = Some(window.clone());
+        wasm_bindgen_futures::spawn_local(async move {
+            let context = Context::new(&window, context_config).await;
+            *pending.borrow_mut() = Some((window.clone(), context));
+            window.request_redraw();
+        });
+    }
+
+    /// Sets up the context created in the background, or exits the
+    /// application if it could not be created.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn poll_pending_context(&mut self, event_loop: &ActiveEventLoop) {
+        let Some((window, context)) = self.pending_context.borrow_mut().take() else {
+            return;
+        };
+        match context {
+            Ok(context) => self.init_context(window, context),
+            Err(e) => self.fail(event_loop, e),
+        }
+    }
+
+    /// Sets up a new graphics context with the options and the state of the
+    /// application, and starts the clock.
+    fn init_context(&mut self, window: Arc<Window>, mut context: Context) {
+        let info = context.adapter_info();
+        log::info!(
+            "Adapter: {} ({:?}, {:?} backend), driver: {} {}",
+            info.name,
+            info.device_type,
+            info.backend,
+            info.driver,
+            info.driver_info
+        );
+        if let Some(mesh) = &self.mesh {
+            context.set_mesh(mesh);
+        }
+        #[cfg(feature = "hot-reload")]
+        {
+            context.shader_path = self.shader_path.clone();
+        }
+        if let Some(wgpu::Color { r, g, b, a }) = self.state.background {
+            context.set_background(r, g, b, a);
+        }
+        if self.state.grid {
+            context.set_grid(Some(Grid::default()));
+        }
+        context.set_gradient(self.state.gradient());
+        context.set_wireframe_enabled(self.state.wireframe);
+        if self.frame_hash {
+            context.enable_frame_capture();
+        }
+        if self.stats_frames.is_some() && !context.enable_gpu_timing() {
+            log::warn!("Timestamp queries are not supported, the GPU time is not measured");
+        }
+        // The cursor position is normalized with the size of the
+        // window, known before the first resize.
+        let size = window.inner_size();
+        self.state.size.get_or_insert((size.width, size.height));
+        self.window = Some(window);
+        self.context = Some(context);
+
+        let now = Instant::now();
+        self.start_time = Some(now);
+        self.last_frame = Some(now);
+    }
+
+    /// Renders a frame, after rebuilding the context if the device has been
+    /// lost, and schedules the next one.
+    fn redraw(&mut self, event_loop: &ActiveEventLoop) {
+        profiler::begin_frame();
+        crate::profile_scope!("frame");
+        self.frame_arena.reset();
+
+        // Rebuild the context if the device has been lost.
+        if self.context.as_ref().unwrap().is_device_lost() {
+            self.recover_context(event_loop);
+            if self.context.is_none() {
+                return;
+            }
+        }
+
+        // Pick up the changes of the shader file.
+        #[cfg(feature = "hot-reload")]
+        self.context.as_mut().unwrap().try_reload_shader();
+
+        // Update the time uniform before rendering.
+        let now = Instant::now();
+        let dt = now.duration_since(self.last_frame.unwrap_or(now));
+        {
+            crate::profile_scope!("animation");
+            let time = now.duration_since(self.start_time.unwrap_or(now));
+            self.last_frame = Some(now);
+
+            // The frame hashes only depend on the frame index.
+            let (time, animation_dt) = if self.frame_hash {
+                (self.frame_index as f32 / 60.0, 1.0 / 60.0)
+            } else {
+                (time.as_secs_f32(), dt.as_secs_f32())
+            };
+            if self.state.spin {
+                self.spin_elapsed += animation_dt;
+            }
+            let context = self.context.as_mut().unwrap();
+            context
+                .time_uniform
+                .set_angle(spin_angle(self.spin_elapsed));
+            context.update_time(time, animation_dt);
+            self.update_morph(time);
+            self.update_pan(animation_dt);
+        }
+
+        let render_result = {
+            crate::profile_scope!("render");
+            self.context.as_mut().unwrap().render()
+        };
+
+        // Print the hash of the frame in the frame hash mode.
+        if let Some(pixels) = self.context.as_mut().unwrap().captured_frame.take() {
+            let line = frame_hash::format_hash_line(
+                self.frame_index,
+                frame_hash::hash_pixels(&pixels),
+                &self.view_state(),
+            );
+            println!("{}", line);
+            self.frame_index += 1;
+        }
+
+        // Print the statistics of the last frames.
+        if let Some(frames) = self.stats_frames.as_mut() {
+            *frames += 1;
+            if *frames == FRAME_STATS_CAPACITY {
+                *frames = 0;
+                eprintln!(
+                    "Frame stats over {}",
+                    self.context.as_ref().unwrap().stats_summary()
+                );
+            }
+        }
+
+        // Display the average frame rate in the title bar.
+        if let Some(fps_counter) = self.fps_counter.as_mut() {
+            fps_counter.record(dt);
+            if let Some(fps) = fps_counter.fps() {
+                self.window.as_ref().unwrap().set_title(&format!(
+                    "{} — {:.1} FPS",
+                    self.title(),
+                    fps
+                ));
+            }
+        }
+
+        match render_result {
+            // Keep redrawing to animate the figure, waiting for the
+            // next frame if the frame rate is limited
+            Ok(_) => match self.frame_duration {
+                Some(frame_duration) if now.elapsed() < frame_duration => {
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(now + frame_duration));
+                }
+                _ => self.window.as_ref().unwrap().request_redraw(),
+            },
+            // Reconfigure the surface if lost
+            Err(DragonflyError::RenderError(wgpu::SurfaceError::Lost)) => {
+                let size = self.context.as_ref().unwrap().size;
+                self.context.as_mut().unwrap().resize(size);
+                self.window.as_ref().unwrap().request_redraw();
+            }
+            // The system is out of memory, we should probably quit
+            Err(e @ DragonflyError::RenderError(wgpu::SurfaceError::OutOfMemory)) => {
+                self.fail(event_loop, e)
+            }
+            // All other errors (Outdated, Timeout) should be resolved
+            // by the next frame
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    /// Applies the effects of an input to the window and the graphics
+    /// context.
+    fn apply(&mut self, event_loop: &ActiveEventLoop, events: Vec<AppEvent>) {
+        for event in events {
+            match event {
+                AppEvent::FigureChanged(fig_idx) => {
+                    self.mesh = None;
+                    self.morph = None;
+                    if let Some(context) = self.context.as_mut() {
+                        show_figure(context, self.figure_pool.as_ref(), fig_idx);
+                    }
+                    match Figure::try_from(fig_idx) {
+                        Ok(figure) => {
+                            log::info!("Figure {}: {} ({})", fig_idx, figure, figure.stats())
+                        }
+                        Err(e) => log::warn!("{}", e),
+                    }
+                    if let Some(window) = self.window.as_ref() {
+                        window.set_title(&self.title());
+                    }
+                }
+                AppEvent::MorphRequested { from, to, duration } => {
+                    let from = match (self.mesh.take(), Figure::try_from(from)) {
+                        (Some(mesh), _) => mesh,
+                        (None, Ok(figure)) => figure.mesh_data(),
+                        (None, Err(e)) => {
+                            log::warn!("Cannot morph: {}", e);
+                            continue;
+                        }
+                    };
+                    match Figure::try_from(to) {
+                        Ok(figure) => log::info!("Morphing to figure {}: {}", to, figure),
+                        Err(e) => {
+                            log::warn!("Cannot morph: {}", e);
+                            continue;
+                        }
+                    }
+                    self.morph = Some(Morph {
+                        from,
+                        to_idx: to,
+                        start: None,
+                        duration,
+                    });
+                    if let Some(window) = self.window.as_ref() {
+                        window.set_title(&self.title());
+                    }
+                }
+                AppEvent::BackgroundChanged(wgpu::Color { r, g, b, a }) => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_background(r, g, b, a);
+                    }
+                }
+                AppEvent::GradientChanged(gradient) => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_gradient(gradient);
+                    }
+                }
+                AppEvent::GridToggled(enabled) => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_grid(enabled.then(Grid::default));
+                    }
+                }
+                AppEvent::CullModeChanged(cull_mode) => {
+                    log::info!("Cull mode: {:?}", cull_mode);
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_cull_mode(cull_mode);
+                    }
+                }
+                AppEvent::WireframeToggled(enabled) => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.set_wireframe_enabled(enabled);
+                    }
+                }
+                AppEvent::ZoomRequested { factor, at } => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.zoom_at(factor, at);
+                    }
+                }
+                AppEvent::Dragged(delta) => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.drag(delta);
+                    }
+                }
+                AppEvent::FitRequested => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.fit_figure_to_window();
+                    }
+                }
+                AppEvent::CameraReset => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.reset_camera();
+                    }
+                }
+                AppEvent::SubdivideRequested => self.subdivide(),
+                AppEvent::Clicked(ndc) => {
+                    if let Some(context) = self.context.as_mut() {
+                        if context.hit_test(ndc) {
+                            context.set_highlight(!context.highlight);
+                            if let Some(window) = self.window.as_ref() {
+                                window.request_redraw();
+                            }
+                        }
+                    }
+                }
+                AppEvent::Resized { width, height } => {
+                    if let Some(context) = self.context.as_mut() {
+                        context.resize(winit::dpi::PhysicalSize { width, height });
+                    }
+                }
+                #[cfg(feature = "dialogs")]
+                AppEvent::DialogRequested(kind) => self.open_dialog(kind),
+                // The dialog shortcuts are only bound with the dialogs
+                // feature.
+                #[cfg(not(feature = "dialogs"))]
+                AppEvent::DialogRequested(_) => {}
+                AppEvent::ProfileRequested => {
+                    // Log the time spent in each subsystem during the last
+                    // frame.
+                    if profiler::ENABLED {
+                        log::info!("Frame profile:\n{}", profiler::last_frame_profile());
+                    } else {
+                        log::info!("Profiling is disabled, enable the `profiling` feature");
+                    }
+                }
+                AppEvent::RedrawRequested => {
+                    if let Some(window) = self.window.as_ref() {
+                        window.request_redraw();
+                    }
+                }
+                AppEvent::Render => {
+                    if self.context.is_some() {
+                        self.redraw(event_loop);
+                    }
+                }
+                AppEvent::Exit => event_loop.exit(),
+            }
+        }
+    }
 }
 
-impl ApplicationHandler for Dragonfly {
+/// Sets the figure at the given index on the context, from its mesh in the
+/// pool if there is one.
+fn show_figure(context: &mut Context, pool: Option<&ObjectPool<CachedMesh>>, fig_idx: u8) {
+    match pool.and_then(|pool| pool.get_at(fig_idx as usize)) {
+        Some(mesh) => context.set_cached_figure(fig_idx, mesh),
+        None => {
+            if let Err(e) = context.set_figure(fig_idx) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+}
+
+impl ApplicationHandler<Command> for Dragonfly {
+    /// Handles the `NewEvents` event, which is called when the event loop
+    /// wakes up.
+    ///
+    /// If the event loop was waiting for the next frame of a limited frame
+    /// rate, a redraw is requested.
+    fn new_events(&mut self, _event_loop: &ActiveEventLoop, cause: StartCause) {
+        if let StartCause::ResumeTimeReached { .. } = cause {
+            if let Some(window) = self.window.as_ref() {
+                window.request_redraw();
+            }
+        }
+    }
+
     /// Handles the `Resumed` event, which is called when the event loop is
     /// started.
     ///
     /// If the window is `None`, the window is created and the context is
-    /// initialized.
+    /// initialized. The application exits if either cannot be created.
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.figure_pool.get_or_insert_with(pool::figure_pool);
         if self.window.is_none() {
             let window_attributes = Window::default_attributes()
-                .with_title("Dragonfly")
+                .with_title(self.title())
                 .with_min_inner_size(winit::dpi::PhysicalSize {
                     width: 1020,
                     height: 1020,
                 });
-            let window = Arc::new(
-                event_loop
-                    .create_window(window_attributes)
-                    .expect("Failed to create window."),
-            );
-
-            let context = pollster::block_on(Context::new(&window));
-            self.window = Some(window);
-            self.context = Some(context);
+            #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+            let window_attributes = {
+                use winit::platform::web::WindowAttributesExtWebSys;
+                window_attributes.with_append(true)
+            };
+            let window = match event_loop.create_window(window_attributes) {
+                Ok(window) => Arc::new(window),
+                Err(e) => {
+                    return self.fail(event_loop, DragonflyError::SurfaceCreation(e.to_string()))
+                }
+            };
+            self.create_context(event_loop, window);
         }
     }
 
     /// Handles a window event.
     ///
-    /// This method will be called when an event occurs on the window.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if a `RedrawRequested` event is received and the
-    /// context cannot be rendered.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the window id is not the same as the id of the window stored
-    /// in the context.
+    /// This method will be called when an event occurs on the window. The
+    /// event is normalized to an `InputEvent`, which updates the application
+    /// state, and the resulting `AppEvent`s are applied.
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
-        match event {
-            WindowEvent::RedrawRequested => {
-                match self.context.as_mut().unwrap().render() {
-                    Ok(_) => {}
-                    // Reconfigure the surface if lost
-                    Err(wgpu::SurfaceError::Lost) => {
-                        let size = self.context.as_ref().unwrap().size;
-                        self.context.as_mut().unwrap().resize(size);
-                        self.window.as_ref().unwrap().request_redraw();
-                    }
-                    // The system is out of memory, we should probably quit
-                    Err(wgpu::SurfaceError::OutOfMemory) => event_loop.exit(),
-                    // All other errors (Outdated, Timeout) should be resolved
-                    // by the next frame
-                    Err(e) => eprintln!("{:?}", e),
-                }
-            }
-            WindowEvent::Resized(physical_size) => {
-                self.context.as_mut().unwrap().resize(physical_size);
-                self.window.as_ref().unwrap().request_redraw();
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        {
+            self.poll_pending_context(event_loop);
+            if self.context.is_none() {
+                return;
             }
+        }
+        match event {
             WindowEvent::ScaleFactorChanged { .. } => {
                 self.context
                     .as_mut()
@@ -98,53 +1049,60 @@ impl ApplicationHandler for Dragonfly {
                     .resize(self.window.as_ref().unwrap().inner_size());
                 self.window.as_ref().unwrap().request_redraw();
             }
-            WindowEvent::KeyboardInput {
-                event:
-                    winit::event::KeyEvent {
-                        state,
-                        physical_key:
-                            winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::Space),
-                        ..
-                    },
-                ..
-            } => {
-                if state == winit::event::ElementState::Released {
-                    let fig_idx = self.context.as_ref().unwrap().fig_idx;
-                    let new_fig_idx = (fig_idx + 1) % 6;
-
-                    self.context.as_mut().unwrap().fig_idx = new_fig_idx;
-
-                    let figure = vertex::Figure::get_figure(new_fig_idx);
-                    let vertices = figure.get_vertices();
-                    let indices = figure.get_indices();
-
-                    self.context.as_mut().unwrap().vertex_buffer =
-                        self.context.as_mut().unwrap().device.create_buffer_init(
-                            &wgpu::util::BufferInitDescriptor {
-                                label: Some("Vertex Buffer"),
-                                contents: bytemuck::cast_slice(&vertices),
-                                usage: wgpu::BufferUsages::VERTEX,
-                            },
-                        );
-                    self.context.as_mut().unwrap().num_vertices = vertices.len() as u32;
-
-                    self.context.as_mut().unwrap().index_buffer =
-                        self.context.as_mut().unwrap().device.create_buffer_init(
-                            &wgpu::util::BufferInitDescriptor {
-                                label: Some("Index Buffer"),
-                                contents: bytemuck::cast_slice(&indices),
-                                usage: wgpu::BufferUsages::INDEX,
-                            },
-                        );
-                    self.context.as_mut().unwrap().num_indices = indices.len() as u32;
-                }
-
-                self.window.as_ref().unwrap().request_redraw();
+            event => {
+                let Some(input) = InputEvent::from_window_event(&event) else {
+                    return;
+                };
+                let events = {
+                    crate::profile_scope!("input");
+                    self.state.handle(&input)
+                };
+                self.apply(event_loop, events);
             }
-            WindowEvent::CloseRequested => {
+        }
+    }
+
+    /// Handles a command sent by another process.
+    ///
+    /// The result of the command is written as JSON on the standard output.
+    ///
+    /// The outcome of a file dialog is handled without writing anything.
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, command: Command) {
+        let running = self.context.is_some();
+        let response = match (&command, running) {
+            (Command::Quit, _) => {
                 event_loop.exit();
+                Ok(())
+            }
+            (Command::DialogClosed { kind, outcome }, _) => {
+                self.dialog_closed(*kind, outcome.clone());
+                return;
             }
-            _ => (),
+            (_, false) => Err("The application is not running".to_string()),
+            (Command::NextFigure, true) => {
+                let events = self.state.next_figure();
+                self.apply(event_loop, events);
+                Ok(())
+            }
+            (Command::SetFigure { index }, true) => match self.state.set_figure(*index) {
+                Some(events) => {
+                    self.apply(event_loop, events);
+                    Ok(())
+                }
+                None => Err(format!("Unknown figure index {}", index)),
+            },
+        };
+
+        let response = match response {
+            Ok(()) => Response::Ok {
+                command: command.name().to_string(),
+            },
+            Err(message) => Response::Error { message },
+        };
+        println!("{}", response.to_json());
+
+        if let Some(window) = self.window.as_ref() {
+            window.request_redraw();
         }
     }
 }
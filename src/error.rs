@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// An error of the application.
+#[derive(Debug)]
+pub enum DragonflyError {
+    /// The event loop could not be created or failed to run.
+    EventLoop(String),
+    /// The window or its rendering surface could not be created.
+    SurfaceCreation(String),
+    /// No graphics adapter is compatible with the surface.
+    AdapterRequest,
+    /// The adapter refused to create a device.
+    DeviceRequest(wgpu::RequestDeviceError),
+    /// The surface supports no texture format.
+    SurfaceConfiguration,
+    /// The frame could not be acquired from the surface.
+    RenderError(wgpu::SurfaceError),
+    /// A custom shader did not compile or does not match the pipeline.
+    Shader(wgpu::Error),
+    /// A file could not be written.
+    Io(std::io::Error),
+}
+
+/// Displays the error as a message for the user.
+impl fmt::Display for DragonflyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DragonflyError::EventLoop(message) => write!(f, "The event loop failed: {}", message),
+            DragonflyError::SurfaceCreation(message) => {
+                write!(f, "Failed to create the window surface: {}", message)
+            }
+            DragonflyError::AdapterRequest => {
+                write!(f, "No compatible graphics adapter was found")
+            }
+            DragonflyError::DeviceRequest(e) => {
+                write!(f, "Failed to create the graphics device: {}", e)
+            }
+            DragonflyError::SurfaceConfiguration => {
+                write!(f, "The window surface supports no texture format")
+            }
+            DragonflyError::RenderError(e) => write!(f, "Failed to render the frame: {}", e),
+            DragonflyError::Shader(e) => write!(f, "Failed to compile the shader: {}", e),
+            DragonflyError::Io(e) => write!(f, "Failed to write the file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DragonflyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DragonflyError::DeviceRequest(e) => Some(e),
+            DragonflyError::RenderError(e) => Some(e),
+            DragonflyError::Shader(e) => Some(e),
+            DragonflyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for DragonflyError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        DragonflyError::DeviceRequest(e)
+    }
+}
+
+impl From<wgpu::SurfaceError> for DragonflyError {
+    fn from(e: wgpu::SurfaceError) -> Self {
+        DragonflyError::RenderError(e)
+    }
+}
+
+impl From<std::io::Error> for DragonflyError {
+    fn from(e: std::io::Error) -> Self {
+        DragonflyError::Io(e)
+    }
+}
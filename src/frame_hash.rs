@@ -0,0 +1,125 @@
+/// The number of bytes of a pixel of the captured textures.
+///
+/// Only the 8-bit RGBA and BGRA formats are captured.
+const BYTES_PER_PIXEL: u32 = 4;
+
+/// Returns the number of bytes of a row of a texture copied to a buffer,
+/// padded to `wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`.
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * BYTES_PER_PIXEL;
+    unpadded.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT
+}
+
+/// Removes the padding at the end of each row of a texture copied to a
+/// buffer, so that the pixels of a frame do not depend on the alignment.
+pub fn strip_row_padding(data: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let unpadded = (width * BYTES_PER_PIXEL) as usize;
+    let padded = padded_bytes_per_row(width) as usize;
+    data.chunks(padded)
+        .take(height as usize)
+        .flat_map(|row| &row[..unpadded])
+        .copied()
+        .collect()
+}
+
+/// Copies a 4-byte-per-pixel texture to the CPU and returns its pixels,
+/// row by row, without padding.
+///
+/// The texture must have the `COPY_SRC` usage. Blocks until the copy is
+/// complete.
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> Vec<u8> {
+    let (width, height) = (texture.width(), texture.height());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Readback Buffer"),
+        size: (padded_bytes_per_row(width) * height) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Readback Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row(width)),
+                rows_per_image: Some(height),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| {
+        result.expect("Failed to map the readback buffer");
+    });
+    device.poll(wgpu::Maintain::Wait);
+
+    let pixels = strip_row_padding(&slice.get_mapped_range(), width, height);
+    buffer.unmap();
+    pixels
+}
+
+/// Hashes the pixels of a frame with the 64-bit FNV-1a hash.
+///
+/// The hash is stable across runs and platforms, so that it can be compared
+/// with a recorded hash.
+pub fn hash_pixels(pixels: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pixels.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+/// Formats the hash of a frame as a line of a hash file: the frame index,
+/// the hash in hexadecimal and the view state.
+pub fn format_hash_line(frame: u64, hash: u64, view_state: &str) -> String {
+    format!("{} {:016x} {}", frame, hash, view_state)
+}
+
+/// Compares the frame hashes of a run with the expected ones, line by line.
+///
+/// Blank lines are ignored. Returns the number of frames verified.
+///
+/// # Errors
+///
+/// Returns an error message describing the first frame that differs, or
+/// the missing frames.
+pub fn verify(expected: &str, actual: &str) -> Result<usize, String> {
+    let lines = |text: &str| -> Vec<String> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (expected, actual) = (lines(expected), lines(actual));
+
+    for (i, (expected, actual)) in expected.iter().zip(&actual).enumerate() {
+        if expected != actual {
+            return Err(format!(
+                "Frame {} differs:\n  expected: {}\n  actual:   {}",
+                i, expected, actual
+            ));
+        }
+    }
+    if expected.len() != actual.len() {
+        return Err(format!(
+            "Expected {} frames, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    Ok(expected.len())
+}
@@ -0,0 +1,96 @@
+use crate::vertex::{Vertex, FLAT_NORMAL};
+
+/// The smallest distance between two lines, in pixels.
+///
+/// It bounds the number of lines, so that the vertices of a grid covering the
+/// largest surface can be indexed with `u16`.
+const MIN_LINE_SPACING: f32 = 1.0;
+
+/// A grid of lines drawn behind the figures, to help aligning them.
+///
+/// The grid is centered on the origin of the clip space and its lines are
+/// evenly spaced in pixels, so that the cells stay square whatever the size
+/// of the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    /// The distance between two main lines, in pixels.
+    pub spacing: f32,
+    /// The color of the lines, blended over the background with its alpha.
+    pub line_color: [f32; 4],
+    /// The number of cells each cell between two main lines is divided into
+    /// by finer lines. The main lines alone are drawn with 1.
+    pub subdivisions: u32,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            spacing: 100.0,
+            line_color: [0.5, 0.5, 0.5, 0.5],
+            subdivisions: 2,
+        }
+    }
+}
+
+impl Grid {
+    /// Generates the lines of the grid covering a viewport of the given size,
+    /// in pixels, as pairs of vertices for a `LineList` topology.
+    ///
+    /// The vertical lines come first, from left to right, then the horizontal
+    /// lines, from bottom to top. An empty viewport has no line.
+    pub fn generate_mesh(
+        &self,
+        viewport_width: f32,
+        viewport_height: f32,
+    ) -> (Vec<Vertex>, Vec<u16>) {
+        let step = (self.spacing / self.subdivisions.max(1) as f32).max(MIN_LINE_SPACING);
+        let color = self.line_color;
+
+        let mut vertices = Vec::new();
+        for x in line_offsets(viewport_width, step) {
+            vertices.push(Vertex {
+                position: [x, -1.0, 0.0],
+                color,
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            });
+            vertices.push(Vertex {
+                position: [x, 1.0, 0.0],
+                color,
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            });
+        }
+        for y in line_offsets(viewport_height, step) {
+            vertices.push(Vertex {
+                position: [-1.0, y, 0.0],
+                color,
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            });
+            vertices.push(Vertex {
+                position: [1.0, y, 0.0],
+                color,
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            });
+        }
+
+        let indices = (0..vertices.len() as u16).collect();
+        (vertices, indices)
+    }
+}
+
+/// Returns the clip space coordinates of the lines spaced by `step` pixels
+/// across a viewport dimension of `size` pixels, centered on the origin.
+fn line_offsets(size: f32, step: f32) -> Vec<f32> {
+    if !size.is_finite() || size <= 0.0 || !step.is_finite() {
+        return Vec::new();
+    }
+
+    let half_size = size / 2.0;
+    let count = (half_size / step).floor() as i32;
+    (-count..=count)
+        .map(|i| i as f32 * step / half_size)
+        .collect()
+}
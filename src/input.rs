@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use winit::{
+    event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent},
+    keyboard::{KeyCode, PhysicalKey},
+};
+
+/// A key handled by the application, independent of the windowing library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Cycles through the figures.
+    Space,
+    /// Starts or stops spinning the figure.
+    A,
+    /// Cycles through the background presets, or the cull modes with
+    /// `Shift`.
+    C,
+    /// Fits the current figure to the window.
+    F,
+    /// Toggles the grid drawn behind the figure, or cycles through the
+    /// gradient presets with `Shift`.
+    G,
+    /// Opens a file, with `Ctrl` and the `dialogs` feature.
+    O,
+    /// Resets the camera and shows the first figure.
+    R,
+    /// Saves the scene, or exports the mesh with `Shift`, with `Ctrl` and the
+    /// `dialogs` feature.
+    S,
+    /// Morphs the current figure into the next one.
+    M,
+    /// Toggles the morphs between the figures cycled through with `Space`.
+    T,
+    /// Toggles between the filled figure and its wireframe.
+    W,
+    /// Pans the camera to the left while held.
+    ArrowLeft,
+    /// Pans the camera to the right while held.
+    ArrowRight,
+    /// Pans the camera up while held.
+    ArrowUp,
+    /// Pans the camera down while held.
+    ArrowDown,
+    /// Logs the profile of the last frame.
+    F10,
+    /// Subdivides the current figure, with the `+`/`=` key or the keypad
+    /// `+`.
+    Plus,
+    /// A digit of the top row of the keyboard, from 0 to 9.
+    Digit(u8),
+}
+
+impl Key {
+    /// Returns the key of a physical key code, if handled by the application.
+    pub fn from_key_code(code: KeyCode) -> Option<Self> {
+        let key = match code {
+            KeyCode::Space => Key::Space,
+            KeyCode::KeyA => Key::A,
+            KeyCode::KeyC => Key::C,
+            KeyCode::KeyF => Key::F,
+            KeyCode::KeyG => Key::G,
+            KeyCode::KeyM => Key::M,
+            KeyCode::KeyO => Key::O,
+            KeyCode::KeyR => Key::R,
+            KeyCode::KeyS => Key::S,
+            KeyCode::KeyT => Key::T,
+            KeyCode::KeyW => Key::W,
+            KeyCode::ArrowLeft => Key::ArrowLeft,
+            KeyCode::ArrowRight => Key::ArrowRight,
+            KeyCode::ArrowUp => Key::ArrowUp,
+            KeyCode::ArrowDown => Key::ArrowDown,
+            KeyCode::F10 => Key::F10,
+            KeyCode::Equal | KeyCode::NumpadAdd => Key::Plus,
+            KeyCode::Digit0 => Key::Digit(0),
+            KeyCode::Digit1 => Key::Digit(1),
+            KeyCode::Digit2 => Key::Digit(2),
+            KeyCode::Digit3 => Key::Digit(3),
+            KeyCode::Digit4 => Key::Digit(4),
+            KeyCode::Digit5 => Key::Digit(5),
+            KeyCode::Digit6 => Key::Digit(6),
+            KeyCode::Digit7 => Key::Digit(7),
+            KeyCode::Digit8 => Key::Digit(8),
+            KeyCode::Digit9 => Key::Digit(9),
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+/// An input of the application, normalized from the window events.
+///
+/// The application state only depends on these events, so that it can be
+/// driven without a window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    /// The window was resized to the given physical size.
+    Resized { width: u32, height: u32 },
+    /// The state of the modifier keys changed.
+    ModifiersChanged { ctrl: bool, shift: bool },
+    /// A key was pressed.
+    KeyPressed(Key),
+    /// A key was released.
+    KeyReleased(Key),
+    /// The cursor moved to the given physical position, from the top left
+    /// corner of the window.
+    CursorMoved { x: f32, y: f32 },
+    /// The left mouse button was pressed.
+    MousePressed,
+    /// The left mouse button was released.
+    MouseReleased,
+    /// The middle or right mouse button was pressed, to drag the camera.
+    PanPressed,
+    /// The middle or right mouse button was released.
+    PanReleased,
+    /// The mouse wheel was scrolled, up for a positive delta, in lines or in
+    /// pixels.
+    Scrolled { delta: f32 },
+    /// Time passed without any input.
+    Wait(Duration),
+    /// The window asked for a new frame.
+    Redraw,
+    /// The window lost the keyboard focus, so the held keys will not be
+    /// released.
+    FocusLost,
+    /// The window was asked to close.
+    CloseRequested,
+}
+
+impl InputEvent {
+    /// Normalizes a window event.
+    ///
+    /// Returns `None` for the events the application does not handle as an
+    /// input, such as the keys it does not use.
+    pub fn from_window_event(event: &WindowEvent) -> Option<Self> {
+        match event {
+            WindowEvent::Resized(size) => Some(InputEvent::Resized {
+                width: size.width,
+                height: size.height,
+            }),
+            WindowEvent::ModifiersChanged(modifiers) => Some(InputEvent::ModifiersChanged {
+                ctrl: modifiers.state().control_key(),
+                shift: modifiers.state().shift_key(),
+            }),
+            WindowEvent::KeyboardInput { event, .. } => {
+                let PhysicalKey::Code(code) = event.physical_key else {
+                    return None;
+                };
+                let key = Key::from_key_code(code)?;
+                Some(match event.state {
+                    ElementState::Pressed => InputEvent::KeyPressed(key),
+                    ElementState::Released => InputEvent::KeyReleased(key),
+                })
+            }
+            WindowEvent::CursorMoved { position, .. } => Some(InputEvent::CursorMoved {
+                x: position.x as f32,
+                y: position.y as f32,
+            }),
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => Some(match state {
+                ElementState::Pressed => InputEvent::MousePressed,
+                ElementState::Released => InputEvent::MouseReleased,
+            }),
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Middle | MouseButton::Right,
+                ..
+            } => Some(match state {
+                ElementState::Pressed => InputEvent::PanPressed,
+                ElementState::Released => InputEvent::PanReleased,
+            }),
+            WindowEvent::MouseWheel { delta, .. } => Some(InputEvent::Scrolled {
+                delta: match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32,
+                },
+            }),
+            WindowEvent::RedrawRequested => Some(InputEvent::Redraw),
+            WindowEvent::Focused(false) => Some(InputEvent::FocusLost),
+            WindowEvent::CloseRequested => Some(InputEvent::CloseRequested),
+            _ => None,
+        }
+    }
+}
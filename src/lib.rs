@@ -1 +1,34 @@
+pub mod animation;
+pub mod arena;
+pub mod atlas;
+pub mod camera;
+pub mod cli;
+pub mod config;
+pub mod context;
+pub mod control;
+pub mod dialog;
+pub mod dragonfly;
+pub mod error;
+pub mod frame_hash;
+pub mod grid;
+pub mod input;
+pub mod pool;
+pub mod profiler;
+pub mod render_queue;
+pub mod snapshot;
+pub mod spec;
+pub mod state;
+pub mod stats;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod uniform;
 pub mod vertex;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub mod web;
+
+/// The clock of the frame timings, from `web-time` on the web, where
+/// `std::time::Instant` panics.
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+pub(crate) use std::time::Instant;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub(crate) use web_time::Instant;
@@ -1,16 +1,122 @@
+use dragonfly::{
+    cli::{Args, VerifyArgs},
+    control::{self, Command},
+    dragonfly::Dragonfly,
+    frame_hash, profiler,
+    vertex::Figure,
+};
 use winit::event_loop::{ControlFlow, EventLoop};
 
-mod context;
-mod dragonfly;
-
 fn main() {
-    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // Compare frame hashes with `verify --expect path --actual path`.
+    if let Some(verify) = args.verify {
+        std::process::exit(run_verify(&verify));
+    }
+
+    let event_loop = match EventLoop::<Command>::with_user_event().build() {
+        Ok(event_loop) => event_loop,
+        Err(e) => {
+            eprintln!("Error: failed to create the event loop: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Listen for commands on the standard input with `--control stdin`.
+    if args.control_stdin {
+        control::spawn_stdin_listener(event_loop.create_proxy());
+    }
+
+    // Record the profile scopes as trace events with `--trace-profile`.
+    if args.trace_profile.is_some() {
+        if !profiler::ENABLED {
+            log::warn!("Profiling is disabled, the trace will be empty");
+        }
+        profiler::enable_tracing();
+    }
 
     event_loop.set_control_flow(ControlFlow::default());
 
-    let mut app = dragonfly::Dragonfly::default();
+    let mut app = Dragonfly::default()
+        .with_proxy(event_loop.create_proxy())
+        .with_frame_hash(args.frame_hash)
+        .with_stats(args.stats)
+        .with_repair(args.repair);
+    if let Some(mode) = args.present_mode {
+        app = app.with_present_mode(mode);
+    }
+    if let Some(preference) = args.power_preference {
+        app = app.with_power_preference(preference);
+    }
+    if let Some([r, g, b, a]) = args.background {
+        app = app.with_background(wgpu::Color { r, g, b, a });
+    }
+    // Display a figure saved as JSON with `--figure path`.
+    if let Some(path) = &args.figure {
+        match load_figure(path) {
+            Ok(figure) => app = app.with_mesh(&figure),
+            Err(e) => {
+                eprintln!("Error: failed to load the figure {:?}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
     match event_loop.run_app(&mut app) {
         Ok(_) => {}
         Err(e) => log::error!("Failed to run app: {:?}", e),
     };
+
+    if let Some(path) = args.trace_profile {
+        if let Err(e) = profiler::write_chrome_trace(&path) {
+            log::error!("Failed to write trace to {:?}: {:?}", path, e);
+        }
+    }
+
+    // Report the fatal error the application exited with.
+    if let Some(e) = app.take_error() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Reads a figure from its JSON file.
+#[cfg(feature = "serialize")]
+fn load_figure(path: &std::path::Path) -> Result<Figure, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Figure::from_json(&json).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "serialize"))]
+fn load_figure(_path: &std::path::Path) -> Result<Figure, String> {
+    Err("the application is built without the serialize feature".to_string())
+}
+
+/// Compares the frame hashes of a run with the expected ones and returns
+/// the exit code: 0 if they match, 1 if they differ, 2 if a file cannot be
+/// read.
+fn run_verify(verify: &VerifyArgs) -> i32 {
+    let read = |path: &std::path::Path| {
+        std::fs::read_to_string(path).map_err(|e| eprintln!("Failed to read {:?}: {}", path, e))
+    };
+    let (Ok(expected), Ok(actual)) = (read(&verify.expect), read(&verify.actual)) else {
+        return 2;
+    };
+
+    match frame_hash::verify(&expected, &actual) {
+        Ok(frames) => {
+            println!("{} frames verified", frames);
+            0
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
 }
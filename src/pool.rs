@@ -0,0 +1,106 @@
+use std::{cell::Cell, sync::Arc};
+
+use crate::vertex::{boundary_edges, Figure, Mesh, Vertex};
+
+/// A fixed set of items built once and handed out in turn, to avoid
+/// rebuilding them each time they are needed.
+#[derive(Debug)]
+pub struct ObjectPool<T> {
+    /// The items of the pool.
+    items: Vec<T>,
+    /// The index of the item returned by the next call to `get`.
+    next: Cell<usize>,
+}
+
+impl<T> ObjectPool<T> {
+    /// Creates a pool of the given items.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            next: Cell::new(0),
+        }
+    }
+
+    /// Returns the next item, going back to the first one after the last,
+    /// or `None` if the pool is empty.
+    pub fn get(&self) -> Option<&T> {
+        let item = self.items.get(self.next.get())?;
+        self.next.set((self.next.get() + 1) % self.items.len());
+        Some(item)
+    }
+
+    /// Returns the item at the given index, or `None` if the index is out
+    /// of range. The next item returned by `get` is left unchanged.
+    pub fn get_at(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    /// Returns the number of items in the pool.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the pool has no item.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// The data uploaded to the GPU to display a mesh with 16-bit indices,
+/// computed once and shared.
+#[derive(Debug, Clone)]
+pub struct CachedMesh {
+    /// The vertices of the mesh.
+    pub vertices: Arc<[Vertex]>,
+    /// The indices of the triangles.
+    pub indices: Arc<[u16]>,
+    /// The indices of the outline, as in `Mesh::get_edge_indices`.
+    pub edge_indices: Arc<[u16]>,
+    /// The indices of the wireframe, as in `Mesh::get_wireframe_indices`.
+    pub wireframe_indices: Arc<[u16]>,
+    /// The corners of the bounding box of the mesh.
+    pub bounds: ([f32; 3], [f32; 3]),
+}
+
+impl CachedMesh {
+    /// Computes the data uploaded to display the mesh.
+    pub fn from_mesh(mesh: &dyn Mesh) -> Self {
+        let vertices = mesh.get_vertices();
+        let indices = mesh.get_indices();
+        Self {
+            edge_indices: boundary_edges(&vertices, &indices).into(),
+            wireframe_indices: mesh.get_wireframe_indices().into(),
+            bounds: mesh.bounds(),
+            vertices: vertices.into(),
+            indices: indices.into(),
+        }
+    }
+}
+
+impl Mesh for CachedMesh {
+    fn get_vertices(&self) -> Vec<Vertex> {
+        self.vertices.to_vec()
+    }
+
+    fn get_indices(&self) -> Vec<u16> {
+        self.indices.to_vec()
+    }
+
+    fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        self.bounds
+    }
+
+    fn get_wireframe_indices(&self) -> Vec<u16> {
+        self.wireframe_indices.to_vec()
+    }
+}
+
+/// Builds the meshes of the figures of `Figure::ALL`, at the same indices.
+pub fn figure_pool() -> ObjectPool<CachedMesh> {
+    ObjectPool::new(
+        Figure::ALL
+            .iter()
+            .map(|figure| CachedMesh::from_mesh(figure))
+            .collect(),
+    )
+}
@@ -0,0 +1,260 @@
+use std::{cell::RefCell, fmt, path::Path, time::Duration};
+
+use crate::Instant;
+
+/// Whether the `profile_scope!` macro records anything.
+///
+/// The macro compiles to nothing unless the `profiling` feature is enabled.
+pub const ENABLED: bool = cfg!(feature = "profiling");
+
+/// Times the rest of the enclosing block under the given scope name.
+///
+/// Scopes can be nested, the time of a nested scope is accumulated under the
+/// path of its parents (for example `frame/render/encode`).
+#[cfg(feature = "profiling")]
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::profiler::ScopeTimer::new($name);
+    };
+}
+
+/// Times the rest of the enclosing block under the given scope name.
+///
+/// The `profiling` feature is disabled, so this compiles to nothing.
+#[cfg(not(feature = "profiling"))]
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {};
+}
+
+/// The accumulated time of a scope during a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeStats {
+    /// The path of the scope, the names of its parents and its own name
+    /// separated by `/`.
+    pub path: String,
+    /// The total time spent in the scope.
+    pub total: Duration,
+    /// The number of times the scope was entered.
+    pub calls: u32,
+}
+
+impl ScopeStats {
+    /// Returns the nesting depth of the scope, 0 for a top-level scope.
+    pub fn depth(&self) -> usize {
+        self.path.matches('/').count()
+    }
+
+    /// Returns the name of the scope, without its parents.
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// The time spent in each scope during a frame, in order of first entry so
+/// that parents come before their children.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FrameProfile {
+    /// The statistics of each scope.
+    pub scopes: Vec<ScopeStats>,
+}
+
+impl FrameProfile {
+    /// Returns the statistics of the scope at the given path.
+    pub fn get(&self, path: &str) -> Option<&ScopeStats> {
+        self.scopes.iter().find(|scope| scope.path == path)
+    }
+
+    /// Returns the statistics of the scope at the given path, adding the
+    /// scope if it was never entered.
+    fn entry(&mut self, path: String) -> &mut ScopeStats {
+        match self.scopes.iter().position(|scope| scope.path == path) {
+            Some(i) => &mut self.scopes[i],
+            None => {
+                self.scopes.push(ScopeStats {
+                    path,
+                    total: Duration::ZERO,
+                    calls: 0,
+                });
+                self.scopes.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+/// Displays the profile as a stacked breakdown, one scope per line,
+/// indented by depth.
+impl fmt::Display for FrameProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for scope in &self.scopes {
+            writeln!(
+                f,
+                "{:indent$}{} {:.3} ms ({}x)",
+                "",
+                scope.name(),
+                scope.total.as_secs_f64() * 1000.0,
+                scope.calls,
+                indent = 2 * scope.depth(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A complete event of a chrome://tracing trace.
+#[derive(Debug, Clone, PartialEq)]
+struct TraceEvent {
+    /// The name of the scope.
+    name: &'static str,
+    /// The start of the scope, in microseconds since the profiler epoch.
+    start_us: u64,
+    /// The duration of the scope, in microseconds.
+    duration_us: u64,
+}
+
+/// The profiler state of a thread.
+#[derive(Debug)]
+struct Profiler {
+    /// The instant the trace timestamps are relative to.
+    epoch: Instant,
+    /// The names of the scopes currently entered, outermost first.
+    stack: Vec<&'static str>,
+    /// The profile of the current frame.
+    current: FrameProfile,
+    /// The profile of the last complete frame.
+    last: FrameProfile,
+    /// The trace events, if tracing is enabled.
+    events: Option<Vec<TraceEvent>>,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler {
+        epoch: Instant::now(),
+        stack: Vec::new(),
+        current: FrameProfile::default(),
+        last: FrameProfile::default(),
+        events: None,
+    });
+}
+
+/// A timer accumulating the time until it is dropped into the frame profile
+/// of the current thread.
+///
+/// Usually created through the `profile_scope!` macro.
+#[derive(Debug)]
+pub struct ScopeTimer {
+    /// The name of the scope.
+    name: &'static str,
+    /// The instant the scope was entered.
+    start: Instant,
+}
+
+impl ScopeTimer {
+    /// Enters the scope with the given name.
+    pub fn new(name: &'static str) -> Self {
+        PROFILER.with(|profiler| {
+            let mut profiler = profiler.borrow_mut();
+            profiler.stack.push(name);
+
+            // Register the scope on entry so that parents come first.
+            let path = profiler.stack.join("/");
+            profiler.current.entry(path);
+        });
+        Self {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for ScopeTimer {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed();
+        PROFILER.with(|profiler| {
+            let mut profiler = profiler.borrow_mut();
+            let path = profiler.stack.join("/");
+            profiler.stack.pop();
+
+            let scope = profiler.current.entry(path);
+            scope.total += duration;
+            scope.calls += 1;
+
+            let start_us = self.start.duration_since(profiler.epoch).as_micros() as u64;
+            if let Some(events) = profiler.events.as_mut() {
+                events.push(TraceEvent {
+                    name: self.name,
+                    start_us,
+                    duration_us: duration.as_micros() as u64,
+                });
+            }
+        });
+    }
+}
+
+/// Starts a new frame on the current thread.
+///
+/// The profile of the frame that just ended becomes the last frame profile.
+pub fn begin_frame() {
+    PROFILER.with(|profiler| {
+        let mut profiler = profiler.borrow_mut();
+        profiler.last = std::mem::take(&mut profiler.current);
+    });
+}
+
+/// Returns the profile of the current frame of the current thread.
+pub fn current_frame_profile() -> FrameProfile {
+    PROFILER.with(|profiler| profiler.borrow().current.clone())
+}
+
+/// Returns the profile of the last complete frame of the current thread.
+pub fn last_frame_profile() -> FrameProfile {
+    PROFILER.with(|profiler| profiler.borrow().last.clone())
+}
+
+/// Starts recording the scopes of the current thread as trace events.
+pub fn enable_tracing() {
+    PROFILER.with(|profiler| {
+        profiler.borrow_mut().events.get_or_insert_with(Vec::new);
+    });
+}
+
+/// Returns the recorded trace events of the current thread in the
+/// chrome://tracing JSON format.
+pub fn chrome_trace_json() -> String {
+    PROFILER.with(|profiler| {
+        let profiler = profiler.borrow();
+        let events: Vec<serde_json::Value> = profiler
+            .events
+            .iter()
+            .flatten()
+            .map(|event| {
+                serde_json::json!({
+                    "name": event.name,
+                    "cat": "dragonfly",
+                    "ph": "X",
+                    "ts": event.start_us,
+                    "dur": event.duration_us,
+                    "pid": 1,
+                    "tid": 1,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    })
+}
+
+/// Writes the recorded trace events of the current thread to a
+/// chrome://tracing JSON file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_chrome_trace(path: &Path) -> std::io::Result<()> {
+    std::fs::write(path, chrome_trace_json())
+}
@@ -0,0 +1,84 @@
+use crate::{
+    context::Context,
+    vertex::{Indices, Mesh, Vertex},
+};
+
+/// Figures queued to be drawn together, from one vertex buffer and one index
+/// buffer.
+///
+/// Each figure is transformed on the CPU when it is pushed, and its indices
+/// are offset by the number of vertices queued before it, so that the whole
+/// queue is uploaded with one write per buffer and drawn with one call.
+#[derive(Debug, Clone, Default)]
+pub struct RenderQueue {
+    /// The vertices of all the figures, transformed.
+    pub vertices: Vec<Vertex>,
+    /// The indices of all the figures, relative to the first vertex of the
+    /// queue.
+    pub indices: Vec<u32>,
+    /// The number of figures queued.
+    len: usize,
+}
+
+impl RenderQueue {
+    /// Returns an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a figure, or any mesh, with its positions transformed by a 4x4
+    /// matrix in row-major order, as with `Mesh::transform_vertices`.
+    pub fn push(&mut self, figure: &dyn Mesh, transform: &[[f32; 4]; 4]) {
+        let offset = self.vertices.len() as u32;
+        self.vertices.extend(figure.transform_vertices(transform));
+        self.indices.extend(
+            figure
+                .get_index_data()
+                .to_u32()
+                .into_iter()
+                .map(|i| i + offset),
+        );
+        self.len += 1;
+    }
+
+    /// Returns the number of figures queued.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no figure is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes every figure from the queue.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.len = 0;
+    }
+
+    /// Displays the queued figures on the window, as one mesh.
+    ///
+    /// The mesh is set as with `Context::set_mesh_unfitted`, so the figures
+    /// keep their transformed positions, and drawn with a single draw call.
+    pub fn flush(&self, context: &mut Context) {
+        context.set_mesh_unfitted(self);
+    }
+}
+
+/// The queue is a mesh of all its figures, with 32-bit indices once they no
+/// longer fit in 16 bits.
+impl Mesh for RenderQueue {
+    fn get_vertices(&self) -> Vec<Vertex> {
+        self.vertices.clone()
+    }
+
+    fn get_indices(&self) -> Vec<u16> {
+        self.indices.iter().map(|&i| i as u16).collect()
+    }
+
+    fn get_index_data(&self) -> Indices {
+        Indices::for_vertex_count(self.indices.clone(), self.vertices.len())
+    }
+}
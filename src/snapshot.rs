@@ -0,0 +1,447 @@
+use std::{io, path::Path};
+
+use wgpu::util::DeviceExt;
+
+use crate::{
+    camera::Camera2D,
+    context::{
+        create_depth_texture, create_figure_pipeline, create_pipeline_layout, create_shader,
+        create_uniform_bind_group_layout, depth_attachment, write_growing_buffer, Context,
+    },
+    error::DragonflyError,
+    frame_hash,
+    uniform::{CameraUniform, TimeUniform, TransformUniform},
+    vertex::Mesh,
+};
+
+/// The format of the snapshot textures, matching the sRGB surfaces.
+const SNAPSHOT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Renders a mesh to an off-screen texture, without a window, and returns
+/// its pixels in 8-bit RGBA, row by row from the top.
+///
+/// The wave effect is disabled, so that the snapshot does not depend on the
+/// time. The size must be non-zero.
+///
+/// # Errors
+///
+/// Returns an error if no adapter is available or if the device cannot be
+/// created.
+pub fn render_snapshot(
+    mesh: &dyn Mesh,
+    width: u32,
+    height: u32,
+    background: wgpu::Color,
+) -> Result<Vec<u8>, DragonflyError> {
+    render(mesh, width, height, background, None)
+}
+
+/// Renders a mesh to an off-screen texture as `render_snapshot`, with the
+/// figures drawn by the given WGSL shader instead of the built-in one.
+///
+/// The shader must have the entry points of `shaders/shader.wgsl`, as for
+/// `Context::with_custom_shader`.
+///
+/// # Errors
+///
+/// Returns an error if no adapter is available, if the device cannot be
+/// created, or if the shader does not compile.
+pub fn render_snapshot_with_shader(
+    mesh: &dyn Mesh,
+    width: u32,
+    height: u32,
+    background: wgpu::Color,
+    source: &str,
+) -> Result<Vec<u8>, DragonflyError> {
+    render(mesh, width, height, background, Some(source))
+}
+
+/// Renders a mesh to an off-screen texture, with the given shader or the
+/// built-in one.
+fn render(
+    mesh: &dyn Mesh,
+    width: u32,
+    height: u32,
+    background: wgpu::Color,
+    shader: Option<&str>,
+) -> Result<Vec<u8>, DragonflyError> {
+    let mut context = ContextHeadless::create(width, height, shader)?;
+    context.background = background;
+    Ok(context.render_figure(mesh))
+}
+
+/// A graphics context rendering to an off-screen texture instead of a
+/// window, for the tests and the screenshots of the CI, where no display is
+/// available.
+///
+/// The device, the texture and the pipeline are created once, so that many
+/// figures can be rendered in turn, and the vertex and index buffers are
+/// reused from one mesh to the next. The wave effect is disabled, so that
+/// the renders do not depend on the time.
+#[derive(Debug)]
+pub struct ContextHeadless {
+    /// The device the figures are rendered with.
+    device: wgpu::Device,
+    /// The queue of the device.
+    queue: wgpu::Queue,
+    /// The texture the figures are rendered to.
+    texture: wgpu::Texture,
+    /// The layout of the render pipeline, kept to rebuild it.
+    pipeline_layout: wgpu::PipelineLayout,
+    /// The bind group of the time and camera uniforms.
+    uniform_bind_group: wgpu::BindGroup,
+    /// The shader module the figures are drawn with, kept to rebuild the
+    /// pipeline.
+    shader: wgpu::ShaderModule,
+    /// The pipeline drawing the figures.
+    render_pipeline: wgpu::RenderPipeline,
+    /// The vertex buffer, grown to fit the largest mesh set.
+    vertex_buffer: wgpu::Buffer,
+    /// The number of vertices of the current mesh.
+    num_vertices: u32,
+    /// The index buffer, grown to fit the largest mesh set.
+    index_buffer: wgpu::Buffer,
+    /// The number of indices of the current mesh.
+    num_indices: u32,
+    /// The format of the indices of the current mesh.
+    index_format: wgpu::IndexFormat,
+    /// The depth buffer, of the size of the texture.
+    depth_texture: wgpu::Texture,
+    /// The color the texture is cleared with before each render.
+    pub background: wgpu::Color,
+}
+
+impl ContextHeadless {
+    /// Creates a context rendering to a texture of the given size, cleared
+    /// in white. The size must be non-zero.
+    ///
+    /// No surface is created, so the adapter is requested without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no adapter is available or if the device cannot
+    /// be created.
+    pub fn new(width: u32, height: u32) -> Result<Self, DragonflyError> {
+        Self::create(width, height, None)
+    }
+
+    /// Creates a context drawing the figures with the given shader or the
+    /// built-in one.
+    fn create(width: u32, height: u32, shader: Option<&str>) -> Result<Self, DragonflyError> {
+        assert!(
+            width > 0 && height > 0,
+            "The snapshot size must be non-zero"
+        );
+
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or(DragonflyError::AdapterRequest)?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))?;
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Snapshot Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SNAPSHOT_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        // Freeze the animation.
+        let mut time_uniform = TimeUniform::new();
+        time_uniform.set_wave(false);
+        let time_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Time Buffer"),
+            contents: bytemuck::bytes_of(&time_uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::bytes_of(&CameraUniform::new(&Camera2D::default())),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let transform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Transform Buffer"),
+            contents: bytemuck::bytes_of(&TransformUniform::default()),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let uniform_bind_group_layout = create_uniform_bind_group_layout(&device);
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Uniform Bind Group"),
+            layout: &uniform_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: time_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: camera_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: transform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let shader = match shader {
+            Some(source) => {
+                Context::with_custom_shader(&device, source).map_err(DragonflyError::Shader)?
+            }
+            None => create_shader(&device),
+        };
+        let pipeline_layout = create_pipeline_layout(&device, &uniform_bind_group_layout);
+        let render_pipeline = create_figure_pipeline(
+            &device,
+            &pipeline_layout,
+            SNAPSHOT_FORMAT,
+            1,
+            &shader,
+            Some(wgpu::Face::Back),
+        );
+
+        // The buffers are created empty and grown by the first mesh set.
+        let create_buffer = |label, usage| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: 0,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let vertex_buffer = create_buffer("Vertex Buffer", wgpu::BufferUsages::VERTEX);
+        let index_buffer = create_buffer("Index Buffer", wgpu::BufferUsages::INDEX);
+
+        let depth_texture = create_depth_texture(&device, width, height, 1);
+
+        Ok(Self {
+            device,
+            queue,
+            texture,
+            depth_texture,
+            pipeline_layout,
+            uniform_bind_group,
+            shader,
+            render_pipeline,
+            vertex_buffer,
+            num_vertices: 0,
+            index_buffer,
+            num_indices: 0,
+            index_format: wgpu::IndexFormat::Uint16,
+            background: wgpu::Color::WHITE,
+        })
+    }
+
+    /// Returns the width and height of the texture, in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.texture.width(), self.texture.height())
+    }
+
+    /// Returns the number of vertices of the current mesh.
+    pub fn num_vertices(&self) -> u32 {
+        self.num_vertices
+    }
+
+    /// Returns the number of indices of the current mesh.
+    pub fn num_indices(&self) -> u32 {
+        self.num_indices
+    }
+
+    /// Sets the faces of the triangles that are not drawn, the back faces by
+    /// default, or `None` to draw both faces.
+    ///
+    /// The pipeline is rebuilt with the same shader module and layout.
+    pub fn set_cull_mode(&mut self, cull_mode: Option<wgpu::Face>) {
+        self.render_pipeline = create_figure_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            SNAPSHOT_FORMAT,
+            1,
+            &self.shader,
+            cull_mode,
+        );
+    }
+
+    /// Sets the mesh rendered by `render`, which may be any type implementing
+    /// `Mesh`.
+    ///
+    /// The vertices and indices are written to the existing buffers with
+    /// `queue.write_buffer`, which are only replaced by larger ones when the
+    /// mesh does not fit.
+    pub fn set_mesh(&mut self, mesh: &dyn Mesh) {
+        let vertices = mesh.get_vertices();
+        let indices = mesh.get_index_data();
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.vertex_buffer,
+            "Vertex Buffer",
+            wgpu::BufferUsages::VERTEX,
+            bytemuck::cast_slice(&vertices),
+        );
+        write_growing_buffer(
+            &self.device,
+            &self.queue,
+            &mut self.index_buffer,
+            "Index Buffer",
+            wgpu::BufferUsages::INDEX,
+            indices.as_bytes(),
+        );
+        self.num_vertices = vertices.len() as u32;
+        self.num_indices = indices.len() as u32;
+        self.index_format = indices.format();
+    }
+
+    /// Renders the current mesh and returns the pixels of the texture in
+    /// 8-bit RGBA, row by row from the top.
+    pub fn render(&mut self) -> Vec<u8> {
+        let view = self
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = self
+            .depth_texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Snapshot Encoder"),
+            });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(self.background),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(depth_attachment(&depth_view)),
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            // An empty mesh only clears the texture.
+            if self.num_indices > 0 {
+                render_pass.set_pipeline(&self.render_pipeline);
+                render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(self.index_buffer.slice(..), self.index_format);
+                render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+            }
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        frame_hash::read_texture(&self.device, &self.queue, &self.texture)
+    }
+
+    /// Renders a figure, or any mesh, and returns the pixels of the texture
+    /// in 8-bit RGBA, row by row from the top.
+    ///
+    /// The mesh is set as the current mesh, as with `set_mesh`.
+    pub fn render_figure(&mut self, figure: &dyn Mesh) -> Vec<u8> {
+        self.set_mesh(figure);
+        self.render()
+    }
+}
+
+/// Encodes 8-bit RGBA pixels, row by row from the top, as a PNG image.
+///
+/// The image data is stored without compression.
+pub fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    assert_eq!(
+        pixels.len(),
+        (width * height * 4) as usize,
+        "The pixels do not match the image size"
+    );
+
+    // Each row starts with its filter type, 0 for none.
+    let row_len = (width * 4) as usize;
+    let mut raw = Vec::with_capacity((row_len + 1) * height as usize);
+    for row in pixels.chunks(row_len.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    // A zlib stream of stored deflate blocks.
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, default compression, filtering and no
+    // interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Writes 8-bit RGBA pixels, row by row from the top, to a PNG file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_png(path: &Path, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    std::fs::write(path, encode_png(width, height, pixels))
+}
+
+/// Appends a PNG chunk: its length, type, data and CRC.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Computes the CRC-32 of the PNG chunks.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Computes the Adler-32 checksum of the zlib streams.
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MODULUS;
+        (a, (b + a) % MODULUS)
+    });
+    (b << 16) | a
+}
@@ -0,0 +1,229 @@
+use crate::vertex::Figure;
+
+/// A description of a figure that can be saved and loaded.
+///
+/// Mirrors each variant of `Figure`. With the `serialize` feature, the specs
+/// are tagged by their `type` field, for example
+/// `{"type": "circle", "segments": 64}`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum FigureSpec {
+    Triangle,
+    Pentagon,
+    Rectangle,
+    Trapezoid,
+    Parallelogram,
+    Circle {
+        segments: u32,
+    },
+    Icosphere {
+        level: u8,
+    },
+    Torus {
+        major_radius: f32,
+        minor_radius: f32,
+        major_segments: u32,
+        minor_segments: u32,
+    },
+    Cylinder {
+        segments: u32,
+    },
+    Cone {
+        segments: u32,
+    },
+    Pyramid,
+    Tetrahedron,
+    Overlap,
+    Hexagon,
+    Hexagram,
+    Diamond,
+    Cross,
+    Arrow {
+        shaft_length: f32,
+        shaft_width: f32,
+        head_width: f32,
+        head_length: f32,
+    },
+    RoundedRectangle {
+        width: f32,
+        height: f32,
+        corner_radius: f32,
+        segments: u32,
+    },
+    Heart {
+        segments: u32,
+    },
+    Plane {
+        subdivisions: u32,
+    },
+    Lathe {
+        profile: Vec<[f32; 2]>,
+        segments: u32,
+    },
+}
+
+impl From<Figure> for FigureSpec {
+    fn from(figure: Figure) -> Self {
+        match figure {
+            Figure::Triangle => FigureSpec::Triangle,
+            Figure::Pentagon => FigureSpec::Pentagon,
+            Figure::Rectangle => FigureSpec::Rectangle,
+            Figure::Trapezoid => FigureSpec::Trapezoid,
+            Figure::Parallelogram => FigureSpec::Parallelogram,
+            Figure::Circle(segments) => FigureSpec::Circle { segments },
+            Figure::Icosphere(level) => FigureSpec::Icosphere { level },
+            Figure::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            } => FigureSpec::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            },
+            Figure::Cylinder { segments } => FigureSpec::Cylinder { segments },
+            Figure::Cone { segments } => FigureSpec::Cone { segments },
+            Figure::Pyramid => FigureSpec::Pyramid,
+            Figure::Tetrahedron => FigureSpec::Tetrahedron,
+            Figure::Overlap => FigureSpec::Overlap,
+            Figure::Hexagon => FigureSpec::Hexagon,
+            Figure::Hexagram => FigureSpec::Hexagram,
+            Figure::Diamond => FigureSpec::Diamond,
+            Figure::Cross => FigureSpec::Cross,
+            Figure::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            } => FigureSpec::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            },
+            Figure::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            } => FigureSpec::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            },
+            Figure::Heart { segments } => FigureSpec::Heart { segments },
+            Figure::Plane { subdivisions } => FigureSpec::Plane { subdivisions },
+            Figure::Lathe { profile, segments } => FigureSpec::Lathe { profile, segments },
+        }
+    }
+}
+
+impl From<FigureSpec> for Figure {
+    fn from(spec: FigureSpec) -> Self {
+        match spec {
+            FigureSpec::Triangle => Figure::Triangle,
+            FigureSpec::Pentagon => Figure::Pentagon,
+            FigureSpec::Rectangle => Figure::Rectangle,
+            FigureSpec::Trapezoid => Figure::Trapezoid,
+            FigureSpec::Parallelogram => Figure::Parallelogram,
+            FigureSpec::Circle { segments } => Figure::Circle(segments),
+            FigureSpec::Icosphere { level } => Figure::Icosphere(level),
+            FigureSpec::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            } => Figure::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            },
+            FigureSpec::Cylinder { segments } => Figure::Cylinder { segments },
+            FigureSpec::Cone { segments } => Figure::Cone { segments },
+            FigureSpec::Pyramid => Figure::Pyramid,
+            FigureSpec::Tetrahedron => Figure::Tetrahedron,
+            FigureSpec::Overlap => Figure::Overlap,
+            FigureSpec::Hexagon => Figure::Hexagon,
+            FigureSpec::Hexagram => Figure::Hexagram,
+            FigureSpec::Diamond => Figure::Diamond,
+            FigureSpec::Cross => Figure::Cross,
+            FigureSpec::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            } => Figure::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            },
+            FigureSpec::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            } => Figure::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            },
+            FigureSpec::Heart { segments } => Figure::Heart { segments },
+            FigureSpec::Plane { subdivisions } => Figure::Plane { subdivisions },
+            FigureSpec::Lathe { profile, segments } => Figure::Lathe { profile, segments },
+        }
+    }
+}
+
+/// A scene that can be saved and loaded, made of several figures.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SceneSpec {
+    /// The figures of the scene.
+    pub figures: Vec<FigureSpec>,
+}
+
+#[cfg(feature = "serialize")]
+impl SceneSpec {
+    /// Returns the JSON representation of the scene.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize scene")
+    }
+
+    /// Parses a scene from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or describes an unknown
+    /// figure.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl Figure {
+    /// Returns the JSON representation of the figure, as its `FigureSpec`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&FigureSpec::from(self.clone())).expect("Failed to serialize figure")
+    }
+
+    /// Parses a figure from its JSON representation, as its `FigureSpec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or describes an unknown
+    /// figure.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<FigureSpec>(json).map(Figure::from)
+    }
+}
@@ -0,0 +1,397 @@
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+    camera::ZOOM_STEP,
+    dialog::DialogKind,
+    dragonfly::{
+        BACKGROUND_PRESETS, CULL_MODES, GRADIENT_PRESETS, MORPH_DURATION, TRANSITION_DURATION,
+    },
+    input::{InputEvent, Key},
+    vertex::{Figure, Gradient},
+};
+
+/// An effect of an input on the application, applied to the window and the
+/// graphics context.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppEvent {
+    /// The figure at the given index must be displayed.
+    FigureChanged(u8),
+    /// The figure at index `from` must morph into the figure at index `to`,
+    /// over the given duration in seconds.
+    MorphRequested { from: u8, to: u8, duration: f32 },
+    /// The background color changed.
+    BackgroundChanged(wgpu::Color),
+    /// The figure is colored with the given gradient, or with its own
+    /// colors.
+    GradientChanged(Option<Gradient>),
+    /// The grid was shown or hidden.
+    GridToggled(bool),
+    /// The figure is drawn as a wireframe, or filled.
+    WireframeToggled(bool),
+    /// The faces of the triangles not drawn changed, `None` to draw both
+    /// faces.
+    CullModeChanged(Option<wgpu::Face>),
+    /// The figure was clicked at the given position, in normalized device
+    /// coordinates.
+    Clicked([f32; 2]),
+    /// The zoom of the camera must be multiplied by `factor`, around the
+    /// point at the given normalized device coordinates.
+    ZoomRequested { factor: f32, at: [f32; 2] },
+    /// The cursor was dragged by the given offset, in normalized device
+    /// coordinates, while panning.
+    Dragged([f32; 2]),
+    /// The camera must frame the current figure.
+    FitRequested,
+    /// The camera must be reset to its default state.
+    CameraReset,
+    /// The current figure must be subdivided once, to smooth it.
+    SubdivideRequested,
+    /// The surface must be resized to the given physical size.
+    Resized { width: u32, height: u32 },
+    /// A file dialog must be opened.
+    DialogRequested(DialogKind),
+    /// The profile of the last frame must be logged.
+    ProfileRequested,
+    /// A new frame must be requested from the window.
+    RedrawRequested,
+    /// A frame must be rendered.
+    Render,
+    /// The application must exit.
+    Exit,
+}
+
+/// The state of the application, independent of the window and the GPU.
+///
+/// The state is updated from normalized `InputEvent`s, and each update
+/// returns the `AppEvent`s to apply.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AppState {
+    /// The current figure, one of `Figure::ALL`.
+    pub figure: Figure,
+    /// The index of the current background preset in `BACKGROUND_PRESETS`.
+    pub bg_idx: usize,
+    /// The background color, if set.
+    pub background: Option<wgpu::Color>,
+    /// The index of the current gradient in `GRADIENT_PRESETS`, plus one,
+    /// or 0 for the own colors of the figures.
+    pub gradient_idx: usize,
+    /// Whether the grid is drawn behind the figure.
+    pub grid: bool,
+    /// Whether the figure is drawn as a wireframe instead of being filled.
+    pub wireframe: bool,
+    /// The index of the current cull mode in `CULL_MODES`.
+    pub cull_idx: usize,
+    /// Whether cycling through the figures with `Space` morphs them into
+    /// each other instead of switching between them.
+    pub transitions: bool,
+    /// Whether the figure spins around its center.
+    pub spin: bool,
+    /// Whether a control key is held.
+    pub ctrl: bool,
+    /// Whether a shift key is held.
+    pub shift: bool,
+    /// The keys held down.
+    pub held_keys: HashSet<Key>,
+    /// The physical size of the window, once known.
+    pub size: Option<(u32, u32)>,
+    /// The position of the cursor, in normalized device coordinates.
+    pub cursor_pos: [f32; 2],
+    /// Whether the left mouse button is held.
+    pub mouse_pressed: bool,
+    /// Whether the middle or right mouse button is held, to drag the camera.
+    pub panning: bool,
+    /// The time elapsed waiting for inputs.
+    pub elapsed: Duration,
+    /// The number of frames rendered.
+    pub frames: u64,
+    /// Whether the application is exiting.
+    pub exiting: bool,
+}
+
+impl AppState {
+    /// Updates the state with an input and returns the events to apply.
+    pub fn handle(&mut self, event: &InputEvent) -> Vec<AppEvent> {
+        match *event {
+            InputEvent::Resized { width, height } => {
+                self.size = Some((width, height));
+                vec![
+                    AppEvent::Resized { width, height },
+                    AppEvent::RedrawRequested,
+                ]
+            }
+            InputEvent::ModifiersChanged { ctrl, shift } => {
+                self.ctrl = ctrl;
+                self.shift = shift;
+                Vec::new()
+            }
+            InputEvent::KeyPressed(key) => {
+                self.held_keys.insert(key);
+                match key {
+                    Key::Space
+                    | Key::ArrowLeft
+                    | Key::ArrowRight
+                    | Key::ArrowUp
+                    | Key::ArrowDown => vec![AppEvent::RedrawRequested],
+                    _ => Vec::new(),
+                }
+            }
+            InputEvent::KeyReleased(key) => {
+                self.held_keys.remove(&key);
+                self.key_released(key)
+            }
+            InputEvent::FocusLost => {
+                self.held_keys.clear();
+                Vec::new()
+            }
+            InputEvent::CursorMoved { x, y } => {
+                let Some(size) = self.size else {
+                    return Vec::new();
+                };
+                let [old_x, old_y] = self.cursor_pos;
+                self.cursor_pos = pixel_to_ndc([x, y], size);
+                if self.panning {
+                    let [new_x, new_y] = self.cursor_pos;
+                    vec![
+                        AppEvent::Dragged([new_x - old_x, new_y - old_y]),
+                        AppEvent::RedrawRequested,
+                    ]
+                } else {
+                    Vec::new()
+                }
+            }
+            InputEvent::MousePressed => {
+                self.mouse_pressed = true;
+                vec![AppEvent::Clicked(self.cursor_pos)]
+            }
+            InputEvent::MouseReleased => {
+                self.mouse_pressed = false;
+                Vec::new()
+            }
+            InputEvent::PanPressed => {
+                self.panning = true;
+                Vec::new()
+            }
+            InputEvent::PanReleased => {
+                self.panning = false;
+                Vec::new()
+            }
+            InputEvent::Scrolled { delta } if delta > 0.0 => vec![
+                AppEvent::ZoomRequested {
+                    factor: ZOOM_STEP,
+                    at: self.cursor_pos,
+                },
+                AppEvent::RedrawRequested,
+            ],
+            InputEvent::Scrolled { delta } if delta < 0.0 => vec![
+                AppEvent::ZoomRequested {
+                    factor: 1.0 / ZOOM_STEP,
+                    at: self.cursor_pos,
+                },
+                AppEvent::RedrawRequested,
+            ],
+            InputEvent::Scrolled { .. } => Vec::new(),
+            InputEvent::Wait(duration) => {
+                self.elapsed += duration;
+                Vec::new()
+            }
+            InputEvent::Redraw => {
+                self.frames += 1;
+                vec![AppEvent::Render]
+            }
+            InputEvent::CloseRequested => {
+                self.exiting = true;
+                vec![AppEvent::Exit]
+            }
+        }
+    }
+
+    /// Returns the direction the camera pans to, from the held arrow keys.
+    ///
+    /// Opposite keys cancel each other, and the direction is not normalized.
+    pub fn pan_direction(&self) -> [f32; 2] {
+        let axis = |negative, positive| {
+            self.held_keys.contains(&positive) as i32 as f32
+                - self.held_keys.contains(&negative) as i32 as f32
+        };
+        [
+            axis(Key::ArrowLeft, Key::ArrowRight),
+            axis(Key::ArrowDown, Key::ArrowUp),
+        ]
+    }
+
+    /// Returns the index of the current figure in `Figure::ALL`.
+    pub fn fig_idx(&self) -> u8 {
+        self.figure.index().unwrap_or_default()
+    }
+
+    /// Selects the next figure, wrapping around after the last one.
+    pub fn next_figure(&mut self) -> Vec<AppEvent> {
+        self.show_figure(self.figure.next())
+    }
+
+    /// Selects the previous figure, wrapping around before the first one.
+    pub fn prev_figure(&mut self) -> Vec<AppEvent> {
+        self.show_figure(self.figure.prev())
+    }
+
+    /// Selects the figure at the given index.
+    ///
+    /// Returns `None` if there is no figure at this index.
+    pub fn set_figure(&mut self, fig_idx: u8) -> Option<Vec<AppEvent>> {
+        let figure = Figure::try_from_index(fig_idx)?;
+        Some(self.show_figure(figure))
+    }
+
+    /// Selects the given figure of `Figure::ALL`.
+    fn show_figure(&mut self, figure: Figure) -> Vec<AppEvent> {
+        self.figure = figure;
+        vec![
+            AppEvent::FigureChanged(self.fig_idx()),
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Resets the camera and selects the first figure.
+    pub fn reset_view(&mut self) -> Vec<AppEvent> {
+        let mut events = self.show_figure(Figure::ALL[0].clone());
+        events.insert(1, AppEvent::CameraReset);
+        events
+    }
+
+    /// Selects the next figure, morphing the current figure into it.
+    pub fn morph_to_next_figure(&mut self) -> Vec<AppEvent> {
+        self.morph_to(self.figure.next(), MORPH_DURATION)
+    }
+
+    /// Selects the given figure of `Figure::ALL`, morphing the current
+    /// figure into it over the given duration in seconds.
+    fn morph_to(&mut self, figure: Figure, duration: f32) -> Vec<AppEvent> {
+        let from = self.fig_idx();
+        self.figure = figure;
+        vec![
+            AppEvent::MorphRequested {
+                from,
+                to: self.fig_idx(),
+                duration,
+            },
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Toggles between morphing the figures cycled through with `Space` into
+    /// each other and switching between them.
+    pub fn toggle_transitions(&mut self) -> Vec<AppEvent> {
+        self.transitions = !self.transitions;
+        Vec::new()
+    }
+
+    /// Starts or stops spinning the figure.
+    ///
+    /// The figure keeps its rotation when it stops, and spins on from it.
+    pub fn toggle_spin(&mut self) -> Vec<AppEvent> {
+        self.spin = !self.spin;
+        vec![AppEvent::RedrawRequested]
+    }
+
+    /// Selects the next background preset, wrapping around after the last
+    /// one.
+    pub fn next_background(&mut self) -> Vec<AppEvent> {
+        self.bg_idx = (self.bg_idx + 1) % BACKGROUND_PRESETS.len();
+        let background = BACKGROUND_PRESETS[self.bg_idx];
+        self.background = Some(background);
+        vec![
+            AppEvent::BackgroundChanged(background),
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Returns the current gradient, or `None` for the own colors of the
+    /// figures.
+    pub fn gradient(&self) -> Option<Gradient> {
+        self.gradient_idx
+            .checked_sub(1)
+            .map(|i| GRADIENT_PRESETS[i])
+    }
+
+    /// Selects the next gradient preset, wrapping around to the own colors
+    /// of the figures after the last one.
+    pub fn next_gradient(&mut self) -> Vec<AppEvent> {
+        self.gradient_idx = (self.gradient_idx + 1) % (GRADIENT_PRESETS.len() + 1);
+        vec![
+            AppEvent::GradientChanged(self.gradient()),
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Shows or hides the grid.
+    pub fn toggle_grid(&mut self) -> Vec<AppEvent> {
+        self.grid = !self.grid;
+        vec![AppEvent::GridToggled(self.grid), AppEvent::RedrawRequested]
+    }
+
+    /// Toggles between the filled figure and its wireframe.
+    pub fn toggle_wireframe(&mut self) -> Vec<AppEvent> {
+        self.wireframe = !self.wireframe;
+        vec![
+            AppEvent::WireframeToggled(self.wireframe),
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Selects the next cull mode, wrapping around after the last one.
+    pub fn next_cull_mode(&mut self) -> Vec<AppEvent> {
+        self.cull_idx = (self.cull_idx + 1) % CULL_MODES.len();
+        vec![
+            AppEvent::CullModeChanged(CULL_MODES[self.cull_idx]),
+            AppEvent::RedrawRequested,
+        ]
+    }
+
+    /// Handles the release of a key, where the shortcuts are triggered.
+    fn key_released(&mut self, key: Key) -> Vec<AppEvent> {
+        match key {
+            Key::Space if self.transitions => {
+                let figure = if self.shift {
+                    self.figure.prev()
+                } else {
+                    self.figure.next()
+                };
+                self.morph_to(figure, TRANSITION_DURATION)
+            }
+            Key::Space if self.shift => self.prev_figure(),
+            Key::Space => self.next_figure(),
+            Key::A => self.toggle_spin(),
+            Key::C if self.shift => self.next_cull_mode(),
+            Key::C => self.next_background(),
+            Key::F => vec![AppEvent::FitRequested, AppEvent::RedrawRequested],
+            Key::G if self.shift => self.next_gradient(),
+            Key::G => self.toggle_grid(),
+            Key::M => self.morph_to_next_figure(),
+            Key::R => self.reset_view(),
+            Key::T => self.toggle_transitions(),
+            Key::W => self.toggle_wireframe(),
+            Key::F10 => vec![AppEvent::ProfileRequested],
+            Key::Plus => vec![AppEvent::SubdivideRequested, AppEvent::RedrawRequested],
+            #[cfg(feature = "dialogs")]
+            Key::O if self.ctrl => vec![AppEvent::DialogRequested(DialogKind::Open)],
+            #[cfg(feature = "dialogs")]
+            Key::S if self.ctrl && self.shift => {
+                vec![AppEvent::DialogRequested(DialogKind::ExportMesh)]
+            }
+            #[cfg(feature = "dialogs")]
+            Key::S if self.ctrl => vec![AppEvent::DialogRequested(DialogKind::SaveScene)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Converts a physical position in a window of the given size, from the top
+/// left corner, to normalized device coordinates, from -1 to 1 with the Y
+/// axis pointing up.
+pub fn pixel_to_ndc(position: [f32; 2], (width, height): (u32, u32)) -> [f32; 2] {
+    let [x, y] = position;
+    [
+        2.0 * x / width.max(1) as f32 - 1.0,
+        1.0 - 2.0 * y / height.max(1) as f32,
+    ]
+}
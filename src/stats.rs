@@ -0,0 +1,236 @@
+use std::{collections::VecDeque, fmt};
+
+/// The number of frames whose statistics are kept.
+pub const FRAME_STATS_CAPACITY: usize = 120;
+
+/// The statistics of a rendered frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// The number of draw calls.
+    pub draw_calls: u32,
+    /// The number of vertices drawn, once per vertex of the meshes of the
+    /// indexed draw calls.
+    pub total_vertices: u32,
+    /// The number of indices drawn.
+    pub total_indices: u32,
+    /// The time spent in `Context::render`, from the acquisition of the
+    /// frame to its presentation, in milliseconds.
+    pub frame_ms: f32,
+    /// The time the GPU spent in the render pass, in milliseconds, if the
+    /// GPU timing is enabled.
+    pub gpu_ms: Option<f32>,
+}
+
+/// The minimum, maximum and average statistics of the recorded frames.
+///
+/// The average numbers of draw calls, vertices and indices are rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsSummary {
+    /// The number of frames summarized.
+    pub frames: usize,
+    /// The minimum of each statistic.
+    pub min: FrameStats,
+    /// The maximum of each statistic.
+    pub max: FrameStats,
+    /// The average of each statistic.
+    pub avg: FrameStats,
+}
+
+impl fmt::Display for StatsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} frames: {:.2} ms (min {:.2}, max {:.2})",
+            self.frames, self.avg.frame_ms, self.min.frame_ms, self.max.frame_ms
+        )?;
+        if let (Some(avg), Some(min), Some(max)) =
+            (self.avg.gpu_ms, self.min.gpu_ms, self.max.gpu_ms)
+        {
+            write!(f, ", GPU {:.2} ms (min {:.2}, max {:.2})", avg, min, max)?;
+        }
+        write!(
+            f,
+            ", {} draw calls, {} vertices, {} indices",
+            self.avg.draw_calls, self.avg.total_vertices, self.avg.total_indices
+        )
+    }
+}
+
+/// The statistics of the last `FRAME_STATS_CAPACITY` frames.
+#[derive(Debug, Default, Clone)]
+pub struct FrameStatsHistory {
+    /// The statistics of the frames, from the oldest to the newest.
+    frames: VecDeque<FrameStats>,
+}
+
+impl FrameStatsHistory {
+    /// Records the statistics of a frame, dropping the oldest frame when
+    /// the history is full.
+    pub fn push(&mut self, stats: FrameStats) {
+        if self.frames.len() == FRAME_STATS_CAPACITY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(stats);
+    }
+
+    /// Returns the number of frames recorded.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether no frame has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the statistics of the last frame, if any.
+    pub fn last(&self) -> Option<&FrameStats> {
+        self.frames.back()
+    }
+
+    /// Returns the minimum, maximum and average statistics of the recorded
+    /// frames, all zero if there are none.
+    ///
+    /// The GPU times are summarized over the frames which have one.
+    pub fn summary(&self) -> StatsSummary {
+        let Some(first) = self.frames.front() else {
+            return StatsSummary::default();
+        };
+
+        let (mut min, mut max) = (*first, *first);
+        let mut sum = [0.0f64; 4];
+        let (mut gpu_sum, mut gpu_frames) = (0.0f64, 0);
+        for stats in &self.frames {
+            min.draw_calls = min.draw_calls.min(stats.draw_calls);
+            max.draw_calls = max.draw_calls.max(stats.draw_calls);
+            min.total_vertices = min.total_vertices.min(stats.total_vertices);
+            max.total_vertices = max.total_vertices.max(stats.total_vertices);
+            min.total_indices = min.total_indices.min(stats.total_indices);
+            max.total_indices = max.total_indices.max(stats.total_indices);
+            min.frame_ms = min.frame_ms.min(stats.frame_ms);
+            max.frame_ms = max.frame_ms.max(stats.frame_ms);
+            if let Some(gpu_ms) = stats.gpu_ms {
+                min.gpu_ms = Some(min.gpu_ms.map_or(gpu_ms, |min| min.min(gpu_ms)));
+                max.gpu_ms = Some(max.gpu_ms.map_or(gpu_ms, |max| max.max(gpu_ms)));
+                gpu_sum += gpu_ms as f64;
+                gpu_frames += 1;
+            }
+
+            sum[0] += stats.draw_calls as f64;
+            sum[1] += stats.total_vertices as f64;
+            sum[2] += stats.total_indices as f64;
+            sum[3] += stats.frame_ms as f64;
+        }
+
+        let frames = self.frames.len();
+        let [draw_calls, total_vertices, total_indices, frame_ms] = sum.map(|s| s / frames as f64);
+        StatsSummary {
+            frames,
+            min,
+            max,
+            avg: FrameStats {
+                draw_calls: draw_calls.round() as u32,
+                total_vertices: total_vertices.round() as u32,
+                total_indices: total_indices.round() as u32,
+                frame_ms: frame_ms as f32,
+                gpu_ms: (gpu_frames > 0).then(|| (gpu_sum / gpu_frames as f64) as f32),
+            },
+        }
+    }
+}
+
+/// Measures the time the GPU spends in a render pass with timestamp queries.
+#[derive(Debug)]
+pub struct GpuTimer {
+    /// The timestamps of the beginning and the end of the pass.
+    query_set: wgpu::QuerySet,
+    /// The buffer the timestamps are resolved to.
+    resolve_buffer: wgpu::Buffer,
+    /// The buffer the resolved timestamps are copied to, to be read back.
+    readback_buffer: wgpu::Buffer,
+    /// The number of nanoseconds per timestamp tick.
+    period: f32,
+}
+
+/// The size of the two timestamps of a pass.
+const TIMESTAMPS_SIZE: wgpu::BufferAddress = 2 * std::mem::size_of::<u64>() as wgpu::BufferAddress;
+
+impl GpuTimer {
+    /// Creates the queries and buffers of the timer, or returns `None` if the
+    /// device has no `TIMESTAMP_QUERY` feature.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Timestamp Query Set"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Resolve Buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Readback Buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period: queue.get_timestamp_period(),
+        })
+    }
+
+    /// Returns the timestamp writes of a render pass measured by the timer.
+    pub fn timestamp_writes(&self) -> wgpu::RenderPassTimestampWrites<'_> {
+        wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(0),
+            end_of_pass_write_index: Some(1),
+        }
+    }
+
+    /// Encodes the copy of the timestamps to the readback buffer, after the
+    /// measured pass.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.resolve_query_set(&self.query_set, 0..2, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            TIMESTAMPS_SIZE,
+        );
+    }
+
+    /// Waits for the submitted commands and returns the duration of the
+    /// measured pass in milliseconds.
+    ///
+    /// Returns `None` if the readback buffer cannot be mapped.
+    pub fn read_ms(&self, device: &wgpu::Device) -> Option<f32> {
+        let slice = self.readback_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().ok()?.ok()?;
+
+        let ticks = {
+            let mapped = slice.get_mapped_range();
+            let timestamp =
+                |i: usize| bytemuck::pod_read_unaligned::<u64>(&mapped[i * 8..i * 8 + 8]);
+            timestamp(1).saturating_sub(timestamp(0))
+        };
+        self.readback_buffer.unmap();
+        Some(ticks as f32 * self.period / 1_000_000.0)
+    }
+}
@@ -0,0 +1,171 @@
+//! Helpers to test the application without a window or a GPU.
+//!
+//! Sequences of inputs are written with a small builder, then replayed on an
+//! `AppState` with a `Harness`:
+//!
+//! ```
+//! use dragonfly::{input::Key, state::AppEvent, testing};
+//!
+//! let harness = testing::run(testing::events().resize(800, 600).key(Key::Space).redraw());
+//! assert_eq!(harness.state.fig_idx(), 1);
+//! assert!(harness.events.contains(&AppEvent::Render));
+//! ```
+
+use std::time::Duration;
+
+use crate::{
+    input::{InputEvent, Key},
+    state::{AppEvent, AppState},
+};
+
+/// Starts a new sequence of inputs.
+pub fn events() -> EventSequence {
+    EventSequence::default()
+}
+
+/// Replays a sequence of inputs on the default state.
+pub fn run(sequence: EventSequence) -> Harness {
+    let mut harness = Harness::default();
+    harness.run(sequence);
+    harness
+}
+
+/// A sequence of inputs, built one input at a time.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EventSequence {
+    events: Vec<InputEvent>,
+}
+
+impl EventSequence {
+    /// Resizes the window to the given physical size.
+    pub fn resize(mut self, width: u32, height: u32) -> Self {
+        self.events.push(InputEvent::Resized { width, height });
+        self
+    }
+
+    /// Presses and releases a key.
+    pub fn key(mut self, key: Key) -> Self {
+        self.events.push(InputEvent::KeyPressed(key));
+        self.events.push(InputEvent::KeyReleased(key));
+        self
+    }
+
+    /// Presses and releases a key while holding `Ctrl`.
+    pub fn ctrl_key(self, key: Key) -> Self {
+        self.with_modifiers(true, false, key)
+    }
+
+    /// Presses and releases a key while holding `Shift`.
+    pub fn shift_key(self, key: Key) -> Self {
+        self.with_modifiers(false, true, key)
+    }
+
+    /// Presses and releases a key while holding `Ctrl` and `Shift`.
+    pub fn ctrl_shift_key(self, key: Key) -> Self {
+        self.with_modifiers(true, true, key)
+    }
+
+    /// Moves the cursor to the given physical position.
+    pub fn cursor(mut self, x: f32, y: f32) -> Self {
+        self.events.push(InputEvent::CursorMoved { x, y });
+        self
+    }
+
+    /// Presses and releases the left mouse button.
+    pub fn click(mut self) -> Self {
+        self.events.push(InputEvent::MousePressed);
+        self.events.push(InputEvent::MouseReleased);
+        self
+    }
+
+    /// Drags the cursor with the right mouse button from a physical position
+    /// to another.
+    pub fn drag(mut self, from: [f32; 2], to: [f32; 2]) -> Self {
+        self.events.push(InputEvent::CursorMoved {
+            x: from[0],
+            y: from[1],
+        });
+        self.events.push(InputEvent::PanPressed);
+        self.events
+            .push(InputEvent::CursorMoved { x: to[0], y: to[1] });
+        self.events.push(InputEvent::PanReleased);
+        self
+    }
+
+    /// Scrolls the mouse wheel by the given number of lines, up if positive.
+    pub fn scroll(mut self, lines: f32) -> Self {
+        self.events.push(InputEvent::Scrolled { delta: lines });
+        self
+    }
+
+    /// Waits for the given number of milliseconds.
+    pub fn wait_ms(mut self, milliseconds: u64) -> Self {
+        self.events
+            .push(InputEvent::Wait(Duration::from_millis(milliseconds)));
+        self
+    }
+
+    /// Renders a frame.
+    pub fn redraw(mut self) -> Self {
+        self.events.push(InputEvent::Redraw);
+        self
+    }
+
+    /// Asks the window to close.
+    pub fn close(mut self) -> Self {
+        self.events.push(InputEvent::CloseRequested);
+        self
+    }
+
+    /// Returns the inputs of the sequence.
+    pub fn build(self) -> Vec<InputEvent> {
+        self.events
+    }
+
+    /// Presses and releases a key with the given modifiers held.
+    fn with_modifiers(mut self, ctrl: bool, shift: bool, key: Key) -> Self {
+        self.events
+            .push(InputEvent::ModifiersChanged { ctrl, shift });
+        self = self.key(key);
+        self.events.push(InputEvent::ModifiersChanged {
+            ctrl: false,
+            shift: false,
+        });
+        self
+    }
+}
+
+/// Drives the application state with sequences of inputs and records the
+/// events it emits.
+#[derive(Debug, Default, Clone)]
+pub struct Harness {
+    /// The state of the application.
+    pub state: AppState,
+    /// The events emitted by the state, in order.
+    pub events: Vec<AppEvent>,
+}
+
+impl Harness {
+    /// Creates a harness starting from the given state.
+    pub fn with_state(state: AppState) -> Self {
+        Self {
+            state,
+            events: Vec::new(),
+        }
+    }
+
+    /// Replays a sequence of inputs, recording the emitted events.
+    pub fn run(&mut self, sequence: EventSequence) -> &mut Self {
+        for input in sequence.build() {
+            let events = self.state.handle(&input);
+            self.events.extend(events);
+        }
+        self
+    }
+
+    /// Clears the recorded events.
+    pub fn clear_events(&mut self) -> &mut Self {
+        self.events.clear();
+        self
+    }
+}
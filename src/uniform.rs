@@ -0,0 +1,105 @@
+use bytemuck;
+
+use crate::camera::Camera2D;
+
+/// Time data uploaded to the shaders every frame.
+///
+/// The time is expressed in seconds since the start of the application and
+/// the delta time is the duration of the previous frame, in seconds.
+///
+/// The `wave` flag enables (non-zero) or disables (zero) the wave effect
+/// applied in the vertex shader, and `angle` spins the figure around the Z
+/// axis. The struct size is a multiple of 16 bytes, as required for uniform
+/// buffers.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TimeUniform {
+    /// The time elapsed since the start of the application, in seconds.
+    pub time: f32,
+    /// The duration of the previous frame, in seconds.
+    pub delta_time: f32,
+    /// Whether the wave effect is enabled (non-zero) or not (zero).
+    pub wave: u32,
+    /// The rotation of the figure around the Z axis, in radians
+    /// counter-clockwise.
+    pub angle: f32,
+}
+
+impl TimeUniform {
+    /// Returns a new time uniform at time zero with the wave effect enabled
+    /// and no rotation.
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            delta_time: 0.0,
+            wave: 1,
+            angle: 0.0,
+        }
+    }
+
+    /// Updates the time and the delta time, in seconds.
+    pub fn update(&mut self, time: f32, delta_time: f32) {
+        self.time = time;
+        self.delta_time = delta_time;
+    }
+
+    /// Enables or disables the wave effect.
+    pub fn set_wave(&mut self, enabled: bool) {
+        self.wave = enabled as u32;
+    }
+
+    /// Sets the rotation of the figure around the Z axis, in radians.
+    pub fn set_angle(&mut self, angle: f32) {
+        self.angle = angle;
+    }
+}
+
+/// Camera data uploaded to the shaders whenever the camera moves.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CameraUniform {
+    /// The view-projection matrix, in column-major order.
+    pub view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    /// Returns the uniform of the given camera.
+    pub fn new(camera: &Camera2D) -> Self {
+        Self {
+            view_proj: camera.view_proj(),
+        }
+    }
+}
+
+/// Transform data uploaded to the shaders whenever the figure is moved.
+///
+/// The matrix is applied to the positions of the figure before the camera,
+/// so the identity leaves the figure where its mesh puts it.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TransformUniform {
+    /// The model matrix, in column-major order.
+    pub transform: [[f32; 4]; 4],
+}
+
+impl TransformUniform {
+    /// The identity matrix.
+    pub const IDENTITY: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    /// Returns the uniform of the given matrix.
+    pub fn new(transform: [[f32; 4]; 4]) -> Self {
+        Self { transform }
+    }
+}
+
+impl Default for TransformUniform {
+    /// Returns the identity transform.
+    fn default() -> Self {
+        Self::new(Self::IDENTITY)
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Vertex;
+
+/// The distance under which two positions are considered to be the same
+/// point when extracting boundaries.
+const WELD_EPSILON: f32 = 1e-5;
+
+/// Returns the canonical index of every vertex, so that vertices sharing the
+/// same position (within `WELD_EPSILON`) share the same canonical index.
+///
+/// The canonical index of a vertex is the index of the first vertex with the
+/// same position.
+fn canonical_indices(vertices: &[Vertex]) -> Vec<u16> {
+    let mut first: HashMap<[i64; 3], u16> = HashMap::new();
+    vertices
+        .iter()
+        .enumerate()
+        .map(|(i, vertex)| {
+            let key = vertex.position.map(|c| (c / WELD_EPSILON).round() as i64);
+            *first.entry(key).or_insert(i as u16)
+        })
+        .collect()
+}
+
+/// Returns the boundary edges of a triangle mesh, as pairs of indices for a
+/// `LineList` topology, in the order of the triangles.
+///
+/// As for `boundary_loop`, the boundary edges are the edges used by a single
+/// triangle once the vertices sharing the same position are merged, so they
+/// outline a flat figure and a closed mesh has none. The edges keep the
+/// original indices of their vertices.
+pub fn boundary_edges(vertices: &[Vertex], indices: &[u16]) -> Vec<u16> {
+    let canonical_of = canonical_indices(vertices);
+    let canonical = |i: u16| canonical_of[i as usize];
+
+    let edges: Vec<(u16, u16)> = indices
+        .chunks_exact(3)
+        .filter(|triangle| triangle.iter().all(|&i| (i as usize) < vertices.len()))
+        .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+        .filter(|&(a, b)| canonical(a) != canonical(b))
+        .collect();
+
+    let key = |(a, b): (u16, u16)| {
+        let (a, b) = (canonical(a), canonical(b));
+        (a.min(b), a.max(b))
+    };
+    let mut counts: HashMap<(u16, u16), u32> = HashMap::new();
+    for &edge in &edges {
+        *counts.entry(key(edge)).or_insert(0) += 1;
+    }
+
+    edges
+        .into_iter()
+        .filter(|&edge| counts[&key(edge)] == 1)
+        .flat_map(|(a, b)| [a, b])
+        .collect()
+}
+
+/// Extracts the outer boundary loop of a triangle mesh.
+///
+/// The boundary edges are the edges used by a single triangle, once the
+/// vertices sharing the same position are merged (for example the seam
+/// vertex of the circle). They are chained in the direction of their
+/// triangles, so the loop of a counter-clockwise mesh is counter-clockwise.
+///
+/// When the mesh has several loops, the one with the most vertices is
+/// returned. Returns `None` if the mesh has no boundary (closed 3D meshes)
+/// or if the boundary edges cannot be chained into a loop.
+pub fn boundary_loop(vertices: &[Vertex], indices: &[u16]) -> Option<Vec<u16>> {
+    let canonical_of = canonical_indices(vertices);
+    let canonical = |i: u16| canonical_of.get(i as usize).copied();
+
+    // Count the triangles using each undirected edge.
+    let mut directed_edges: Vec<(u16, u16)> = Vec::with_capacity(indices.len());
+    let mut counts: HashMap<(u16, u16), u32> = HashMap::new();
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [
+            canonical(triangle[0])?,
+            canonical(triangle[1])?,
+            canonical(triangle[2])?,
+        ];
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            if from == to {
+                continue;
+            }
+            directed_edges.push((from, to));
+            *counts.entry((from.min(to), from.max(to))).or_insert(0) += 1;
+        }
+    }
+
+    // Keep the edges used by a single triangle.
+    let next: HashMap<u16, u16> = directed_edges
+        .into_iter()
+        .filter(|&(from, to)| counts[&(from.min(to), from.max(to))] == 1)
+        .collect();
+
+    // Chain the edges into loops, starting from the smallest index for
+    // deterministic results.
+    let mut starts: Vec<u16> = next.keys().copied().collect();
+    starts.sort_unstable();
+
+    let mut visited: HashSet<u16> = HashSet::new();
+    let mut best: Option<Vec<u16>> = None;
+    for start in starts {
+        if !visited.insert(start) {
+            continue;
+        }
+
+        let mut boundary = vec![start];
+        let mut current = *next.get(&start)?;
+        while current != start {
+            if !visited.insert(current) {
+                return None;
+            }
+            boundary.push(current);
+            current = *next.get(&current)?;
+        }
+
+        if best.as_ref().is_none_or(|best| boundary.len() > best.len()) {
+            best = Some(boundary);
+        }
+    }
+
+    best
+}
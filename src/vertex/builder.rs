@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use super::{Mesh, MeshData, Vertex, FLAT_NORMAL};
+
+/// Builds a mesh vertex by vertex and triangle by triangle.
+///
+/// The vertices pushed with `push_vertex` face the viewer and have no
+/// texture coordinates; `compute_normals` and `generate_planar_uvs` can be
+/// applied to the built mesh. The number of vertices is limited by the `u16`
+/// indices: pushing a vertex past that limit panics.
+#[derive(Debug, Default)]
+pub struct MeshBuilder {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+    /// The index of each distinct vertex, when deduplicating.
+    dedup: Option<HashMap<VertexKey, u16>>,
+}
+
+/// The bits of a vertex, to find identical vertices.
+type VertexKey = ([u32; 3], [u32; 4], [u32; 3], [u32; 2]);
+
+impl MeshBuilder {
+    /// Returns an empty builder, not deduplicating the vertices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether identical vertices are merged, pushing a vertex equal to
+    /// a previous one returning the index of the previous one.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup.then(|| {
+            self.vertices
+                .iter()
+                .enumerate()
+                .map(|(i, vertex)| (key(vertex), i as u16))
+                .collect()
+        });
+        self
+    }
+
+    /// Returns the number of vertices pushed so far.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Pushes a vertex facing the viewer, and returns its index.
+    pub fn push_vertex(&mut self, position: [f32; 3], color: [f32; 4]) -> u16 {
+        self.push(Vertex {
+            position,
+            color,
+            normal: FLAT_NORMAL,
+            tex_coords: [0.0, 0.0],
+        })
+    }
+
+    /// Pushes a triangle of previously pushed vertices, which should be
+    /// counter-clockwise to face the viewer.
+    pub fn push_triangle(&mut self, a: u16, b: u16, c: u16) {
+        self.indices.extend([a, b, c]);
+    }
+
+    /// Pushes a quad of previously pushed vertices, given counter-clockwise,
+    /// as the triangles `a b c` and `a c d`.
+    pub fn push_quad(&mut self, a: u16, b: u16, c: u16, d: u16) {
+        self.push_triangle(a, b, c);
+        self.push_triangle(a, c, d);
+    }
+
+    /// Appends the vertices and triangles of a mesh, keeping its normals and
+    /// texture coordinates. The triangles with indices out of range are
+    /// ignored.
+    pub fn extend_from_mesh(&mut self, mesh: &dyn Mesh) {
+        let remapped: Vec<u16> = mesh
+            .get_vertices()
+            .into_iter()
+            .map(|vertex| self.push(vertex))
+            .collect();
+        for triangle in mesh.get_indices().chunks_exact(3) {
+            if let [Some(&a), Some(&b), Some(&c)] =
+                [0, 1, 2].map(|k| remapped.get(triangle[k] as usize))
+            {
+                self.push_triangle(a, b, c);
+            }
+        }
+    }
+
+    /// Returns the built mesh.
+    pub fn build(self) -> MeshData {
+        MeshData {
+            vertices: self.vertices,
+            indices: self.indices,
+        }
+    }
+
+    fn push(&mut self, vertex: Vertex) -> u16 {
+        if let Some(&index) = self.dedup.as_ref().and_then(|d| d.get(&key(&vertex))) {
+            return index;
+        }
+
+        let index = u16::try_from(self.vertices.len())
+            .expect("too many vertices to be indexed with u16 indices");
+        self.vertices.push(vertex);
+        if let Some(dedup) = &mut self.dedup {
+            dedup.insert(key(&vertex), index);
+        }
+        index
+    }
+}
+
+fn key(vertex: &Vertex) -> VertexKey {
+    (
+        vertex.position.map(f32::to_bits),
+        vertex.color.map(f32::to_bits),
+        vertex.normal.map(f32::to_bits),
+        vertex.tex_coords.map(f32::to_bits),
+    )
+}
@@ -0,0 +1,55 @@
+use super::Vertex;
+
+/// Converts a color from HSV to RGB.
+///
+/// The hue is in degrees and wraps around, so 360 is red again, as is -360.
+/// The saturation and the value range from 0.0 to 1.0.
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let chroma = v * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let [r, g, b] = match h as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    let m = v - chroma;
+    [r + m, g + m, b + m]
+}
+
+/// Returns the hue, in degrees from 0 to 360, of a direction in the XY
+/// plane: red along the positive X axis, then green and blue
+/// counter-clockwise. A zero direction has no angle, and gets a hue of 0.
+fn hue_of(dx: f32, dy: f32) -> f32 {
+    if dx == 0.0 && dy == 0.0 {
+        return 0.0;
+    }
+    dy.atan2(dx).to_degrees().rem_euclid(360.0)
+}
+
+/// Colors the vertices as a color wheel, keeping their alpha.
+///
+/// The hue of each vertex is its angle around the centroid of the vertices,
+/// the average of their positions, in the XY plane. The vertices at the
+/// centroid get a hue of 0, red.
+pub fn apply_rainbow(vertices: &mut [Vertex]) {
+    if vertices.is_empty() {
+        return;
+    }
+    let sum = vertices.iter().fold([0.0; 2], |sum: [f32; 2], vertex| {
+        [sum[0] + vertex.position[0], sum[1] + vertex.position[1]]
+    });
+    let center = sum.map(|c| c / vertices.len() as f32);
+
+    for vertex in vertices {
+        let hue = hue_of(
+            vertex.position[0] - center[0],
+            vertex.position[1] - center[1],
+        );
+        let [r, g, b] = hsv_to_rgb(hue, 1.0, 1.0);
+        vertex.color = [r, g, b, vertex.color[3]];
+    }
+}
@@ -0,0 +1,85 @@
+use super::Vertex;
+
+/// The axis along which a gradient goes from its first color to its last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From the leftmost vertices to the rightmost ones.
+    Horizontal,
+    /// From the lowest vertices to the highest ones.
+    Vertical,
+    /// From the vertices nearest to the center of the bounding box to the
+    /// farthest ones.
+    Radial,
+}
+
+/// A linear gradient between two colors, applied with `apply_gradient`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    /// The color at the start of the gradient.
+    pub from: [f32; 3],
+    /// The color at the end of the gradient.
+    pub to: [f32; 3],
+    /// The axis of the gradient.
+    pub direction: GradientDirection,
+}
+
+impl Gradient {
+    /// Colors the vertices with the gradient, as `apply_gradient`.
+    pub fn apply(&self, vertices: &mut [Vertex]) {
+        apply_gradient(vertices, self.from, self.to, self.direction);
+    }
+}
+
+/// Colors the vertices with a gradient from `from` to `to`, keeping their
+/// alpha.
+///
+/// Each vertex is projected onto the axis of the gradient, normalized by the
+/// bounds of the vertices, so the vertices at one extreme get exactly `from`
+/// and those at the other exactly `to`. The color only depends on the
+/// position, so the vertices sharing a position share a color. When the
+/// vertices do not extend along the axis, they get the middle color.
+pub fn apply_gradient(
+    vertices: &mut [Vertex],
+    from: [f32; 3],
+    to: [f32; 3],
+    direction: GradientDirection,
+) {
+    let (min, max) = vertices.iter().fold(
+        ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+        |(min, max), vertex| {
+            (
+                std::array::from_fn(|i| min[i].min(vertex.position[i])),
+                std::array::from_fn(|i| max[i].max(vertex.position[i])),
+            )
+        },
+    );
+    let center: [f32; 3] = std::array::from_fn(|i| (min[i] + max[i]) / 2.0);
+    let distance = |position: [f32; 3]| {
+        (0..3)
+            .map(|i| (position[i] - center[i]).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    };
+    let project = |position: [f32; 3]| match direction {
+        GradientDirection::Horizontal => position[0],
+        GradientDirection::Vertical => position[1],
+        GradientDirection::Radial => distance(position),
+    };
+
+    let (start, end) = vertices
+        .iter()
+        .map(|vertex| project(vertex.position))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(start, end), p| {
+            (start.min(p), end.max(p))
+        });
+    for vertex in vertices {
+        let t = if end > start {
+            (project(vertex.position) - start) / (end - start)
+        } else {
+            0.5
+        };
+        // Weighing both ends keeps the extremes exact.
+        let [r, g, b] = std::array::from_fn(|i| from[i] * (1.0 - t) + to[i] * t);
+        vertex.color = [r, g, b, vertex.color[3]];
+    }
+}
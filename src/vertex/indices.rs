@@ -0,0 +1,62 @@
+/// The indices of a mesh, stored in 16 bits when every vertex can be
+/// addressed with `u16`, and in 32 bits otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indices {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Indices {
+    /// Returns the indices stored in the smallest format addressing the
+    /// given number of vertices.
+    pub fn for_vertex_count(indices: Vec<u32>, vertex_count: usize) -> Self {
+        if vertex_count > u16::MAX as usize {
+            Indices::U32(indices)
+        } else {
+            Indices::U16(indices.into_iter().map(|i| i as u16).collect())
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U16(indices) => indices.len(),
+            Indices::U32(indices) => indices.len(),
+        }
+    }
+
+    /// Returns whether there is no index.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the format of the index buffer holding the indices.
+    pub fn format(&self) -> wgpu::IndexFormat {
+        match self {
+            Indices::U16(_) => wgpu::IndexFormat::Uint16,
+            Indices::U32(_) => wgpu::IndexFormat::Uint32,
+        }
+    }
+
+    /// Returns the indices as bytes, to be uploaded to an index buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Indices::U16(indices) => bytemuck::cast_slice(indices),
+            Indices::U32(indices) => bytemuck::cast_slice(indices),
+        }
+    }
+
+    /// Returns the indices widened to `u32`.
+    pub fn to_u32(&self) -> Vec<u32> {
+        match self {
+            Indices::U16(indices) => indices.iter().map(|&i| i as u32).collect(),
+            Indices::U32(indices) => indices.clone(),
+        }
+    }
+}
+
+impl From<Vec<u16>> for Indices {
+    fn from(indices: Vec<u16>) -> Self {
+        Indices::U16(indices)
+    }
+}
@@ -0,0 +1,149 @@
+use super::{normals::normalize, Mesh, Vertex};
+
+/// The half size of the box centered at the origin that `normalized` fits
+/// the meshes in, leaving a margin inside clip space.
+pub const FIT_HALF_EXTENT: f32 = 0.9;
+
+/// The vertices and indices of a mesh, owned and ready to be transformed,
+/// merged or uploaded.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshData {
+    /// The vertices of the mesh.
+    pub vertices: Vec<Vertex>,
+    /// The indices of the triangles of the mesh.
+    pub indices: Vec<u16>,
+}
+
+impl MeshData {
+    /// Returns the mesh translated by the given offset.
+    pub fn translated(self, offset: [f32; 3]) -> Self {
+        self.map_vertices(
+            |[x, y, z]| [x + offset[0], y + offset[1], z + offset[2]],
+            |normal| normal,
+        )
+    }
+
+    /// Returns the mesh scaled uniformly by the given factor, around the
+    /// origin.
+    pub fn scaled(self, factor: f32) -> Self {
+        self.scaled_xyz([factor; 3])
+    }
+
+    /// Returns the mesh scaled by the given factor along each axis, around
+    /// the origin.
+    ///
+    /// A scale mirroring the XY plane (a negative factor along exactly one of
+    /// X and Y) reverses the triangles, so that they stay counter-clockwise
+    /// on screen and are not culled.
+    ///
+    /// The normals are transformed by the inverse of the scale, to stay
+    /// perpendicular to the surface.
+    pub fn scaled_xyz(self, factors: [f32; 3]) -> Self {
+        let mirrored = (factors[0] < 0.0) != (factors[1] < 0.0);
+        let [fx, fy, fz] = factors;
+        let mut mesh = self.map_vertices(
+            |[x, y, z]| [x * fx, y * fy, z * fz],
+            // The cofactors avoid dividing by a zero factor.
+            |[x, y, z]| {
+                let sign = (fx * fy * fz).signum();
+                [x * fy * fz * sign, y * fx * fz * sign, z * fx * fy * sign]
+            },
+        );
+        if mirrored {
+            for triangle in mesh.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+        mesh
+    }
+
+    /// Returns the mesh scaled uniformly and translated to fit
+    /// `[-FIT_HALF_EXTENT, FIT_HALF_EXTENT]` on every axis, centered at the
+    /// origin, keeping its proportions.
+    ///
+    /// A mesh with no extent, such as a single point, is only translated.
+    pub fn normalized(&self) -> Self {
+        let (offset, factor) = fit_transform(self.bounds());
+        self.clone().translated(offset).scaled(factor)
+    }
+
+    /// Returns the mesh rotated counter-clockwise around the Z axis by the
+    /// given angle, in radians.
+    pub fn rotated_z(self, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let rotate = move |[x, y, z]: [f32; 3]| [x * cos - y * sin, x * sin + y * cos, z];
+        self.map_vertices(rotate, rotate)
+    }
+
+    /// Applies a function to the position and another to the normal of every
+    /// vertex, leaving the colors and the indices untouched.
+    ///
+    /// The transformed normals are normalized, and kept unchanged if they
+    /// vanish.
+    fn map_vertices(
+        mut self,
+        f: impl Fn([f32; 3]) -> [f32; 3],
+        g: impl Fn([f32; 3]) -> [f32; 3],
+    ) -> Self {
+        for vertex in &mut self.vertices {
+            vertex.position = f(vertex.position);
+            vertex.normal = normalize(g(vertex.normal)).unwrap_or(vertex.normal);
+        }
+        self
+    }
+}
+
+/// Returns the offset centering a bounding box at the origin, and the factor
+/// then scaling its largest side to `2.0 * FIT_HALF_EXTENT`.
+///
+/// The factor is 1 for a box with no extent.
+pub(crate) fn fit_transform((min, max): ([f32; 3], [f32; 3])) -> ([f32; 3], f32) {
+    let offset = std::array::from_fn(|i| -(min[i] + max[i]) / 2.0);
+    let extent = (0..3).map(|i| max[i] - min[i]).fold(0.0, f32::max);
+    let factor = if extent > 0.0 {
+        2.0 * FIT_HALF_EXTENT / extent
+    } else {
+        1.0
+    };
+    (offset, factor)
+}
+
+impl From<(Vec<Vertex>, Vec<u16>)> for MeshData {
+    fn from((vertices, indices): (Vec<Vertex>, Vec<u16>)) -> Self {
+        Self { vertices, indices }
+    }
+}
+
+impl Mesh for MeshData {
+    fn get_vertices(&self) -> Vec<Vertex> {
+        self.vertices.clone()
+    }
+
+    fn get_indices(&self) -> Vec<u16> {
+        self.indices.clone()
+    }
+}
+
+/// Lets an ad-hoc pair of vertices and indices be passed as a mesh.
+impl Mesh for (Vec<Vertex>, Vec<u16>) {
+    fn get_vertices(&self) -> Vec<Vertex> {
+        self.0.clone()
+    }
+
+    fn get_indices(&self) -> Vec<u16> {
+        self.1.clone()
+    }
+}
+
+/// Lets borrowed vertices and indices be passed as a mesh, without owning
+/// them.
+impl Mesh for (&[Vertex], &[u16]) {
+    fn get_vertices(&self) -> Vec<Vertex> {
+        self.0.to_vec()
+    }
+
+    fn get_indices(&self) -> Vec<u16> {
+        self.1.to_vec()
+    }
+}
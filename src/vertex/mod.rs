@@ -1,20 +1,167 @@
+mod boundary;
+mod builder;
+mod color;
+mod gradient;
+mod indices;
+mod mesh_data;
+mod morph;
+mod name;
+mod normals;
+mod obj;
+mod outline;
+mod ply;
+mod repair;
+mod similarity;
+mod stats;
+mod stl;
+mod subdivide;
+mod svg;
+mod uv;
+#[allow(clippy::module_inception)]
 pub mod vertex;
+mod weld;
 
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
+
+pub use boundary::{boundary_edges, boundary_loop};
+pub use builder::MeshBuilder;
+pub use color::{apply_rainbow, hsv_to_rgb};
+pub use gradient::{apply_gradient, Gradient, GradientDirection};
+pub use indices::Indices;
+pub(crate) use mesh_data::fit_transform;
+pub use mesh_data::{MeshData, FIT_HALF_EXTENT};
+pub use morph::morph;
+pub use name::ParseFigureError;
+pub use normals::{compute_normals, FLAT_NORMAL};
+pub use obj::{parse_obj, ObjError};
+pub use ply::PlyError;
+pub use repair::{check_import, repair, validate, ImportReport, MeshError, MeshIssue};
+pub use similarity::{mesh_similarity, MAX_SHAPE_DISTANCE};
+pub use stats::{mesh_stats, MeshStats};
+pub use stl::StlError;
+pub use subdivide::{subdivide, SubdivisionError};
+pub use svg::SvgError;
+pub use uv::generate_planar_uvs;
 pub use vertex::Vertex;
+pub use weld::weld;
+
+/// The maximum subdivision level of an icosphere.
+///
+/// An icosphere of level `n` has `10 * 4^n + 2` vertices, so level 6 (40962
+/// vertices) is the last one that can be indexed with `u16`.
+pub const MAX_ICOSPHERE_LEVEL: u8 = 6;
+
+/// The maximum number of subdivisions of a plane.
+///
+/// A plane with `n` subdivisions has `(n + 2)^2` vertices, so 254
+/// subdivisions (65536 vertices) is the largest grid that can be indexed
+/// with `u16`.
+pub const MAX_PLANE_SUBDIVISIONS: u32 = 254;
+
+/// The maximum number of segments of a cylinder.
+///
+/// A cylinder of `n` segments has `4n + 2` vertices, so 16383 segments is
+/// the most that can be indexed with `u16`.
+pub const MAX_CYLINDER_SEGMENTS: u32 = 16383;
+
+/// The maximum number of segments of a cone.
+///
+/// A cone of `n` segments has `2n + 2` vertices, so 32767 segments is the
+/// most that can be indexed with `u16`.
+pub const MAX_CONE_SEGMENTS: u32 = 32767;
+
+/// The maximum number of segments of a torus, around each of its circles.
+///
+/// A torus of `m` by `n` segments has `m * n` vertices, so 256 segments
+/// around both circles is the most that can be indexed with `u16`.
+pub const MAX_TORUS_SEGMENTS: u32 = 256;
+
+/// The maximum number of segments per corner of a rounded rectangle.
+///
+/// A rounded rectangle of `n` segments per corner has `4n + 1` vertices, so
+/// 16383 segments is the most that can be indexed with `u16`.
+pub const MAX_ROUNDED_RECTANGLE_SEGMENTS: u32 = 16383;
+
+/// The maximum number of segments of a heart.
+///
+/// A heart of `n` segments has `n + 1` vertices, so 65535 segments is the
+/// most that can be indexed with `u16`.
+pub const MAX_HEART_SEGMENTS: u32 = u16::MAX as u32;
 
 /// Represents a geometric figure that can be rendered.
 ///
 /// The `Figure` enum defines various geometric shapes that can be used for
 /// rendering.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum Figure {
     #[default]
     Triangle,
     Pentagon,
+    /// A regular hexagon of radius 0.5, with a corner on the positive X
+    /// axis.
+    Hexagon,
+    /// A six-pointed star, with its tips 0.5 from the origin, one pointing
+    /// up, and its inner corners `0.5 / √3` from the origin.
+    Hexagram,
+    /// A rhombus with its corners on the axes, 0.5 from the origin.
+    Diamond,
+    /// A plus sign spanning from -0.5 to 0.5 along both axes, with arms 0.2
+    /// wide.
+    Cross,
+    /// An arrow pointing right, centered on the origin: a shaft followed by
+    /// a triangular head. See `Figure::arrow` for the valid proportions.
+    Arrow {
+        shaft_length: f32,
+        shaft_width: f32,
+        head_width: f32,
+        head_length: f32,
+    },
+    /// A rectangle of the given size centered on the origin, its corners
+    /// rounded with quarter circles of `segments` vertices.
+    RoundedRectangle {
+        width: f32,
+        height: f32,
+        corner_radius: f32,
+        segments: u32,
+    },
+    /// A heart sampled at `segments` points of its parametric curve, fitted
+    /// into the [-0.5, 0.5] square.
+    Heart {
+        segments: u32,
+    },
     Rectangle,
     Trapezoid,
     Parallelogram,
     Circle(u32),
+    Icosphere(u8),
+    Torus {
+        major_radius: f32,
+        minor_radius: f32,
+        major_segments: u32,
+        minor_segments: u32,
+    },
+    Cylinder {
+        segments: u32,
+    },
+    Cone {
+        segments: u32,
+    },
+    Pyramid,
+    Tetrahedron,
+    /// Two overlapping half-transparent squares, red and blue, showing the
+    /// alpha blending.
+    Overlap,
+    Plane {
+        subdivisions: u32,
+    },
+    Lathe {
+        profile: Vec<[f32; 2]>,
+        segments: u32,
+    },
 }
 
 /// A trait representing a mesh, which is a collection of vertices and indices.
@@ -26,6 +173,190 @@ pub trait Mesh {
 
     /// Returns a vector of indices that define the order of vertices to be used for rendering.
     fn get_indices(&self) -> Vec<u16>;
+
+    /// Returns the indices of the mesh in the format used by the index
+    /// buffer.
+    ///
+    /// The default implementation returns `get_indices` in 16 bits. A mesh
+    /// with more vertices than `u16` can address overrides it to return
+    /// 32-bit indices, `get_indices` then being truncated.
+    fn get_index_data(&self) -> Indices {
+        Indices::U16(self.get_indices())
+    }
+
+    /// Returns the vertices and indices of the mesh, to be transformed.
+    fn mesh_data(&self) -> MeshData {
+        MeshData {
+            vertices: self.get_vertices(),
+            indices: self.get_indices(),
+        }
+    }
+
+    /// Returns the corners of the axis-aligned bounding box of the mesh: the
+    /// minimum and the maximum of the vertex coordinates.
+    ///
+    /// An empty mesh has an empty box at the origin.
+    fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        let vertices = self.get_vertices();
+        let Some(first) = vertices.first() else {
+            return ([0.0; 3], [0.0; 3]);
+        };
+
+        vertices
+            .iter()
+            .fold((first.position, first.position), |(min, max), vertex| {
+                (
+                    std::array::from_fn(|i| min[i].min(vertex.position[i])),
+                    std::array::from_fn(|i| max[i].max(vertex.position[i])),
+                )
+            })
+    }
+
+    /// Returns the centroid of the mesh, the average position of its
+    /// vertices.
+    ///
+    /// An empty mesh has its centroid at the origin.
+    fn centroid(&self) -> [f32; 3] {
+        let vertices = self.get_vertices();
+        if vertices.is_empty() {
+            return [0.0; 3];
+        }
+
+        let sum = vertices.iter().fold([0.0; 3], |sum: [f32; 3], vertex| {
+            std::array::from_fn(|i| sum[i] + vertex.position[i])
+        });
+        sum.map(|c| c / vertices.len() as f32)
+    }
+
+    /// Returns the corners of the bounding box of the mesh in the XY plane:
+    /// `(min_xy, max_xy)`.
+    ///
+    /// This is the projection of `bounds`; implementors may override it for
+    /// efficiency.
+    fn get_bounding_box(&self) -> ([f32; 2], [f32; 2]) {
+        let (min, max) = self.bounds();
+        ([min[0], min[1]], [max[0], max[1]])
+    }
+
+    /// Returns the centroid of the mesh in the XY plane, the average
+    /// position of its vertices.
+    fn get_centroid(&self) -> [f32; 2] {
+        let [x, y, _] = self.centroid();
+        [x, y]
+    }
+
+    /// Returns the signed area of a 2D mesh in the XY plane, positive when
+    /// its outer boundary is counter-clockwise and negative when it is
+    /// clockwise.
+    ///
+    /// The area is computed with the shoelace formula over the boundary
+    /// loop, so the inner vertices, such as the center of a circle, are
+    /// ignored. A mesh without a boundary has no area.
+    fn get_signed_area(&self) -> f32 {
+        let vertices = self.get_vertices();
+        let Some(boundary) = boundary_loop(&vertices, &self.get_indices()) else {
+            return 0.0;
+        };
+
+        let twice_area: f32 = boundary
+            .iter()
+            .zip(boundary.iter().cycle().skip(1))
+            .map(|(&a, &b)| {
+                let [xa, ya, _] = vertices[a as usize].position;
+                let [xb, yb, _] = vertices[b as usize].position;
+                xa * yb - xb * ya
+            })
+            .sum();
+        twice_area / 2.0
+    }
+
+    /// Returns the area of a 2D mesh in the XY plane, whatever its winding.
+    fn get_area(&self) -> f32 {
+        self.get_signed_area().abs()
+    }
+
+    /// Returns the number of vertices and triangles of the mesh, the area
+    /// of its triangles and the length of its boundary.
+    ///
+    /// See `mesh_stats`.
+    fn stats(&self) -> MeshStats {
+        mesh_stats(&self.get_vertices(), &self.get_indices())
+    }
+
+    /// Returns the average length of the edges of the triangles of the mesh.
+    ///
+    /// The edges are taken triangle by triangle, so an edge shared by two
+    /// triangles counts twice. A mesh without triangles has an average edge
+    /// length of 0.
+    fn average_edge_length(&self) -> f32 {
+        let vertices = self.get_vertices();
+        let indices = self.get_indices();
+        let triangles = indices.chunks_exact(3);
+        if triangles.len() == 0 {
+            return 0.0;
+        }
+
+        let count = 3 * triangles.len();
+        let total: f32 = triangles
+            .flat_map(|triangle| [(0, 1), (1, 2), (2, 0)].map(|(a, b)| (triangle[a], triangle[b])))
+            .map(|(a, b)| vertices[a as usize].distance_to(&vertices[b as usize]))
+            .sum();
+        total / count as f32
+    }
+
+    /// Returns the vertices of the mesh with their positions transformed by
+    /// a 4x4 matrix, in row-major order.
+    ///
+    /// The positions are extended with `w = 1`, and divided by the
+    /// transformed `w`. The other attributes, normals included, are left
+    /// unchanged.
+    fn transform_vertices(&self, matrix: &[[f32; 4]; 4]) -> Vec<Vertex> {
+        let mut vertices = self.get_vertices();
+        for vertex in &mut vertices {
+            let [x, y, z] = vertex.position;
+            let [tx, ty, tz, tw] = matrix.map(|row| row[0] * x + row[1] * y + row[2] * z + row[3]);
+            vertex.position = [tx / tw, ty / tw, tz / tw];
+        }
+        vertices
+    }
+
+    /// Returns every edge of the triangles of the mesh once, as pairs of
+    /// indices for a `LineList` topology, in the order of the triangles.
+    ///
+    /// An edge shared by two triangles, in either direction, is emitted only
+    /// the first time it is found.
+    fn get_wireframe_indices(&self) -> Vec<u16> {
+        let mut seen = HashSet::new();
+        self.get_indices()
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .filter(|&(a, b)| seen.insert((a.min(b), a.max(b))))
+            .flat_map(|(a, b)| [a, b])
+            .collect()
+    }
+
+    /// Checks that the mesh can be rendered: the number of indices is a
+    /// multiple of 3, every index is in range, and no triangle repeats a
+    /// vertex or has (nearly) no area.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first defect found. See `validate` for a full report of
+    /// the defects of a mesh.
+    fn validate(&self) -> Result<(), MeshError> {
+        repair::check(&self.get_vertices(), &self.get_indices())
+    }
+
+    /// Returns a ribbon of quads of the given thickness along the outer
+    /// boundary of the mesh, to draw a border around a 2D figure.
+    ///
+    /// The ribbon is centered on the boundary, extending `thickness / 2`
+    /// inward and outward, with mitered corners. Inner edges, such as the
+    /// spokes of a circle, are ignored. Returns no vertex if the mesh has no
+    /// boundary.
+    fn get_outline(&self, thickness: f32) -> (Vec<Vertex>, Vec<u16>) {
+        outline::outline(&self.get_vertices(), &self.get_indices(), thickness)
+    }
 }
 
 /// Implementation of the `Mesh` trait for the `Figure` enum.
@@ -34,153 +365,1162 @@ pub trait Mesh {
 /// methods for retrieving the vertices and indices that make up the geometric
 /// figure.
 impl Mesh for Figure {
+    /// The normals are computed from the triangles, so the flat figures face
+    /// the viewer and the 3D figures get smooth normals. The texture
+    /// coordinates are projected from the XY plane.
     fn get_vertices(&self) -> Vec<Vertex> {
-        match self {
-            Figure::Triangle => vec![
-                Vertex {
-                    position: [0.0, 0.5, 0.0],
-                    color: [1.0, 0.0, 0.0],
-                },
-                Vertex {
-                    position: [-0.5, -0.5, 0.0],
-                    color: [0.0, 1.0, 0.0],
-                },
-                Vertex {
-                    position: [0.5, -0.5, 0.0],
-                    color: [0.0, 0.0, 1.0],
-                },
-            ],
-            Figure::Pentagon => vec![
-                Vertex {
-                    position: [-0.0868241, 0.49240386, 0.0],
-                    color: [1.0, 0.0, 0.0],
-                },
-                Vertex {
-                    position: [-0.49513406, 0.06958647, 0.0],
-                    color: [0.5, 0.5, 0.0],
-                },
-                Vertex {
-                    position: [-0.21918549, -0.44939706, 0.0],
-                    color: [0.0, 1.0, 0.0],
-                },
-                Vertex {
-                    position: [0.35966998, -0.3473291, 0.0],
-                    color: [0.0, 0.5, 0.5],
-                },
-                Vertex {
-                    position: [0.44147372, 0.2347359, 0.0],
-                    color: [0.0, 0.0, 1.0],
-                },
-            ],
-            Figure::Rectangle => vec![
-                Vertex {
-                    position: [-0.5, 0.25, 0.0],
-                    color: [1.0, 0.0, 0.0],
-                },
-                Vertex {
-                    position: [-0.5, -0.25, 0.0],
-                    color: [0.5, 0.5, 0.0],
-                },
-                Vertex {
-                    position: [0.5, -0.25, 0.0],
-                    color: [0.0, 0.5, 0.5],
-                },
-                Vertex {
-                    position: [0.5, 0.25, 0.0],
-                    color: [0.0, 0.0, 1.0],
-                },
-            ],
-            Figure::Trapezoid => vec![
-                Vertex {
-                    position: [-0.25, 0.5, 0.0],
-                    color: [1.0, 0.0, 0.0],
-                },
-                Vertex {
-                    position: [-0.5, -0.5, 0.0],
-                    color: [0.5, 0.5, 0.0],
-                },
-                Vertex {
-                    position: [0.5, -0.5, 0.0],
-                    color: [0.0, 0.5, 0.5],
-                },
-                Vertex {
-                    position: [0.25, 0.5, 0.0],
-                    color: [0.0, 0.0, 1.0],
-                },
-            ],
-            Figure::Parallelogram => vec![
-                Vertex {
-                    position: [-0.25, 0.5, 0.0],
-                    color: [1.0, 0.0, 0.0],
-                },
-                Vertex {
-                    position: [-0.5, -0.5, 0.0],
-                    color: [0.5, 0.5, 0.0],
-                },
-                Vertex {
-                    position: [0.25, -0.5, 0.0],
-                    color: [0.0, 0.5, 0.5],
-                },
-                Vertex {
-                    position: [0.5, 0.5, 0.0],
-                    color: [0.0, 0.0, 1.0],
-                },
-            ],
-            Figure::Circle(num_segments) => {
-                const TWO_PI: f32 = 2.0 * std::f32::consts::PI;
+        let mut vertices = {
+            match self {
+                Figure::Triangle => vec![
+                    Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.5, -0.5, 0.0, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.5, -0.5, 0.0, 0.0, 0.0, 1.0),
+                ],
+                Figure::Pentagon => vec![
+                    Vertex::at_colored(-0.0868241, 0.49240386, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.49513406, 0.06958647, 0.0, 0.5, 0.5, 0.0),
+                    Vertex::at_colored(-0.21918549, -0.44939706, 0.0, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.35966998, -0.3473291, 0.0, 0.0, 0.5, 0.5),
+                    Vertex::at_colored(0.44147372, 0.2347359, 0.0, 0.0, 0.0, 1.0),
+                ],
+                Figure::Rectangle => vec![
+                    Vertex::at_colored(-0.5, 0.25, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.5, -0.25, 0.0, 0.5, 0.5, 0.0),
+                    Vertex::at_colored(0.5, -0.25, 0.0, 0.0, 0.5, 0.5),
+                    Vertex::at_colored(0.5, 0.25, 0.0, 0.0, 0.0, 1.0),
+                ],
+                Figure::Trapezoid => vec![
+                    Vertex::at_colored(-0.25, 0.5, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.5, -0.5, 0.0, 0.5, 0.5, 0.0),
+                    Vertex::at_colored(0.5, -0.5, 0.0, 0.0, 0.5, 0.5),
+                    Vertex::at_colored(0.25, 0.5, 0.0, 0.0, 0.0, 1.0),
+                ],
+                Figure::Parallelogram => vec![
+                    Vertex::at_colored(-0.25, 0.5, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.5, -0.5, 0.0, 0.5, 0.5, 0.0),
+                    Vertex::at_colored(0.25, -0.5, 0.0, 0.0, 0.5, 0.5),
+                    Vertex::at_colored(0.5, 0.5, 0.0, 0.0, 0.0, 1.0),
+                ],
+                Figure::Pyramid => vec![
+                    // Base
+                    Vertex::at_colored(-0.5, -0.5, -0.5, 0.5, 0.5, 0.5),
+                    Vertex::at_colored(0.5, -0.5, -0.5, 0.5, 0.5, 0.5),
+                    Vertex::at_colored(0.5, -0.5, 0.5, 0.5, 0.5, 0.5),
+                    Vertex::at_colored(-0.5, -0.5, 0.5, 0.5, 0.5, 0.5),
+                    // Front face
+                    Vertex::at_colored(-0.5, -0.5, 0.5, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(0.5, -0.5, 0.5, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 0.0, 0.0),
+                    // Right face
+                    Vertex::at_colored(0.5, -0.5, 0.5, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.5, -0.5, -0.5, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.0, 0.5, 0.0, 0.0, 1.0, 0.0),
+                    // Back face
+                    Vertex::at_colored(0.5, -0.5, -0.5, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(-0.5, -0.5, -0.5, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(0.0, 0.5, 0.0, 0.0, 0.0, 1.0),
+                    // Left face
+                    Vertex::at_colored(-0.5, -0.5, -0.5, 1.0, 1.0, 0.0),
+                    Vertex::at_colored(-0.5, -0.5, 0.5, 1.0, 1.0, 0.0),
+                    Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 1.0, 0.0),
+                ],
+                Figure::Tetrahedron => vec![
+                    Vertex::at_colored(0.2886751, 0.2886751, 0.2886751, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(0.2886751, -0.2886751, -0.2886751, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.2886751, 0.2886751, -0.2886751, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(0.2886751, 0.2886751, 0.2886751, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(-0.2886751, -0.2886751, 0.2886751, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.2886751, -0.2886751, -0.2886751, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.2886751, 0.2886751, 0.2886751, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(-0.2886751, 0.2886751, -0.2886751, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(-0.2886751, -0.2886751, 0.2886751, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(0.2886751, -0.2886751, -0.2886751, 1.0, 1.0, 0.0),
+                    Vertex::at_colored(-0.2886751, -0.2886751, 0.2886751, 1.0, 1.0, 0.0),
+                    Vertex::at_colored(-0.2886751, 0.2886751, -0.2886751, 1.0, 1.0, 0.0),
+                ],
+                Figure::Hexagon => {
+                    const CORNER_COLORS: [[f32; 4]; 6] = [
+                        [1.0, 0.0, 0.0, 1.0],
+                        [1.0, 1.0, 0.0, 1.0],
+                        [0.0, 1.0, 0.0, 1.0],
+                        [0.0, 1.0, 1.0, 1.0],
+                        [0.0, 0.0, 1.0, 1.0],
+                        [1.0, 0.0, 1.0, 1.0],
+                    ];
 
-                let vertices: Vec<Vertex> = std::iter::once(Vertex {
-                    position: [0.0, 0.0, 0.0],
-                    color: [0.5, 0.5, 0.5],
+                    std::iter::once(Vertex::at_colored(0.0, 0.0, 0.0, 1.0, 1.0, 1.0))
+                        .chain(CORNER_COLORS.iter().enumerate().map(|(i, &color)| {
+                            let angle = i as f32 * std::f32::consts::FRAC_PI_3;
+                            Vertex {
+                                position: [0.5 * angle.cos(), 0.5 * angle.sin(), 0.0],
+                                color,
+                                normal: FLAT_NORMAL,
+                                tex_coords: [0.0, 0.0],
+                            }
+                        }))
+                        .collect()
+                }
+                Figure::Hexagram => {
+                    const OUTER_RADIUS: f32 = 0.5;
+                    // The inner corners are where the edges of the two
+                    // triangles of the star cross.
+                    const INNER_RADIUS: f32 = OUTER_RADIUS / 1.732_050_8;
+
+                    // Counter-clockwise from the top tip, alternating the
+                    // tips and the inner corners, colored around the hue
+                    // circle.
+                    std::iter::once(Vertex::at_colored(0.0, 0.0, 0.0, 1.0, 1.0, 1.0))
+                        .chain((0..12).map(|i| {
+                            let angle = (90.0 + i as f32 * 30.0).to_radians();
+                            let radius = if i % 2 == 0 {
+                                OUTER_RADIUS
+                            } else {
+                                INNER_RADIUS
+                            };
+                            Vertex::from_hsv(
+                                i as f32 * 30.0,
+                                1.0,
+                                1.0,
+                                radius * angle.cos(),
+                                radius * angle.sin(),
+                                0.0,
+                            )
+                        }))
+                        .collect()
+                }
+                // Counter-clockwise from the top, as the rectangle.
+                Figure::Diamond => vec![
+                    Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 0.0, 0.0),
+                    Vertex::at_colored(-0.5, 0.0, 0.0, 0.0, 1.0, 0.0),
+                    Vertex::at_colored(0.0, -0.5, 0.0, 0.0, 0.0, 1.0),
+                    Vertex::at_colored(0.5, 0.0, 0.0, 1.0, 1.0, 0.0),
+                ],
+                Figure::Cross => {
+                    const TIP: f32 = 0.5;
+                    const HALF_WIDTH: f32 = 0.1;
+                    const CENTER_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+                    // Counter-clockwise around the outline, from the top
+                    // arm, each arm going from its tip to its inner corner.
+                    let arms = [
+                        ([0.0, 1.0], [1.0, 0.0, 0.0, 1.0]),
+                        ([-1.0, 0.0], [0.0, 1.0, 0.0, 1.0]),
+                        ([0.0, -1.0], [0.0, 0.0, 1.0, 1.0]),
+                        ([1.0, 0.0], [1.0, 1.0, 0.0, 1.0]),
+                    ];
+                    arms.into_iter()
+                        .flat_map(|([dx, dy], color)| {
+                            // The side to the left of the arm, going outward.
+                            let [sx, sy] = [-dy, dx];
+                            [
+                                (
+                                    [dx * TIP - sx * HALF_WIDTH, dy * TIP - sy * HALF_WIDTH],
+                                    color,
+                                ),
+                                (
+                                    [dx * TIP + sx * HALF_WIDTH, dy * TIP + sy * HALF_WIDTH],
+                                    color,
+                                ),
+                                (
+                                    [(dx + sx) * HALF_WIDTH, (dy + sy) * HALF_WIDTH],
+                                    CENTER_COLOR,
+                                ),
+                            ]
+                        })
+                        .map(|([x, y], color)| Vertex {
+                            position: [x, y, 0.0],
+                            color,
+                            normal: FLAT_NORMAL,
+                            tex_coords: [0.0, 0.0],
+                        })
+                        .collect()
+                }
+                Figure::Arrow {
+                    shaft_length,
+                    shaft_width,
+                    head_width,
+                    head_length,
+                } => arrow(*shaft_length, *shaft_width, *head_width, *head_length),
+                Figure::Overlap => [
+                    ([-0.15, 0.15], [1.0, 0.0, 0.0, 0.5]),
+                    ([0.15, -0.15], [0.0, 0.0, 1.0, 0.5]),
+                ]
+                .into_iter()
+                .flat_map(|([x, y], color)| {
+                    [[-0.3, -0.3], [0.3, -0.3], [0.3, 0.3], [-0.3, 0.3]].map(|[dx, dy]| Vertex {
+                        position: [x + dx, y + dy, 0.0],
+                        color,
+                        normal: FLAT_NORMAL,
+                        tex_coords: [0.0, 0.0],
+                    })
                 })
-                .chain((0..(num_segments + 1)).map(|i| {
-                    let angle = i as f32 * TWO_PI / *num_segments as f32;
-                    Vertex {
-                        position: [0.5 * angle.cos(), 0.5 * angle.sin(), 0.0],
-                        color: [
-                            angle.sin(),
-                            (angle + 2.0 * TWO_PI / 6.0).sin(),
-                            (angle + 4.0 * TWO_PI / 6.0).sin(),
-                        ],
-                    }
-                }))
-                .collect();
-
-                vertices
+                .collect(),
+                Figure::Circle(num_segments) => {
+                    const TWO_PI: f32 = 2.0 * std::f32::consts::PI;
+
+                    let vertices: Vec<Vertex> =
+                        std::iter::once(Vertex::at_colored(0.0, 0.0, 0.0, 0.5, 0.5, 0.5))
+                            .chain((0..(num_segments + 1)).map(|i| {
+                                let angle = i as f32 * TWO_PI / *num_segments as f32;
+                                Vertex::from_hsv(
+                                    angle.to_degrees(),
+                                    1.0,
+                                    1.0,
+                                    0.5 * angle.cos(),
+                                    0.5 * angle.sin(),
+                                    0.0,
+                                )
+                            }))
+                            .collect();
+
+                    vertices
+                }
+                Figure::Icosphere(level) => {
+                    let (positions, _) = icosphere(*level);
+
+                    // Map the position on the sphere to a color.
+                    positions
+                        .into_iter()
+                        .map(|position| Vertex {
+                            position,
+                            color: [position[0] + 0.5, position[1] + 0.5, position[2] + 0.5, 1.0],
+                            normal: FLAT_NORMAL,
+                            tex_coords: [0.0, 0.0],
+                        })
+                        .collect()
+                }
+                Figure::Torus {
+                    major_radius,
+                    minor_radius,
+                    major_segments,
+                    minor_segments,
+                } => {
+                    const TWO_PI: f32 = 2.0 * std::f32::consts::PI;
+
+                    // Clamp the tube radius to avoid self-intersections, and
+                    // the segments so that the indices fit in `u16`.
+                    let minor_radius = minor_radius.min(0.99 * major_radius);
+                    let major_segments = (*major_segments).min(MAX_TORUS_SEGMENTS);
+                    let minor_segments = (*minor_segments).min(MAX_TORUS_SEGMENTS);
+
+                    (0..major_segments)
+                        .flat_map(|i| {
+                            let u = i as f32 * TWO_PI / major_segments as f32;
+                            (0..minor_segments).map(move |j| {
+                                let v = j as f32 * TWO_PI / minor_segments as f32;
+                                let radius = major_radius + minor_radius * v.cos();
+                                Vertex {
+                                    position: [
+                                        radius * u.cos(),
+                                        radius * u.sin(),
+                                        minor_radius * v.sin(),
+                                    ],
+                                    color: [
+                                        0.5 + 0.5 * u.cos(),
+                                        0.5 + 0.5 * u.sin(),
+                                        0.5 + 0.5 * v.cos(),
+                                        1.0,
+                                    ],
+                                    normal: FLAT_NORMAL,
+                                    tex_coords: [0.0, 0.0],
+                                }
+                            })
+                        })
+                        .collect()
+                }
+                Figure::RoundedRectangle {
+                    width,
+                    height,
+                    corner_radius,
+                    segments,
+                } => {
+                    let (vertices, _) =
+                        rounded_rectangle(*width, *height, *corner_radius, *segments);
+
+                    vertices
+                }
+                Figure::Heart { segments } => {
+                    let (vertices, _) = heart(*segments);
+
+                    vertices
+                }
+                Figure::Cylinder { segments } => {
+                    let (vertices, _) = cylinder(*segments);
+
+                    vertices
+                }
+                Figure::Plane { subdivisions } => {
+                    let (vertices, _) = plane(*subdivisions);
+
+                    vertices
+                }
+                Figure::Lathe { profile, segments } => {
+                    let (vertices, _) = lathe(profile, *segments);
+
+                    vertices
+                }
+                Figure::Cone { segments } => {
+                    let (vertices, _) = cone(*segments);
+
+                    vertices
+                }
             }
-        }
+        };
+        compute_normals(&mut vertices, &self.get_indices());
+        generate_planar_uvs(&mut vertices);
+        vertices
     }
 
     fn get_indices(&self) -> Vec<u16> {
         match self {
             Figure::Triangle => vec![0, 1, 2],
             Figure::Pentagon => vec![0, 1, 4, 1, 2, 4, 2, 3, 4],
-            Figure::Rectangle | Figure::Trapezoid | Figure::Parallelogram => vec![0, 1, 3, 1, 2, 3],
-            Figure::Circle(num_segments) => {
-                let indices: Vec<u16> = (1..(num_segments + 1) as u16)
-                    .flat_map(|i| [0, i, i + 1])
+            Figure::Rectangle | Figure::Trapezoid | Figure::Parallelogram | Figure::Diamond => {
+                vec![0, 1, 3, 1, 2, 3]
+            }
+            Figure::Pyramid => vec![
+                0, 1, 2, 0, 2, 3, // Base
+                4, 5, 6, // Front face
+                7, 8, 9, // Right face
+                10, 11, 12, // Back face
+                13, 14, 15, // Left face
+            ],
+            Figure::Tetrahedron => vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            Figure::Overlap => vec![0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7],
+            Figure::Hexagon => (1..=6).flat_map(|i| [0, i, i % 6 + 1]).collect(),
+            Figure::Hexagram => (1..=12).flat_map(|i| [0, i, i % 12 + 1]).collect(),
+            // The shaft, then the head as a fan around the tip, so that the
+            // corners of the shaft are not in the middle of an edge.
+            Figure::Arrow { .. } => vec![0, 1, 5, 0, 5, 6, 3, 4, 5, 3, 5, 1, 3, 1, 2],
+            Figure::Cross => vec![
+                0, 1, 2, 0, 2, 11, // Top arm
+                2, 3, 4, 2, 4, 5, // Left arm
+                5, 6, 7, 5, 7, 8, // Bottom arm
+                8, 9, 10, 8, 10, 11, // Right arm
+                2, 5, 8, 2, 8, 11, // Center
+            ],
+            Figure::Circle(num_segments) => circle_indices(*num_segments)
+                .into_iter()
+                .map(|i| i as u16)
+                .collect(),
+            Figure::Icosphere(level) => {
+                let (_, indices) = icosphere(*level);
+
+                indices
+            }
+            Figure::Torus {
+                major_segments,
+                minor_segments,
+                ..
+            } => {
+                // Stitch the grid, wrapping around in both directions. The
+                // quads are split counter-clockwise seen from the outside.
+                let major_segments = (*major_segments).min(MAX_TORUS_SEGMENTS);
+                let minor_segments = (*minor_segments).min(MAX_TORUS_SEGMENTS);
+                let index = |i: u32, j: u32| {
+                    ((i % major_segments) * minor_segments + (j % minor_segments)) as u16
+                };
+                let indices: Vec<u16> = (0..major_segments)
+                    .flat_map(|i| {
+                        (0..minor_segments).flat_map(move |j| {
+                            let a = index(i, j);
+                            let b = index(i + 1, j);
+                            let c = index(i + 1, j + 1);
+                            let d = index(i, j + 1);
+                            [a, b, c, a, c, d]
+                        })
+                    })
                     .collect();
 
                 indices
             }
+            Figure::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            } => {
+                let (_, indices) = rounded_rectangle(*width, *height, *corner_radius, *segments);
+
+                indices
+            }
+            Figure::Heart { segments } => {
+                let (_, indices) = heart(*segments);
+
+                indices
+            }
+            Figure::Cylinder { segments } => {
+                let (_, indices) = cylinder(*segments);
+
+                indices
+            }
+            Figure::Plane { subdivisions } => {
+                let (_, indices) = plane(*subdivisions);
+
+                indices
+            }
+            Figure::Lathe { profile, segments } => {
+                let (_, indices) = lathe(profile, *segments);
+
+                indices
+            }
+            Figure::Cone { segments } => {
+                let (_, indices) = cone(*segments);
+
+                indices
+            }
+        }
+    }
+
+    /// A circle with more vertices than `u16` can address gets 32-bit
+    /// indices.
+    fn get_index_data(&self) -> Indices {
+        match self {
+            Figure::Circle(num_segments) => {
+                // The center, and the first vertex repeated at the end.
+                let vertex_count = *num_segments as usize + 2;
+                Indices::for_vertex_count(circle_indices(*num_segments), vertex_count)
+            }
+            _ => Indices::U16(self.get_indices()),
         }
     }
 }
 
 impl Figure {
+    /// Imports a mesh from a Wavefront OBJ file, fitted into the
+    /// [-0.5, 0.5] cube. See `parse_obj` for the supported subset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid OBJ
+    /// file.
+    pub fn from_obj(path: &Path) -> Result<MeshData, ObjError> {
+        parse_obj(&std::fs::read_to_string(path)?)
+    }
+
+    /// Returns an arrow with the given proportions, or `None` if they do not
+    /// describe an arrow fitting in the [-0.5, 0.5] square: the sizes must be
+    /// positive, the head wider than the shaft, and the arrow at most 1.0
+    /// long and wide.
+    pub fn arrow(
+        shaft_length: f32,
+        shaft_width: f32,
+        head_width: f32,
+        head_length: f32,
+    ) -> Option<Self> {
+        let positive = [shaft_length, shaft_width, head_width, head_length]
+            .iter()
+            .all(|&size| size > 0.0);
+        (positive
+            && shaft_length + head_length <= 1.0
+            && head_width > shaft_width
+            && head_width <= 1.0)
+            .then_some(Figure::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            })
+    }
+
+    /// The figures cycled through with the space bar, in order. The index
+    /// of a figure in this slice is its index in `try_from_index`.
+    pub const ALL: &[Figure] = &[
+        Figure::Triangle,
+        Figure::Pentagon,
+        Figure::Rectangle,
+        Figure::Trapezoid,
+        Figure::Parallelogram,
+        Figure::Circle(64),
+        Figure::Torus {
+            major_radius: 0.35,
+            minor_radius: 0.15,
+            major_segments: 48,
+            minor_segments: 24,
+        },
+        Figure::Pyramid,
+        Figure::Tetrahedron,
+        Figure::Overlap,
+        Figure::Hexagon,
+        Figure::Diamond,
+        Figure::Cross,
+        Figure::default_arrow(),
+        Figure::Heart { segments: 64 },
+        Figure::RoundedRectangle {
+            width: 0.9,
+            height: 0.6,
+            corner_radius: 0.15,
+            segments: 8,
+        },
+        Figure::Hexagram,
+    ];
+
+    /// The number of figures cycled through with the space bar.
+    pub const COUNT: u8 = Figure::ALL.len() as u8;
+
+    /// The fewest segments `circle_for_resolution` tessellates a circle
+    /// with.
+    pub const MIN_CIRCLE_SEGMENTS: u32 = 12;
+    /// The most segments `circle_for_resolution` tessellates a circle with,
+    /// the center, the segments and the seam making a vertex per 16-bit
+    /// index.
+    pub const MAX_CIRCLE_SEGMENTS: u32 = u16::MAX as u32 - 1;
+
+    /// Returns a circle with enough segments to look round when it is
+    /// `pixel_diameter` pixels wide, and no more.
+    ///
+    /// The segments are chosen so that the chord error, the largest
+    /// distance between a segment and the arc it replaces, stays under half
+    /// a pixel. The count is clamped between `MIN_CIRCLE_SEGMENTS` and
+    /// `MAX_CIRCLE_SEGMENTS`.
+    pub fn circle_for_resolution(pixel_diameter: u32) -> Self {
+        let radius = pixel_diameter as f64 / 2.0;
+        // A chord spanning the angle 2θ is r(1 - cos θ) from its arc.
+        let segments = if radius > 0.5 {
+            let half_angle = (1.0 - 0.5 / radius).acos();
+            (std::f64::consts::PI / half_angle).ceil()
+        } else {
+            0.0
+        };
+        Figure::Circle(
+            (segments.min(Self::MAX_CIRCLE_SEGMENTS as f64) as u32).max(Self::MIN_CIRCLE_SEGMENTS),
+        )
+    }
+
+    /// Returns an arrow 1.0 long, its head taking 40% of its length.
+    pub const fn default_arrow() -> Self {
+        Figure::Arrow {
+            shaft_length: 0.6,
+            shaft_width: 0.2,
+            head_width: 0.5,
+            head_length: 0.4,
+        }
+    }
+
+    /// Returns the figure at the given index in `Figure::ALL`, or `None` if
+    /// the index is not in the range `0..Figure::COUNT`.
+    pub fn try_from_index(i: u8) -> Option<Self> {
+        Figure::ALL.get(i as usize).cloned()
+    }
+
+    /// Returns the index of the figure in `Figure::ALL`, or `None` if the
+    /// figure is not cycled through, like a circle with other segments.
+    pub fn index(&self) -> Option<u8> {
+        Figure::ALL
+            .iter()
+            .position(|figure| figure == self)
+            .map(|i| i as u8)
+    }
+
+    /// Returns the figure after this one in `Figure::ALL`, wrapping around
+    /// after the last one. A figure which is not cycled through is followed
+    /// by the first one.
+    pub fn next(&self) -> Self {
+        let i = self.index().map_or(0, |i| (i + 1) % Figure::COUNT);
+        Figure::ALL[i as usize].clone()
+    }
+
+    /// Returns the figure before this one in `Figure::ALL`, wrapping around
+    /// before the first one. A figure which is not cycled through is
+    /// preceded by the last one.
+    pub fn prev(&self) -> Self {
+        let i = self.index().map_or(Figure::COUNT - 1, |i| {
+            (i + Figure::COUNT - 1) % Figure::COUNT
+        });
+        Figure::ALL[i as usize].clone()
+    }
+
     /// Returns the figure at the given index.
     ///
-    /// If the index is not in the range 0..4, the default figure (Triangle) is
-    /// returned.
+    /// If the index is not in the range `0..Figure::COUNT`, the default figure
+    /// (Triangle) is returned.
+    /// A warning is logged when the index falls back to the default figure.
+    #[deprecated(note = "use `Figure::try_from` to detect unknown indices")]
     pub fn get_figure(i: u8) -> Self {
-        match i {
-            0 => Figure::Triangle,
-            1 => Figure::Pentagon,
-            2 => Figure::Rectangle,
-            3 => Figure::Trapezoid,
-            4 => Figure::Parallelogram,
-            5 => Figure::Circle(64),
-            _ => Figure::Triangle,
+        Self::try_from(i).unwrap_or_else(|e| {
+            log::warn!("{}, falling back to the triangle", e);
+            Figure::default()
+        })
+    }
+
+    /// Returns the edges of the outline of the figure, as pairs of indices
+    /// for a `LineList` topology.
+    ///
+    /// See `boundary_edges`.
+    pub fn get_edge_indices(&self) -> Vec<u16> {
+        boundary_edges(&self.get_vertices(), &self.get_indices())
+    }
+
+    /// Returns the mesh morphed from `from` to `to` at `t`, from 0 to 1.
+    ///
+    /// If both meshes have the same number of vertices, the vertices are
+    /// interpolated one by one. Otherwise the mesh switches from `from` to
+    /// `to` halfway. In both cases, the indices are those of `from` for
+    /// `t < 0.5`, and those of `to` after.
+    pub fn morph(from: &dyn Mesh, to: &dyn Mesh, t: f32) -> (Vec<Vertex>, Vec<u16>) {
+        let (from_vertices, to_vertices) = (from.get_vertices(), to.get_vertices());
+        let indices = if t < 0.5 {
+            from.get_indices()
+        } else {
+            to.get_indices()
+        };
+
+        let vertices = if from_vertices.len() == to_vertices.len() {
+            from_vertices
+                .iter()
+                .zip(&to_vertices)
+                .map(|(a, b)| Vertex::lerp(a, b, t))
+                .collect()
+        } else if t < 0.5 {
+            from_vertices
+        } else {
+            to_vertices
+        };
+        (vertices, indices)
+    }
+}
+
+/// An index with no figure in `Figure::ALL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidFigureIndex(pub u8);
+
+impl fmt::Display for InvalidFigureIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No figure at index {}, the figures range from 0 to {}",
+            self.0,
+            Figure::COUNT - 1
+        )
+    }
+}
+
+impl std::error::Error for InvalidFigureIndex {}
+
+/// Converts an index to the figure at that index in `Figure::ALL`.
+///
+/// Fails with the index if it is not in the range `0..Figure::COUNT`.
+impl TryFrom<u8> for Figure {
+    type Error = InvalidFigureIndex;
+
+    fn try_from(i: u8) -> Result<Self, Self::Error> {
+        Self::try_from_index(i).ok_or(InvalidFigureIndex(i))
+    }
+}
+
+/// Builds an icosphere of radius 0.5 by subdividing an icosahedron `level`
+/// times.
+///
+/// Each subdivision splits every triangle into four triangles and projects
+/// the new vertices back on the sphere. The midpoints are cached per edge so
+/// that the vertices shared by two triangles are not duplicated. The level is
+/// clamped to `MAX_ICOSPHERE_LEVEL` so that the indices fit in `u16`.
+///
+/// Returns the positions of the vertices and the indices of the triangles,
+/// wound counter-clockwise when seen from the outside.
+fn icosphere(level: u8) -> (Vec<[f32; 3]>, Vec<u16>) {
+    const RADIUS: f32 = 0.5;
+
+    // Project a point on the sphere.
+    let project = |[x, y, z]: [f32; 3]| {
+        let length = (x * x + y * y + z * z).sqrt();
+        [
+            x * RADIUS / length,
+            y * RADIUS / length,
+            z * RADIUS / length,
+        ]
+    };
+
+    // Vertices of the icosahedron.
+    let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let mut positions: Vec<[f32; 3]> = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .into_iter()
+    .map(project)
+    .collect();
+
+    // Triangles of the icosahedron.
+    let mut triangles: Vec<[u16; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..level.min(MAX_ICOSPHERE_LEVEL) {
+        let mut midpoints: HashMap<(u16, u16), u16> = HashMap::new();
+        let mut midpoint = |a: u16, b: u16, positions: &mut Vec<[f32; 3]>| {
+            let key = (a.min(b), a.max(b));
+            *midpoints.entry(key).or_insert_with(|| {
+                let [ax, ay, az] = positions[a as usize];
+                let [bx, by, bz] = positions[b as usize];
+                positions.push(project([(ax + bx) / 2.0, (ay + by) / 2.0, (az + bz) / 2.0]));
+                (positions.len() - 1) as u16
+            })
+        };
+
+        triangles = triangles
+            .into_iter()
+            .flat_map(|[a, b, c]| {
+                let ab = midpoint(a, b, &mut positions);
+                let bc = midpoint(b, c, &mut positions);
+                let ca = midpoint(c, a, &mut positions);
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    (positions, triangles.into_iter().flatten().collect())
+}
+
+/// Returns the indices of a circle, as a fan of triangles around the center.
+fn circle_indices(num_segments: u32) -> Vec<u32> {
+    (1..num_segments + 1).flat_map(|i| [0, i, i + 1]).collect()
+}
+
+/// Returns the vertices of an arrow pointing right, centered on the
+/// origin, counter-clockwise from the bottom left corner of the shaft.
+fn arrow(shaft_length: f32, shaft_width: f32, head_width: f32, head_length: f32) -> Vec<Vertex> {
+    const SHAFT_COLOR: [f32; 4] = [0.2, 0.4, 0.9, 1.0];
+    const HEAD_COLOR: [f32; 4] = [1.0, 0.5, 0.0, 1.0];
+    const TIP_COLOR: [f32; 4] = [1.0, 0.9, 0.0, 1.0];
+
+    let tail = -(shaft_length + head_length) / 2.0;
+    let base = tail + shaft_length;
+    let tip = base + head_length;
+    let (shaft, head) = (shaft_width / 2.0, head_width / 2.0);
+
+    [
+        ([tail, -shaft], SHAFT_COLOR),
+        ([base, -shaft], SHAFT_COLOR),
+        ([base, -head], HEAD_COLOR),
+        ([tip, 0.0], TIP_COLOR),
+        ([base, head], HEAD_COLOR),
+        ([base, shaft], SHAFT_COLOR),
+        ([tail, shaft], SHAFT_COLOR),
+    ]
+    .map(|([x, y], color)| Vertex {
+        position: [x, y, 0.0],
+        color,
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    })
+    .to_vec()
+}
+
+/// Builds a rectangle of the given size centered on the origin, with its
+/// corners rounded by quarter circles.
+///
+/// Each corner has `segments` vertices, from one straight edge to the next,
+/// counter-clockwise from the bottom right corner, and the vertices are
+/// fanned around a center vertex. The colors are warm in the middle of the
+/// corners and cool along the straight edges. The number of segments is
+/// clamped between 2 and `MAX_ROUNDED_RECTANGLE_SEGMENTS`, so that the
+/// indices fit in `u16`, and the radius to half the smaller side; the
+/// triangles collapsed by a zero radius or a zero straight edge are skipped.
+///
+/// Returns the vertices and the indices of the triangles.
+fn rounded_rectangle(
+    width: f32,
+    height: f32,
+    corner_radius: f32,
+    segments: u32,
+) -> (Vec<Vertex>, Vec<u16>) {
+    const WARM: [f32; 4] = [1.0, 0.4, 0.2, 1.0];
+    const COOL: [f32; 4] = [0.2, 0.6, 1.0, 1.0];
+
+    let segments = segments.clamp(2, MAX_ROUNDED_RECTANGLE_SEGMENTS);
+    let radius = corner_radius.clamp(0.0, width.min(height) / 2.0);
+    let [dx, dy] = [width / 2.0 - radius, height / 2.0 - radius];
+
+    let center = Vertex {
+        position: [0.0, 0.0, 0.0],
+        color: COOL,
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    };
+    let corners = [[dx, -dy], [dx, dy], [-dx, dy], [-dx, -dy]];
+    let perimeter = corners.iter().enumerate().flat_map(|(k, &[cx, cy])| {
+        (0..segments).map(move |i| {
+            // The arc of the corner k starts at the angle (k - 1) * pi / 2.
+            let t = i as f32 / (segments - 1) as f32;
+            let angle = (k as f32 - 1.0 + t) * std::f32::consts::FRAC_PI_2;
+            let warmth = (t * std::f32::consts::PI).sin();
+            Vertex {
+                position: [cx + radius * angle.cos(), cy + radius * angle.sin(), 0.0],
+                color: std::array::from_fn(|c| COOL[c] + (WARM[c] - COOL[c]) * warmth),
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            }
+        })
+    });
+    let vertices: Vec<Vertex> = std::iter::once(center).chain(perimeter).collect();
+
+    let count = vertices.len() as u16 - 1;
+    let indices = (1..=count)
+        .map(|i| [0, i, i % count + 1])
+        .filter(|&[_, a, b]| vertices[a as usize].position != vertices[b as usize].position)
+        .flatten()
+        .collect();
+
+    (vertices, indices)
+}
+
+/// Builds a heart from the parametric curve `x = 16 sin³(t)`,
+/// `y = 13 cos(t) - 5 cos(2t) - 2 cos(3t) - cos(4t)`.
+///
+/// The curve is sampled at `segments` points, clamped between 3 and
+/// `MAX_HEART_SEGMENTS` so that the indices fit in `u16`,
+/// counter-clockwise from the dip between the lobes, then centered and
+/// scaled uniformly into the [-0.5, 0.5] square. The points are fanned
+/// around a center vertex, and colored by their angle around it.
+///
+/// Returns the vertices and the indices of the triangles.
+fn heart(segments: u32) -> (Vec<Vertex>, Vec<u16>) {
+    use std::f32::consts::TAU;
+
+    let segments = segments.clamp(3, MAX_HEART_SEGMENTS);
+    let points: Vec<[f32; 2]> = (0..segments)
+        .map(|i| {
+            // The curve is clockwise for increasing t.
+            let t = -(i as f32) * TAU / segments as f32;
+            [
+                16.0 * t.sin().powi(3),
+                13.0 * t.cos() - 5.0 * (2.0 * t).cos() - 2.0 * (3.0 * t).cos() - (4.0 * t).cos(),
+            ]
+        })
+        .collect();
+
+    let (min, max) = points.iter().fold(
+        ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+        |(min, max), p| {
+            (
+                [min[0].min(p[0]), min[1].min(p[1])],
+                [max[0].max(p[0]), max[1].max(p[1])],
+            )
+        },
+    );
+    let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    let scale = 1.0 / (max[0] - min[0]).max(max[1] - min[1]);
+
+    let perimeter = points.iter().map(|&[x, y]| {
+        let [x, y] = [(x - center[0]) * scale, (y - center[1]) * scale];
+        let angle = y.atan2(x);
+        Vertex::at(x, y, 0.0).with_color(
+            0.6 + 0.4 * angle.cos(),
+            0.2 + 0.2 * angle.sin(),
+            0.4 - 0.2 * angle.cos(),
+        )
+    });
+    let vertices: Vec<Vertex> = std::iter::once(Vertex::at_colored(0.0, 0.0, 0.0, 1.0, 0.2, 0.4))
+        .chain(perimeter)
+        .collect();
+
+    let count = segments as u16;
+    let indices = (1..=count).flat_map(|i| [0, i, i % count + 1]).collect();
+
+    (vertices, indices)
+}
+
+/// Returns the positions of a circular cross-section of the given radius,
+/// parallel to the XZ plane at the given height.
+///
+/// The positions go counter-clockwise when seen from below.
+fn circle_section(segments: u32, radius: f32, y: f32) -> Vec<[f32; 3]> {
+    const TWO_PI: f32 = 2.0 * std::f32::consts::PI;
+
+    (0..segments)
+        .map(|i| {
+            let angle = i as f32 * TWO_PI / segments as f32;
+            [radius * angle.cos(), y, radius * angle.sin()]
+        })
+        .collect()
+}
+
+/// Returns the color of a side vertex of a cylinder or a cone.
+fn side_color([x, _, z]: [f32; 3]) -> [f32; 4] {
+    [0.5 + x, 0.5, 0.5 + z, 1.0]
+}
+
+/// Builds a capped cylinder of radius 0.5 and height 1.0 along the Y axis.
+///
+/// The rim vertices are duplicated between the side and the caps so that
+/// the caps have their own color. The number of segments is clamped between
+/// 3 and `MAX_CYLINDER_SEGMENTS` so that the indices fit in `u16`.
+///
+/// Returns the vertices and the indices of the triangles, wound
+/// counter-clockwise when seen from the outside.
+fn cylinder(segments: u32) -> (Vec<Vertex>, Vec<u16>) {
+    let segments = segments.clamp(3, MAX_CYLINDER_SEGMENTS);
+    let n = segments as u16;
+
+    let bottom = circle_section(segments, 0.5, -0.5);
+    let top = circle_section(segments, 0.5, 0.5);
+
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(4 * segments as usize + 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(12 * segments as usize);
+
+    // Side: bottom ring followed by the top ring.
+    vertices.extend(bottom.iter().chain(top.iter()).map(|&position| Vertex {
+        position,
+        color: side_color(position),
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    }));
+    for i in 0..n {
+        let j = (i + 1) % n;
+        indices.extend([i, n + i, j, j, n + i, n + j]);
+    }
+
+    // Bottom cap: center followed by the rim.
+    let center = vertices.len() as u16;
+    vertices.push(Vertex::at_colored(0.0, -0.5, 0.0, 0.2, 0.2, 0.2));
+    vertices.extend(bottom.iter().map(|&position| Vertex {
+        position,
+        color: [0.4, 0.4, 0.4, 1.0],
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    }));
+    for i in 0..n {
+        indices.extend([center, center + 1 + i, center + 1 + (i + 1) % n]);
+    }
+
+    // Top cap: center followed by the rim.
+    let center = vertices.len() as u16;
+    vertices.push(Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 1.0, 1.0));
+    vertices.extend(top.iter().map(|&position| Vertex {
+        position,
+        color: [0.8, 0.8, 0.8, 1.0],
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    }));
+    for i in 0..n {
+        indices.extend([center, center + 1 + (i + 1) % n, center + 1 + i]);
+    }
+
+    (vertices, indices)
+}
+
+/// Builds a capped cone of radius 0.5 and height 1.0 along the Y axis, with
+/// the apex at the top.
+///
+/// The rim vertices are duplicated between the side and the base cap so that
+/// the cap has its own color. The number of segments is clamped between 3
+/// and `MAX_CONE_SEGMENTS` so that the indices fit in `u16`.
+///
+/// Returns the vertices and the indices of the triangles, wound
+/// counter-clockwise when seen from the outside.
+fn cone(segments: u32) -> (Vec<Vertex>, Vec<u16>) {
+    let segments = segments.clamp(3, MAX_CONE_SEGMENTS);
+    let n = segments as u16;
+
+    let base = circle_section(segments, 0.5, -0.5);
+
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(2 * segments as usize + 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(6 * segments as usize);
+
+    // Side: base ring followed by the apex.
+    vertices.extend(base.iter().map(|&position| Vertex {
+        position,
+        color: side_color(position),
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    }));
+    vertices.push(Vertex::at_colored(0.0, 0.5, 0.0, 1.0, 1.0, 1.0));
+    for i in 0..n {
+        indices.extend([i, n, (i + 1) % n]);
+    }
+
+    // Base cap: center followed by the rim.
+    let center = vertices.len() as u16;
+    vertices.push(Vertex::at_colored(0.0, -0.5, 0.0, 0.2, 0.2, 0.2));
+    vertices.extend(base.iter().map(|&position| Vertex {
+        position,
+        color: [0.4, 0.4, 0.4, 1.0],
+        normal: FLAT_NORMAL,
+        tex_coords: [0.0, 0.0],
+    }));
+    for i in 0..n {
+        indices.extend([center, center + 1 + i, center + 1 + (i + 1) % n]);
+    }
+
+    (vertices, indices)
+}
+
+/// Builds a surface of revolution by revolving a profile polyline around
+/// the Y axis.
+///
+/// Each profile point `[x, y]` becomes a ring of `segments` vertices of
+/// radius `x` at height `y`, and consecutive rings are stitched with quads.
+/// A point on the axis (`x = 0`) becomes a single pole vertex stitched with
+/// a triangle fan, so that no degenerate quad is emitted. The surface faces
+/// the right of the profile in the XY plane, outward for a profile going
+/// from bottom to top.
+///
+/// The number of segments is clamped to at least 3, and to at most as many
+/// as keep every vertex addressable with `u16`. If the profile has too many
+/// points to fit even with 3 segments, the points past the last ring that
+/// fits are dropped.
+///
+/// Returns the vertices and the indices of the triangles.
+pub fn lathe(profile: &[[f32; 2]], segments: u32) -> (Vec<Vertex>, Vec<u16>) {
+    const MAX_VERTICES: usize = u16::MAX as usize + 1;
+    let is_pole = |radius: f32| radius.abs() < 1e-6;
+
+    let poles = profile
+        .iter()
+        .filter(|&&[radius, _]| is_pole(radius))
+        .count();
+    let rings = profile.len() - poles;
+    let max_segments = MAX_VERTICES.saturating_sub(poles) / rings.max(1);
+    let segments = segments.min(max_segments as u32).max(3);
+    let n = segments as u16;
+
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut indices: Vec<u16> = Vec::new();
+
+    // The index of the first vertex of each ring, and whether it is a pole.
+    let mut previous: Option<(u16, bool)> = None;
+    for &[radius, y] in profile {
+        let pole = is_pole(radius);
+        let positions = if pole {
+            vec![[0.0, y, 0.0]]
+        } else {
+            circle_section(segments, radius, y)
+        };
+        if vertices.len() + positions.len() > MAX_VERTICES {
+            break;
+        }
+        let start = vertices.len() as u16;
+        vertices.extend(positions.into_iter().map(|position| Vertex {
+            position,
+            color: side_color(position),
+            normal: FLAT_NORMAL,
+            tex_coords: [0.0, 0.0],
+        }));
+
+        match previous {
+            Some((below, false)) if !pole => {
+                for i in 0..n {
+                    let j = (i + 1) % n;
+                    indices.extend([below + i, start + i, below + j]);
+                    indices.extend([below + j, start + i, start + j]);
+                }
+            }
+            Some((below, false)) => {
+                for i in 0..n {
+                    indices.extend([below + i, start, below + (i + 1) % n]);
+                }
+            }
+            Some((below, true)) if !pole => {
+                for i in 0..n {
+                    indices.extend([below, start + i, start + (i + 1) % n]);
+                }
+            }
+            _ => {}
         }
+        previous = Some((start, pole));
     }
+
+    (vertices, indices)
+}
+
+/// Builds a unit square in the XY plane, facing the positive Z axis, split
+/// into a regular grid.
+///
+/// Each side is split `subdivisions` times, giving `n = subdivisions + 1`
+/// quads per side and a `(n + 1) x (n + 1)` grid of shared vertices, stored
+/// row by row from the top-left corner. The positions lie exactly on the
+/// lattice and the colors encode the `(u, v)` grid coordinates in the red
+/// and green channels. The subdivisions are clamped to
+/// `MAX_PLANE_SUBDIVISIONS` so that the indices fit in `u16`.
+///
+/// Returns the vertices and the indices of the quads, row by row, split
+/// into two triangles wound counter-clockwise.
+fn plane(subdivisions: u32) -> (Vec<Vertex>, Vec<u16>) {
+    let n = subdivisions.min(MAX_PLANE_SUBDIVISIONS) + 1;
+
+    let vertices: Vec<Vertex> = (0..=n)
+        .flat_map(|i| {
+            (0..=n).map(move |j| {
+                let u = j as f32 / n as f32;
+                let v = i as f32 / n as f32;
+                Vertex {
+                    position: [u - 0.5, 0.5 - v, 0.0],
+                    color: [u, v, 0.0, 1.0],
+                    normal: FLAT_NORMAL,
+                    tex_coords: [0.0, 0.0],
+                }
+            })
+        })
+        .collect();
+
+    // Same split as the rectangle: top-left, bottom-left and top-right, then
+    // bottom-left, bottom-right and top-right.
+    let index = |i: u32, j: u32| (i * (n + 1) + j) as u16;
+    let indices: Vec<u16> = (0..n)
+        .flat_map(|i| {
+            (0..n).flat_map(move |j| {
+                let a = index(i, j);
+                let b = index(i + 1, j);
+                let c = index(i + 1, j + 1);
+                let d = index(i, j + 1);
+                [a, b, d, b, c, d]
+            })
+        })
+        .collect();
+
+    (vertices, indices)
+}
+
+/// Merges several meshes into a single mesh, to render them in one draw
+/// call.
+///
+/// The vertices are concatenated in order, and the indices of each mesh are
+/// offset by the number of vertices of the meshes before it.
+///
+/// Returns `None` if the merged mesh has too many vertices to be indexed
+/// with `u16` indices.
+pub fn merge(meshes: &[&dyn Mesh]) -> Option<(Vec<Vertex>, Vec<u16>)> {
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let mut indices: Vec<u16> = Vec::new();
+
+    for mesh in meshes {
+        let offset = vertices.len();
+        vertices.extend(mesh.get_vertices());
+        if vertices.len() > u16::MAX as usize + 1 {
+            return None;
+        }
+        indices.extend(mesh.get_indices().into_iter().map(|i| i + offset as u16));
+    }
+
+    Some((vertices, indices))
 }
@@ -0,0 +1,120 @@
+use std::f32::consts::{PI, TAU};
+
+use super::{boundary_loop, Figure, MeshData, Vertex};
+
+/// Returns the mesh morphed from `from` to `to` at `t`, clamped between 0
+/// and 1.
+///
+/// The outer boundary loops of both meshes are resampled to the same number
+/// of vertices, keeping their corners, and interpolated vertex by vertex,
+/// positions and colors. The interior is fanned from the interpolated
+/// centers of the loops, so the morphed mesh keeps the same vertices and
+/// indices for every `t` strictly between 0 and 1. The endpoints are
+/// returned unchanged at 0 and 1.
+///
+/// A mesh without boundary, such as a closed 3D figure, cannot be
+/// resampled: the meshes are then morphed as with `Figure::morph`.
+pub fn morph(from: &MeshData, to: &MeshData, t: f32) -> MeshData {
+    // A NaN is clamped to 0.
+    if t <= 0.0 || t.is_nan() {
+        return from.clone();
+    }
+    if t >= 1.0 {
+        return to.clone();
+    }
+
+    let rings = ring(from).zip(ring(to));
+    let Some((from_ring, to_ring)) = rings else {
+        return MeshData::from(Figure::morph(from, to, t));
+    };
+    let count = from_ring.len().max(to_ring.len());
+    let from_ring = resample(&from_ring, count);
+    let to_ring = align(&from_ring, resample(&to_ring, count));
+
+    let center = Vertex::lerp(&average(&from_ring), &average(&to_ring), t);
+    let vertices = std::iter::once(center)
+        .chain(
+            from_ring
+                .iter()
+                .zip(&to_ring)
+                .map(|(a, b)| Vertex::lerp(a, b, t)),
+        )
+        .collect();
+    let indices = (0..count as u16)
+        .flat_map(|i| [0, i + 1, (i + 1) % count as u16 + 1])
+        .collect();
+    MeshData { vertices, indices }
+}
+
+/// Returns the vertices of the outer boundary loop of a mesh, in order, or
+/// `None` if the mesh has no boundary or a loop of less than 3 vertices.
+fn ring(mesh: &MeshData) -> Option<Vec<Vertex>> {
+    let boundary = boundary_loop(&mesh.vertices, &mesh.indices)?;
+    (boundary.len() >= 3).then(|| {
+        boundary
+            .iter()
+            .map(|&i| mesh.vertices[i as usize])
+            .collect()
+    })
+}
+
+/// Resamples a closed loop to `count` vertices, at least as many as the
+/// loop has.
+///
+/// The vertices of the loop are kept, and the new ones are inserted evenly
+/// along its edges, each one on the edge with the longest segments so far.
+fn resample(ring: &[Vertex], count: usize) -> Vec<Vertex> {
+    let n = ring.len();
+    let lengths: Vec<f32> = (0..n)
+        .map(|i| ring[i].distance_to(&ring[(i + 1) % n]))
+        .collect();
+    let mut segments = vec![1usize; n];
+    for _ in n..count {
+        let segment_length = |i: usize| lengths[i] / segments[i] as f32;
+        let longest = (0..n)
+            .max_by(|&a, &b| segment_length(a).total_cmp(&segment_length(b)))
+            .unwrap_or(0);
+        segments[longest] += 1;
+    }
+
+    (0..n)
+        .flat_map(|i| {
+            let (a, b) = (&ring[i], &ring[(i + 1) % n]);
+            let s = segments[i];
+            (0..s).map(move |k| Vertex::lerp(a, b, k as f32 / s as f32))
+        })
+        .collect()
+}
+
+/// Rotates the loop `ring` so that it starts at the vertex whose direction
+/// from its center is the closest to the direction of the first vertex of
+/// `reference` from its own center.
+///
+/// The morph then turns as little as possible.
+fn align(reference: &[Vertex], mut ring: Vec<Vertex>) -> Vec<Vertex> {
+    let angle = |vertex: &Vertex, center: &Vertex| {
+        let [x, y, _] = vertex.position;
+        let [cx, cy, _] = center.position;
+        (y - cy).atan2(x - cx)
+    };
+    let start = angle(&reference[0], &average(reference));
+    let center = average(&ring);
+    let gap = |vertex: &Vertex| ((angle(vertex, &center) - start + PI).rem_euclid(TAU) - PI).abs();
+    let first = (0..ring.len())
+        .min_by(|&a, &b| gap(&ring[a]).total_cmp(&gap(&ring[b])))
+        .unwrap_or(0);
+    ring.rotate_left(first);
+    ring
+}
+
+/// Returns the average of the vertices, positions, colors, normals and
+/// texture coordinates. There must be at least one vertex.
+fn average(vertices: &[Vertex]) -> Vertex {
+    vertices
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(vertices[0], |mean, (i, vertex)| {
+            Vertex::lerp(&mean, vertex, 1.0 / (i + 1) as f32)
+        })
+}
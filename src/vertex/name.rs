@@ -0,0 +1,260 @@
+use std::{fmt, str::FromStr};
+
+use super::Figure;
+
+/// An error while parsing a figure from its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFigureError {
+    /// The name is not the name of a figure.
+    UnknownFigure(String),
+    /// A parameter of the figure is not a valid number.
+    InvalidParameter { figure: &'static str, value: String },
+    /// More parameters are given than the figure has.
+    TooManyParameters { figure: &'static str, count: usize },
+}
+
+impl fmt::Display for ParseFigureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFigureError::UnknownFigure(name) => write!(f, "Unknown figure '{}'", name),
+            ParseFigureError::InvalidParameter { figure, value } => {
+                write!(f, "Invalid parameter '{}' for the {}", value, figure)
+            }
+            ParseFigureError::TooManyParameters { figure, count } => {
+                write!(
+                    f,
+                    "Too many parameters for the {}, which takes at most {}",
+                    figure, count
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseFigureError {}
+
+impl Figure {
+    /// Returns the name of the kind of figure, in snake case, as in the
+    /// `type` of its `FigureSpec`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Figure::Triangle => "triangle",
+            Figure::Pentagon => "pentagon",
+            Figure::Hexagon => "hexagon",
+            Figure::Hexagram => "hexagram",
+            Figure::Diamond => "diamond",
+            Figure::Cross => "cross",
+            Figure::Arrow { .. } => "arrow",
+            Figure::RoundedRectangle { .. } => "rounded_rectangle",
+            Figure::Heart { .. } => "heart",
+            Figure::Rectangle => "rectangle",
+            Figure::Trapezoid => "trapezoid",
+            Figure::Parallelogram => "parallelogram",
+            Figure::Circle(_) => "circle",
+            Figure::Icosphere(_) => "icosphere",
+            Figure::Torus { .. } => "torus",
+            Figure::Cylinder { .. } => "cylinder",
+            Figure::Cone { .. } => "cone",
+            Figure::Pyramid => "pyramid",
+            Figure::Tetrahedron => "tetrahedron",
+            Figure::Overlap => "overlap",
+            Figure::Plane { .. } => "plane",
+            Figure::Lathe { .. } => "lathe",
+        }
+    }
+}
+
+/// Displays the name of the figure followed by its parameters, separated by
+/// colons, for example `circle:64`, in the order of the fields of the
+/// variant. The lathe gives its segments before its profile, as `x,y` points
+/// separated by semicolons. The output parses back to the same figure.
+impl fmt::Display for Figure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())?;
+        match self {
+            Figure::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            } => write!(
+                f,
+                ":{}:{}:{}:{}",
+                shaft_length, shaft_width, head_width, head_length
+            ),
+            Figure::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            } => write!(f, ":{}:{}:{}:{}", width, height, corner_radius, segments),
+            Figure::Heart { segments }
+            | Figure::Circle(segments)
+            | Figure::Cylinder { segments }
+            | Figure::Cone { segments } => write!(f, ":{}", segments),
+            Figure::Icosphere(level) => write!(f, ":{}", level),
+            Figure::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            } => write!(
+                f,
+                ":{}:{}:{}:{}",
+                major_radius, minor_radius, major_segments, minor_segments
+            ),
+            Figure::Plane { subdivisions } => write!(f, ":{}", subdivisions),
+            Figure::Lathe { profile, segments } => {
+                write!(f, ":{}:", segments)?;
+                for (i, [x, y]) in profile.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { ";" };
+                    write!(f, "{}{},{}", separator, x, y)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses a figure from its name and parameters, as displayed, ignoring the
+/// case.
+///
+/// The missing parameters take their default values: those of the figure
+/// at the same position on the space bar, or else small default sizes.
+impl FromStr for Figure {
+    type Err = ParseFigureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.trim().to_lowercase();
+        let mut parts = lowercase.split(':');
+        let name = parts.next().unwrap_or_default();
+        let mut figure =
+            default_of(name).ok_or_else(|| ParseFigureError::UnknownFigure(s.to_string()))?;
+        let figure_name = figure.name();
+        let parameters: Vec<&str> = parts.collect();
+        let invalid = |value: &str| ParseFigureError::InvalidParameter {
+            figure: figure_name,
+            value: value.to_string(),
+        };
+
+        let mut slots: Vec<Slot> = match &mut figure {
+            Figure::Arrow {
+                shaft_length,
+                shaft_width,
+                head_width,
+                head_length,
+            } => vec![
+                Slot::F32(shaft_length),
+                Slot::F32(shaft_width),
+                Slot::F32(head_width),
+                Slot::F32(head_length),
+            ],
+            Figure::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+                segments,
+            } => vec![
+                Slot::F32(width),
+                Slot::F32(height),
+                Slot::F32(corner_radius),
+                Slot::U32(segments),
+            ],
+            Figure::Heart { segments }
+            | Figure::Circle(segments)
+            | Figure::Cylinder { segments }
+            | Figure::Cone { segments } => vec![Slot::U32(segments)],
+            Figure::Icosphere(level) => vec![Slot::U8(level)],
+            Figure::Torus {
+                major_radius,
+                minor_radius,
+                major_segments,
+                minor_segments,
+            } => vec![
+                Slot::F32(major_radius),
+                Slot::F32(minor_radius),
+                Slot::U32(major_segments),
+                Slot::U32(minor_segments),
+            ],
+            Figure::Plane { subdivisions } => vec![Slot::U32(subdivisions)],
+            Figure::Lathe { profile, segments } => {
+                vec![Slot::U32(segments), Slot::Profile(profile)]
+            }
+            _ => Vec::new(),
+        };
+
+        if parameters.len() > slots.len() {
+            return Err(ParseFigureError::TooManyParameters {
+                figure: figure_name,
+                count: slots.len(),
+            });
+        }
+        for (slot, value) in slots.iter_mut().zip(&parameters) {
+            let value = value.trim();
+            match slot {
+                Slot::F32(field) => **field = value.parse().map_err(|_| invalid(value))?,
+                Slot::U32(field) => **field = value.parse().map_err(|_| invalid(value))?,
+                Slot::U8(field) => **field = value.parse().map_err(|_| invalid(value))?,
+                Slot::Profile(field) => {
+                    **field = value
+                        .split(';')
+                        .map(|point| {
+                            let (x, y) = point.split_once(',')?;
+                            Some([x.trim().parse().ok()?, y.trim().parse().ok()?])
+                        })
+                        .collect::<Option<Vec<[f32; 2]>>>()
+                        .ok_or_else(|| invalid(value))?;
+                }
+            }
+        }
+        drop(slots);
+
+        Ok(figure)
+    }
+}
+
+/// A parameter of a figure, to be overwritten by the parsed value.
+enum Slot<'a> {
+    F32(&'a mut f32),
+    U32(&'a mut u32),
+    U8(&'a mut u8),
+    Profile(&'a mut Vec<[f32; 2]>),
+}
+
+/// Returns the figure of the given lowercase name with its default
+/// parameters, or `None` if there is no such figure.
+fn default_of(name: &str) -> Option<Figure> {
+    let index = match name {
+        "triangle" => 0,
+        "pentagon" => 1,
+        "rectangle" => 2,
+        "trapezoid" => 3,
+        "parallelogram" => 4,
+        "circle" => 5,
+        "torus" => 6,
+        "pyramid" => 7,
+        "tetrahedron" => 8,
+        "overlap" => 9,
+        "hexagon" => 10,
+        "diamond" => 11,
+        "cross" => 12,
+        "arrow" => 13,
+        "heart" => 14,
+        "rounded_rectangle" => 15,
+        "hexagram" => 16,
+        // The figures which are not on the space bar.
+        "icosphere" => return Some(Figure::Icosphere(2)),
+        "cylinder" => return Some(Figure::Cylinder { segments: 32 }),
+        "cone" => return Some(Figure::Cone { segments: 32 }),
+        "plane" => return Some(Figure::Plane { subdivisions: 8 }),
+        "lathe" => {
+            return Some(Figure::Lathe {
+                profile: vec![[0.0, -0.5], [0.4, -0.25], [0.25, 0.25], [0.0, 0.5]],
+                segments: 32,
+            })
+        }
+        _ => return None,
+    };
+    Figure::try_from_index(index)
+}
@@ -0,0 +1,50 @@
+use super::Vertex;
+
+/// The normal of the flat figures, facing the viewer.
+pub const FLAT_NORMAL: [f32; 3] = [0.0, 0.0, 1.0];
+
+/// Computes the normal of every vertex from the triangles using it.
+///
+/// The normals of the faces are weighted by their area and accumulated on
+/// their vertices, then normalized, so that a vertex shared by several faces
+/// gets a smooth normal and a vertex of a single face gets the normal of the
+/// face. A counter-clockwise flat figure gets `FLAT_NORMAL`, as does a vertex
+/// used by no triangle. The triangles with indices out of range are ignored.
+pub fn compute_normals(vertices: &mut [Vertex], indices: &[u16]) {
+    let mut normals = vec![[0.0; 3]; vertices.len()];
+    for triangle in indices.chunks_exact(3) {
+        if triangle.iter().any(|&i| i as usize >= vertices.len()) {
+            continue;
+        }
+
+        let [a, b, c] = [0, 1, 2].map(|k| vertices[triangle[k] as usize].position);
+        let face = cross(sub(b, a), sub(c, a));
+        for &i in triangle {
+            let normal: &mut [f32; 3] = &mut normals[i as usize];
+            *normal = std::array::from_fn(|k| normal[k] + face[k]);
+        }
+    }
+
+    for (vertex, normal) in vertices.iter_mut().zip(normals) {
+        vertex.normal = normalize(normal).unwrap_or(FLAT_NORMAL);
+    }
+}
+
+/// Returns the vector scaled to a unit length, or `None` if it has no
+/// length.
+pub(super) fn normalize(v: [f32; 3]) -> Option<[f32; 3]> {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    (length > f32::EPSILON && length.is_finite()).then(|| v.map(|c| c / length))
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
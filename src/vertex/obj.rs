@@ -0,0 +1,176 @@
+use std::{fmt, io};
+
+use super::{
+    apply_gradient, compute_normals, generate_planar_uvs, GradientDirection, Mesh, MeshData,
+    Vertex, FLAT_NORMAL,
+};
+
+/// The color of the lowest vertices of an imported mesh.
+const BOTTOM_COLOR: [f32; 3] = [0.1, 0.2, 0.9];
+/// The color of the highest vertices of an imported mesh.
+const TOP_COLOR: [f32; 3] = [0.9, 0.6, 0.1];
+
+/// An error while importing a Wavefront OBJ file.
+#[derive(Debug)]
+pub enum ObjError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// A vertex or face line could not be parsed.
+    InvalidLine { line: usize },
+    /// A face refers to a vertex which is not defined, the index being
+    /// 1-based, or relative to the last vertex when negative.
+    IndexOutOfRange { line: usize, index: i64 },
+    /// The file defines more vertices than `u16` indices can address.
+    TooManyVertices,
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "Failed to read the OBJ file: {}", e),
+            ObjError::InvalidLine { line } => write!(f, "Line {}: invalid vertex or face", line),
+            ObjError::IndexOutOfRange { line, index } => {
+                write!(f, "Line {}: the face refers to no vertex {}", line, index)
+            }
+            ObjError::TooManyVertices => write!(
+                f,
+                "The mesh has more than {} vertices",
+                u16::MAX as usize + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ObjError {
+    fn from(e: io::Error) -> Self {
+        ObjError::Io(e)
+    }
+}
+
+/// Parses a mesh from the source of a Wavefront OBJ file.
+///
+/// Only the vertex positions (`v`) and the faces (`f`) are read; the other
+/// lines, normals and texture coordinates included, are ignored. The faces
+/// are triangulated as fans around their first vertex, so they are expected
+/// to be convex.
+///
+/// The mesh is centered and scaled to fit in the [-0.5, 0.5] cube, and
+/// colored with a gradient along the Y axis. The normals are computed from
+/// the triangles.
+///
+/// # Errors
+///
+/// Returns an error if a line is malformed, if a face refers to an undefined
+/// vertex, or if there are too many vertices.
+pub fn parse_obj(source: &str) -> Result<MeshData, ObjError> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut indices: Vec<u16> = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let invalid = || ObjError::InvalidLine { line: line_number };
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let mut coordinates = tokens.map(|token| token.parse::<f32>());
+                let mut next = || coordinates.next().and_then(Result::ok).ok_or_else(invalid);
+                positions.push([next()?, next()?, next()?]);
+                if positions.len() > u16::MAX as usize + 1 {
+                    return Err(ObjError::TooManyVertices);
+                }
+            }
+            Some("f") => {
+                let face = tokens
+                    .map(|token| {
+                        // Only the position of `v/vt/vn` is used.
+                        let index: i64 = token
+                            .split('/')
+                            .next()
+                            .and_then(|index| index.parse().ok())
+                            .ok_or_else(invalid)?;
+                        resolve_index(index, positions.len()).ok_or(ObjError::IndexOutOfRange {
+                            line: line_number,
+                            index,
+                        })
+                    })
+                    .collect::<Result<Vec<u16>, ObjError>>()?;
+                if face.len() < 3 {
+                    return Err(invalid());
+                }
+
+                for k in 1..face.len() - 1 {
+                    indices.extend([face[0], face[k], face[k + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let vertices = positions
+        .into_iter()
+        .map(|position| Vertex {
+            position,
+            color: [0.0, 0.0, 0.0, 1.0],
+            normal: FLAT_NORMAL,
+            tex_coords: [0.0, 0.0],
+        })
+        .collect();
+    Ok(normalized(MeshData { vertices, indices }))
+}
+
+/// Returns the 0-based index of the vertex referred to by a face, or `None`
+/// if there is no such vertex among the `count` vertices defined so far.
+fn resolve_index(index: i64, count: usize) -> Option<u16> {
+    let resolved = match index {
+        1.. => index - 1,
+        ..=-1 => count as i64 + index,
+        0 => return None,
+    };
+    (0..count as i64)
+        .contains(&resolved)
+        .then_some(resolved as u16)
+}
+
+/// Returns the mesh centered and scaled into the [-0.5, 0.5] cube, colored
+/// by height, with its normals and texture coordinates.
+pub(super) fn normalized(mesh: MeshData) -> MeshData {
+    let mut mesh = fitted(mesh);
+    color_by_height(&mut mesh.vertices);
+    mesh
+}
+
+/// Returns the mesh centered and scaled uniformly into the [-0.5, 0.5]
+/// cube, with its normals computed from the triangles and its texture
+/// coordinates projected from the XY plane.
+pub(super) fn fitted(mesh: MeshData) -> MeshData {
+    let (min, max) = mesh.bounds();
+    let center: [f32; 3] = std::array::from_fn(|i| (min[i] + max[i]) / 2.0);
+    let extent = (0..3).map(|i| max[i] - min[i]).fold(0.0, f32::max);
+    let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+
+    let mut mesh = mesh.translated(center.map(|c| -c)).scaled(scale);
+    compute_normals(&mut mesh.vertices, &mesh.indices);
+    generate_planar_uvs(&mut mesh.vertices);
+    mesh
+}
+
+/// Colors the vertices with a gradient from `BOTTOM_COLOR` at the lowest
+/// vertex to `TOP_COLOR` at the highest one.
+pub(super) fn color_by_height(vertices: &mut [Vertex]) {
+    apply_gradient(
+        vertices,
+        BOTTOM_COLOR,
+        TOP_COLOR,
+        GradientDirection::Vertical,
+    );
+}
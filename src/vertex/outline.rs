@@ -0,0 +1,76 @@
+use super::{boundary::boundary_loop, Vertex};
+
+/// The maximum length of a miter join, relative to half the thickness.
+///
+/// Sharper corners have their miter clipped to this length instead of
+/// producing long spikes.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Builds a ribbon of quads of the given thickness along the outer boundary
+/// of a 2D mesh, centered on the boundary.
+///
+/// The corners are joined with miters, clipped to `MITER_LIMIT`. The ribbon
+/// vertices keep the color of their boundary vertex. Returns no vertex if
+/// the mesh has no boundary.
+pub(super) fn outline(
+    vertices: &[Vertex],
+    indices: &[u16],
+    thickness: f32,
+) -> (Vec<Vertex>, Vec<u16>) {
+    let Some(boundary) = boundary_loop(vertices, indices) else {
+        return (Vec::new(), Vec::new());
+    };
+    let n = boundary.len();
+    let half = thickness / 2.0;
+
+    // The outward normal of the edge starting at each boundary point, the
+    // loop of a counter-clockwise mesh being counter-clockwise.
+    let position = |i: usize| vertices[boundary[i % n] as usize].position;
+    let normals: Vec<[f32; 2]> = (0..n)
+        .map(|i| {
+            let ([x0, y0, _], [x1, y1, _]) = (position(i), position(i + 1));
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+            [dy / length, -dx / length]
+        })
+        .collect();
+
+    let mut ribbon: Vec<Vertex> = Vec::with_capacity(2 * n);
+    for i in 0..n {
+        // Miter along the bisector of the normals of the two edges.
+        let [ax, ay] = normals[(i + n - 1) % n];
+        let [bx, by] = normals[i];
+        let (mx, my) = (ax + bx, ay + by);
+        let length = (mx * mx + my * my).sqrt();
+        let (mx, my) = if length > f32::EPSILON {
+            (mx / length, my / length)
+        } else {
+            (bx, by)
+        };
+        let cos = (mx * bx + my * by).max(1.0 / MITER_LIMIT);
+        let offset = half / cos;
+
+        let vertex = vertices[boundary[i] as usize];
+        let [x, y, z] = vertex.position;
+        for sign in [-1.0, 1.0] {
+            ribbon.push(Vertex {
+                position: [x + sign * offset * mx, y + sign * offset * my, z],
+                color: vertex.color,
+                normal: vertex.normal,
+                tex_coords: vertex.tex_coords,
+            });
+        }
+    }
+
+    // One quad per boundary edge, from the inner to the outer side.
+    let ribbon_indices = (0..n as u16)
+        .flat_map(|i| {
+            let j = (i + 1) % n as u16;
+            let (inner_i, outer_i) = (2 * i, 2 * i + 1);
+            let (inner_j, outer_j) = (2 * j, 2 * j + 1);
+            [inner_i, outer_i, outer_j, inner_i, outer_j, inner_j]
+        })
+        .collect();
+
+    (ribbon, ribbon_indices)
+}
@@ -0,0 +1,311 @@
+use std::{fmt, io::Read};
+
+use super::{
+    obj::{color_by_height, fitted},
+    MeshData, Vertex, FLAT_NORMAL,
+};
+
+/// An error while importing a PLY file.
+#[derive(Debug)]
+pub enum PlyError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// A line of the header could not be parsed.
+    InvalidHeader { line: usize },
+    /// The data is not stored as ASCII.
+    UnsupportedFormat(String),
+    /// The vertices have no `x`, `y` or `z` property, or the faces no list of
+    /// vertex indices.
+    MissingProperty(&'static str),
+    /// A line of the data does not match the properties of its element.
+    InvalidLine { line: usize },
+    /// The file ends before all the declared elements.
+    Truncated,
+    /// A face refers to a vertex which is not declared.
+    IndexOutOfRange { line: usize, index: i64 },
+    /// The file declares more vertices than `u16` indices can address.
+    TooManyVertices(usize),
+}
+
+impl fmt::Display for PlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlyError::Io(e) => write!(f, "Failed to read the PLY file: {}", e),
+            PlyError::InvalidHeader { line } => write!(f, "Line {}: invalid PLY header", line),
+            PlyError::UnsupportedFormat(format) => {
+                write!(f, "The PLY format {} is not supported, only ascii", format)
+            }
+            PlyError::MissingProperty(name) => {
+                write!(f, "The PLY file has no {} property", name)
+            }
+            PlyError::InvalidLine { line } => write!(f, "Line {}: invalid PLY element", line),
+            PlyError::Truncated => write!(f, "The PLY file ends before its last element"),
+            PlyError::IndexOutOfRange { line, index } => {
+                write!(f, "Line {}: the face refers to no vertex {}", line, index)
+            }
+            PlyError::TooManyVertices(count) => write!(
+                f,
+                "The PLY file has {} vertices, more than the {} supported",
+                count,
+                u16::MAX as usize + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PlyError {
+    fn from(e: std::io::Error) -> Self {
+        PlyError::Io(e)
+    }
+}
+
+/// A property of an element, as declared by the header.
+struct Property {
+    name: String,
+    /// Whether the property is a list, prefixed by its length.
+    list: bool,
+    /// Whether the values are floating-point numbers rather than integers.
+    float: bool,
+}
+
+/// An element of the file, as declared by the header.
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    /// Returns the position of the scalar property with the given name.
+    fn scalar(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|p| p.name == name && !p.list)
+    }
+
+    /// Returns the position of the list property with the given name.
+    fn list(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|p| p.name == name && p.list)
+    }
+}
+
+impl MeshData {
+    /// Imports a mesh from an ASCII PLY file.
+    ///
+    /// The properties are read in the order declared by the header. The
+    /// vertices need `x`, `y` and `z`, and may have `red`, `green` and
+    /// `blue`, from 0 to 255 for integers and from 0.0 to 1.0 for floats.
+    /// The faces need a `vertex_indices` (or `vertex_index`) list, and are
+    /// triangulated as fans. The other elements and properties are ignored.
+    ///
+    /// The mesh is fitted into the [-0.5, 0.5] cube. Without colors, it is
+    /// colored by height, as with `parse_obj`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, if the header is
+    /// malformed or lacks a required property, if an element does not match
+    /// its properties, or if a face refers to an undeclared vertex.
+    pub fn from_ply(mut reader: impl Read) -> Result<MeshData, PlyError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+        let mut lines = source.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let elements = parse_header(&mut lines)?;
+        let vertex_count = elements
+            .iter()
+            .find(|element| element.name == "vertex")
+            .map_or(0, |element| element.count);
+        if vertex_count > u16::MAX as usize + 1 {
+            return Err(PlyError::TooManyVertices(vertex_count));
+        }
+
+        let mut vertices = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::new();
+        let mut colored = false;
+        for element in &elements {
+            match element.name.as_str() {
+                "vertex" => {
+                    let [x, y, z] = ["x", "y", "z"]
+                        .map(|name| element.scalar(name).ok_or(PlyError::MissingProperty(name)));
+                    let (x, y, z) = (x?, y?, z?);
+                    let rgb = ["red", "green", "blue"].map(|name| element.scalar(name));
+                    let rgb = match rgb {
+                        [Some(r), Some(g), Some(b)] => Some([r, g, b]),
+                        _ => None,
+                    };
+                    colored = rgb.is_some();
+
+                    for _ in 0..element.count {
+                        let (_, values) = read_element(&mut lines, element)?;
+                        let scalar = |k: usize| values[k][0];
+                        let color = rgb.map_or([1.0; 3], |rgb| {
+                            rgb.map(|k| {
+                                let scale = if element.properties[k].float {
+                                    1.0
+                                } else {
+                                    255.0
+                                };
+                                (scalar(k) / scale) as f32
+                            })
+                        });
+                        vertices.push(Vertex {
+                            position: [x, y, z].map(|k| scalar(k) as f32),
+                            color: [color[0], color[1], color[2], 1.0],
+                            normal: FLAT_NORMAL,
+                            tex_coords: [0.0, 0.0],
+                        });
+                    }
+                }
+                "face" => {
+                    let list = element
+                        .list("vertex_indices")
+                        .or_else(|| element.list("vertex_index"))
+                        .ok_or(PlyError::MissingProperty("vertex_indices"))?;
+
+                    for _ in 0..element.count {
+                        let (line, values) = read_element(&mut lines, element)?;
+                        let face = values[list]
+                            .iter()
+                            .map(|&index| {
+                                if index.fract() != 0.0 {
+                                    return Err(PlyError::InvalidLine { line });
+                                }
+                                let index = index as i64;
+                                (0..vertex_count as i64)
+                                    .contains(&index)
+                                    .then_some(index as u16)
+                                    .ok_or(PlyError::IndexOutOfRange { line, index })
+                            })
+                            .collect::<Result<Vec<u16>, PlyError>>()?;
+                        if face.len() < 3 {
+                            return Err(PlyError::InvalidLine { line });
+                        }
+                        for k in 1..face.len() - 1 {
+                            indices.extend([face[0], face[k], face[k + 1]]);
+                        }
+                    }
+                }
+                _ => {
+                    for _ in 0..element.count {
+                        read_element(&mut lines, element)?;
+                    }
+                }
+            }
+        }
+
+        let mut mesh = fitted(MeshData { vertices, indices });
+        if !colored {
+            color_by_height(&mut mesh.vertices);
+        }
+        Ok(mesh)
+    }
+}
+
+/// Parses the header, up to `end_header`, and returns the declared
+/// elements in order.
+fn parse_header<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Element>, PlyError> {
+    let mut elements: Vec<Element> = Vec::new();
+
+    let (line, magic) = lines.next().ok_or(PlyError::InvalidHeader { line: 1 })?;
+    if magic.trim() != "ply" {
+        return Err(PlyError::InvalidHeader { line });
+    }
+
+    for (line, content) in lines.by_ref() {
+        let invalid = || PlyError::InvalidHeader { line };
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["format", "ascii", _] => {}
+            ["format", format, _] => return Err(PlyError::UnsupportedFormat(format.to_string())),
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| invalid())?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, value_type, name] => {
+                let element = elements.last_mut().ok_or_else(invalid)?;
+                if is_float(count_type).ok_or_else(invalid)? {
+                    return Err(invalid());
+                }
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    list: true,
+                    float: is_float(value_type).ok_or_else(invalid)?,
+                });
+            }
+            ["property", value_type, name] => {
+                let element = elements.last_mut().ok_or_else(invalid)?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    list: false,
+                    float: is_float(value_type).ok_or_else(invalid)?,
+                });
+            }
+            ["end_header"] => return Ok(elements),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Err(PlyError::Truncated)
+}
+
+/// Returns whether a PLY type is a floating-point type, or `None` if it is
+/// not a PLY type.
+fn is_float(value_type: &str) -> Option<bool> {
+    match value_type {
+        "char" | "uchar" | "short" | "ushort" | "int" | "uint" | "int8" | "uint8" | "int16"
+        | "uint16" | "int32" | "uint32" => Some(false),
+        "float" | "double" | "float32" | "float64" => Some(true),
+        _ => None,
+    }
+}
+
+/// Reads the next line of data, and returns its line number and the values
+/// of each property of the element, a single value for a scalar property.
+fn read_element<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    element: &Element,
+) -> Result<(usize, Vec<Vec<f64>>), PlyError> {
+    let (line, content) = lines
+        .find(|(_, content)| !content.trim().is_empty())
+        .ok_or(PlyError::Truncated)?;
+    let invalid = || PlyError::InvalidLine { line };
+
+    let mut tokens = content.split_whitespace().map(|token| token.parse::<f64>());
+    let mut next = || tokens.next().and_then(Result::ok).ok_or_else(invalid);
+    let values = element
+        .properties
+        .iter()
+        .map(|property| {
+            if property.list {
+                let length = next()?;
+                if length < 0.0 || length.fract() != 0.0 {
+                    return Err(invalid());
+                }
+                (0..length as usize).map(|_| next()).collect()
+            } else {
+                Ok(vec![next()?])
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if tokens.next().is_some() {
+        return Err(invalid());
+    }
+    Ok((line, values))
+}
@@ -0,0 +1,440 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use super::{MeshData, Vertex};
+
+/// The distance under which two vertices of the same color are considered
+/// to be duplicates.
+const WELD_EPSILON: f32 = 1e-5;
+
+/// The area under which a triangle is considered to be degenerate.
+const DEGENERATE_AREA: f32 = 1e-12;
+
+/// A defect of an imported mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MeshIssue {
+    /// Positions with a NaN or infinite coordinate.
+    NonFinitePositions,
+    /// Indices pointing past the last vertex.
+    IndicesOutOfRange,
+    /// Vertices with the same position and color as a previous vertex.
+    DuplicateVertices,
+    /// Triangles with a repeated vertex or no area.
+    DegenerateTriangles,
+    /// Triangles wound in the opposite direction to their neighbours.
+    InconsistentWinding,
+}
+
+impl MeshIssue {
+    /// Returns the description of the issue, as written in the report.
+    pub fn description(&self) -> &'static str {
+        match self {
+            MeshIssue::NonFinitePositions => "non-finite positions",
+            MeshIssue::IndicesOutOfRange => "indices out of range",
+            MeshIssue::DuplicateVertices => "duplicate vertices",
+            MeshIssue::DegenerateTriangles => "degenerate triangles",
+            MeshIssue::InconsistentWinding => "inconsistently wound triangles",
+        }
+    }
+}
+
+/// The result of the validation of an imported mesh.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportReport {
+    /// The issues found in the mesh with the number of occurrences, in the
+    /// order of `MeshIssue`.
+    pub issues: Vec<(MeshIssue, usize)>,
+}
+
+impl ImportReport {
+    /// Returns whether no issue was found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the number of occurrences of the given issue.
+    pub fn count(&self, issue: MeshIssue) -> usize {
+        self.issues
+            .iter()
+            .find(|(found, _)| *found == issue)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Writes the report next to the given mesh file, as `<file>.report.txt`.
+    ///
+    /// Returns the path of the report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be written.
+    pub fn write_next_to(&self, mesh_path: &Path) -> io::Result<PathBuf> {
+        let mut path = mesh_path.as_os_str().to_owned();
+        path.push(".report.txt");
+        let path = PathBuf::from(path);
+        std::fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+/// Displays the report, one issue per line.
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return writeln!(f, "Mesh report: no issues");
+        }
+
+        writeln!(f, "Mesh report: {} issue(s)", self.issues.len())?;
+        for (issue, count) in &self.issues {
+            writeln!(f, "  {}: {}", issue.description(), count)?;
+        }
+        Ok(())
+    }
+}
+
+/// The first defect that makes a mesh unfit for rendering, as returned by
+/// `Mesh::validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshError {
+    /// The number of indices is not a multiple of 3.
+    IncompleteTriangle { index_count: usize },
+    /// An index points past the last vertex.
+    IndexOutOfRange {
+        position: usize,
+        index: u16,
+        vertex_count: usize,
+    },
+    /// A triangle uses the same vertex more than once.
+    RepeatedVertex { triangle: usize },
+    /// A triangle has (nearly) no area.
+    DegenerateTriangle { triangle: usize },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::IncompleteTriangle { index_count } => write!(
+                f,
+                "The mesh has {} indices, which is not a multiple of 3",
+                index_count
+            ),
+            MeshError::IndexOutOfRange {
+                position,
+                index,
+                vertex_count,
+            } => write!(
+                f,
+                "Index {} at position {} is out of range for {} vertices",
+                index, position, vertex_count
+            ),
+            MeshError::RepeatedVertex { triangle } => {
+                write!(f, "Triangle {} uses the same vertex twice", triangle)
+            }
+            MeshError::DegenerateTriangle { triangle } => {
+                write!(f, "Triangle {} has no area", triangle)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+/// Checks that a mesh can be rendered, stopping at the first defect.
+///
+/// Unlike `validate`, the duplicate vertices and the winding are not checked,
+/// as they do not prevent the mesh from being rendered.
+pub(super) fn check(vertices: &[Vertex], indices: &[u16]) -> Result<(), MeshError> {
+    if !indices.len().is_multiple_of(3) {
+        return Err(MeshError::IncompleteTriangle {
+            index_count: indices.len(),
+        });
+    }
+    if let Some((position, &index)) = indices
+        .iter()
+        .enumerate()
+        .find(|(_, &i)| i as usize >= vertices.len())
+    {
+        return Err(MeshError::IndexOutOfRange {
+            position,
+            index,
+            vertex_count: vertices.len(),
+        });
+    }
+
+    for (t, triangle) in indices.chunks_exact(3).enumerate() {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+        if a == b || b == c || c == a {
+            return Err(MeshError::RepeatedVertex { triangle: t });
+        }
+        if is_degenerate(vertices, &[a, b, c]) {
+            return Err(MeshError::DegenerateTriangle { triangle: t });
+        }
+    }
+    Ok(())
+}
+
+/// Runs the validation suite on a mesh and reports every defect found.
+///
+/// The winding is only checked on the triangles with valid indices that are
+/// not degenerate.
+pub fn validate(vertices: &[Vertex], indices: &[u16]) -> ImportReport {
+    let non_finite = vertices
+        .iter()
+        .filter(|vertex| !vertex.position.iter().all(|c| c.is_finite()))
+        .count();
+    let out_of_range = indices
+        .iter()
+        .filter(|&&i| i as usize >= vertices.len())
+        .count();
+    let canonical = canonical_indices(vertices);
+    let duplicates = canonical
+        .iter()
+        .enumerate()
+        .filter(|&(i, &first)| first as usize != i)
+        .count();
+
+    let (degenerate, triangles): (Vec<[u16; 3]>, Vec<[u16; 3]>) =
+        valid_triangles(vertices, indices)
+            .into_iter()
+            .partition(|triangle| is_degenerate(vertices, triangle));
+    let degenerates = degenerate.len();
+    let inconsistent = winding_components(&triangles)
+        .into_iter()
+        .map(|flips| {
+            let flipped = flips.iter().filter(|(_, flip)| *flip).count();
+            flipped.min(flips.len() - flipped)
+        })
+        .sum();
+
+    let issues = [
+        (MeshIssue::NonFinitePositions, non_finite),
+        (MeshIssue::IndicesOutOfRange, out_of_range),
+        (MeshIssue::DuplicateVertices, duplicates),
+        (MeshIssue::DegenerateTriangles, degenerates),
+        (MeshIssue::InconsistentWinding, inconsistent),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .collect();
+
+    ImportReport { issues }
+}
+
+/// Repairs the defects reported by `validate`.
+///
+/// In order, the non-finite coordinates are clamped to 0, the triangles
+/// with indices out of range are dropped, the duplicate vertices are welded,
+/// the degenerate triangles are dropped and the winding is unified. Each
+/// connected part of the mesh is then oriented like its boundary, so that a
+/// flat part faces the positive Z axis, or outward if it is closed.
+///
+/// The repair is deterministic and validating the repaired mesh reports no
+/// issue (as long as no edge is shared by more than two triangles).
+pub fn repair(vertices: &[Vertex], indices: &[u16]) -> (Vec<Vertex>, Vec<u16>) {
+    // Clamp the non-finite coordinates.
+    let vertices: Vec<Vertex> = vertices
+        .iter()
+        .map(|vertex| Vertex {
+            position: vertex.position.map(|c| if c.is_finite() { c } else { 0.0 }),
+            color: vertex.color,
+            normal: vertex.normal,
+            tex_coords: vertex.tex_coords,
+        })
+        .collect();
+
+    // Weld the duplicates, keeping the first vertex of each position.
+    let canonical = canonical_indices(&vertices);
+    let mut remap: Vec<u16> = vec![0; vertices.len()];
+    let mut welded: Vec<Vertex> = Vec::with_capacity(vertices.len());
+    for (i, &first) in canonical.iter().enumerate() {
+        if first as usize == i {
+            remap[i] = welded.len() as u16;
+            welded.push(vertices[i]);
+        } else {
+            remap[i] = remap[first as usize];
+        }
+    }
+
+    // Drop the triangles out of range, then the degenerate ones.
+    let triangles: Vec<[u16; 3]> = valid_triangles(&vertices, indices)
+        .into_iter()
+        .map(|triangle| triangle.map(|i| remap[i as usize]))
+        .filter(|triangle| !is_degenerate(&welded, triangle))
+        .collect();
+
+    // Unify the winding of each part, then orient it like its boundary.
+    let mut repaired = triangles.clone();
+    for flips in winding_components(&triangles) {
+        let mut part: Vec<[u16; 3]> = flips
+            .iter()
+            .map(|&(t, flip)| {
+                if flip {
+                    flipped(triangles[t])
+                } else {
+                    triangles[t]
+                }
+            })
+            .collect();
+
+        // A closed part is oriented by its signed volume, an open part by
+        // the signed area of its boundary, which is the sum of the signed
+        // areas of its triangles.
+        let mut edge_counts: HashMap<(u16, u16), u32> = HashMap::new();
+        for &[a, b, c] in &part {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                *edge_counts.entry((from.min(to), from.max(to))).or_insert(0) += 1;
+            }
+        }
+        let closed = edge_counts.values().all(|&count| count > 1);
+        let orientation: f32 = part
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|i| welded[i as usize].position);
+                if closed {
+                    dot(a, cross(b, c))
+                } else {
+                    (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])
+                }
+            })
+            .sum();
+        if orientation < 0.0 {
+            part = part.into_iter().map(flipped).collect();
+        }
+
+        for (&(t, _), triangle) in flips.iter().zip(part) {
+            repaired[t] = triangle;
+        }
+    }
+
+    (welded, repaired.into_iter().flatten().collect())
+}
+
+/// Validates an imported mesh, and repairs it if `repair` is set.
+///
+/// Returns the mesh, repaired or not, with the report of the imported mesh.
+pub fn check_import(mesh: MeshData, repair: bool) -> (MeshData, ImportReport) {
+    let report = validate(&mesh.vertices, &mesh.indices);
+    if !repair || report.is_clean() {
+        return (mesh, report);
+    }
+    (self::repair(&mesh.vertices, &mesh.indices).into(), report)
+}
+
+/// Returns the canonical index of every vertex, the index of the first
+/// vertex with the same color and the same position (within
+/// `WELD_EPSILON`).
+fn canonical_indices(vertices: &[Vertex]) -> Vec<u16> {
+    let mut first: HashMap<([i64; 3], [u32; 4]), u16> = HashMap::new();
+    vertices
+        .iter()
+        .enumerate()
+        .map(|(i, vertex)| {
+            let position = vertex.position.map(|c| (c / WELD_EPSILON).round() as i64);
+            let color = vertex.color.map(f32::to_bits);
+            *first.entry((position, color)).or_insert(i as u16)
+        })
+        .collect()
+}
+
+/// Returns the complete triangles whose indices are all in range.
+fn valid_triangles(vertices: &[Vertex], indices: &[u16]) -> Vec<[u16; 3]> {
+    indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .filter(|triangle| triangle.iter().all(|&i| (i as usize) < vertices.len()))
+        .collect()
+}
+
+/// Returns whether a triangle has a repeated vertex or no area.
+fn is_degenerate(vertices: &[Vertex], triangle: &[u16; 3]) -> bool {
+    let [a, b, c] = *triangle;
+    if a == b || b == c || c == a {
+        return true;
+    }
+
+    let [a, b, c] = triangle.map(|i| vertices[i as usize].position);
+    let normal = cross(sub(b, a), sub(c, a));
+    dot(normal, normal) <= DEGENERATE_AREA * DEGENERATE_AREA
+}
+
+/// Groups the triangles into edge-connected parts, and returns for each
+/// triangle of each part whether it must be flipped to be wound like the
+/// first triangle of its part.
+///
+/// Two neighbouring triangles are wound consistently when they go through
+/// their shared edge in opposite directions.
+fn winding_components(triangles: &[[u16; 3]]) -> Vec<Vec<(usize, bool)>> {
+    let edges = |triangle: [u16; 3]| {
+        let [a, b, c] = triangle;
+        [(a, b), (b, c), (c, a)]
+    };
+
+    let mut neighbours: HashMap<(u16, u16), Vec<usize>> = HashMap::new();
+    for (t, &triangle) in triangles.iter().enumerate() {
+        for (from, to) in edges(triangle) {
+            neighbours
+                .entry((from.min(to), from.max(to)))
+                .or_default()
+                .push(t);
+        }
+    }
+
+    let mut flips: Vec<Option<bool>> = vec![None; triangles.len()];
+    let mut components = Vec::new();
+    for seed in 0..triangles.len() {
+        if flips[seed].is_some() {
+            continue;
+        }
+
+        flips[seed] = Some(false);
+        let mut component = vec![(seed, false)];
+        let mut queue = VecDeque::from([seed]);
+        while let Some(t) = queue.pop_front() {
+            let flip = flips[t].unwrap();
+            let triangle = if flip {
+                flipped(triangles[t])
+            } else {
+                triangles[t]
+            };
+            for (from, to) in edges(triangle) {
+                for &other in &neighbours[&(from.min(to), from.max(to))] {
+                    if flips[other].is_some() {
+                        continue;
+                    }
+                    // The neighbour must go through the edge from `to` to
+                    // `from`, otherwise it is flipped.
+                    let other_flip = edges(triangles[other]).contains(&(from, to));
+                    flips[other] = Some(other_flip);
+                    component.push((other, other_flip));
+                    queue.push_back(other);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// Returns the triangle wound in the opposite direction.
+fn flipped([a, b, c]: [u16; 3]) -> [u16; 3] {
+    [a, c, b]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
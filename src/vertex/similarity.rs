@@ -0,0 +1,146 @@
+use super::{boundary::boundary_loop, Figure, Mesh};
+
+/// The distance returned when a mesh has no usable boundary.
+pub const MAX_SHAPE_DISTANCE: f32 = f32::MAX;
+
+/// The number of points sampled along each boundary.
+const SAMPLES: usize = 64;
+
+/// Samples `SAMPLES` points evenly spaced by arc length along the boundary
+/// loop of a mesh, centered on their centroid.
+///
+/// The loop is oriented counter-clockwise. Returns `None` if the mesh has no
+/// boundary or if its boundary has no length.
+fn sample_boundary(mesh: &impl Mesh) -> Option<Vec<[f32; 2]>> {
+    let vertices = mesh.get_vertices();
+    let boundary = boundary_loop(&vertices, &mesh.get_indices())?;
+    if boundary.len() < 3 {
+        return None;
+    }
+
+    let mut points: Vec<[f32; 2]> = boundary
+        .iter()
+        .map(|&i| {
+            let [x, y, _] = vertices[i as usize].position;
+            [x, y]
+        })
+        .collect();
+
+    // Orient the loop counter-clockwise.
+    let signed_area: f32 = (0..points.len())
+        .map(|i| {
+            let [x0, y0] = points[i];
+            let [x1, y1] = points[(i + 1) % points.len()];
+            x0 * y1 - x1 * y0
+        })
+        .sum();
+    if signed_area < 0.0 {
+        points.reverse();
+    }
+
+    // Length of the segment starting at each point of the closed loop.
+    let segment_length = |i: usize| {
+        let [x0, y0] = points[i];
+        let [x1, y1] = points[(i + 1) % points.len()];
+        ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt()
+    };
+    let perimeter: f32 = (0..points.len()).map(segment_length).sum();
+    if !perimeter.is_finite() || perimeter <= f32::EPSILON {
+        return None;
+    }
+
+    // Walk along the loop, emitting a sample every `perimeter / SAMPLES`.
+    let step = perimeter / SAMPLES as f32;
+    let mut samples = Vec::with_capacity(SAMPLES);
+    let mut segment = 0;
+    let mut segment_start = 0.0;
+    for k in 0..SAMPLES {
+        let distance = k as f32 * step;
+        while segment + 1 < points.len() && segment_start + segment_length(segment) < distance {
+            segment_start += segment_length(segment);
+            segment += 1;
+        }
+
+        let length = segment_length(segment);
+        let t = if length > 0.0 {
+            ((distance - segment_start) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let [x0, y0] = points[segment];
+        let [x1, y1] = points[(segment + 1) % points.len()];
+        samples.push([x0 + t * (x1 - x0), y0 + t * (y1 - y0)]);
+    }
+
+    // Center the samples on their centroid.
+    let cx = samples.iter().map(|p| p[0]).sum::<f32>() / SAMPLES as f32;
+    let cy = samples.iter().map(|p| p[1]).sum::<f32>() / SAMPLES as f32;
+    Some(samples.into_iter().map(|[x, y]| [x - cx, y - cy]).collect())
+}
+
+/// Computes a resolution-independent distance between the shapes of two 2D
+/// meshes.
+///
+/// The outer boundary of each mesh is sampled evenly by arc length and
+/// centered on its centroid. The samples of `b` are then aligned on the
+/// samples of `a` with the optimal rotation (Procrustes analysis), trying
+/// every starting sample, and the smallest root mean square distance between
+/// the samples is returned.
+///
+/// The distance is 0 for identical shapes, regardless of their rotation or
+/// tessellation. Returns `MAX_SHAPE_DISTANCE` if one of the meshes has no
+/// usable boundary (empty, degenerate or closed 3D meshes).
+pub fn mesh_similarity(a: &impl Mesh, b: &impl Mesh) -> f32 {
+    let (Some(a), Some(b)) = (sample_boundary(a), sample_boundary(b)) else {
+        return MAX_SHAPE_DISTANCE;
+    };
+
+    let norm = |points: &[[f32; 2]]| points.iter().map(|[x, y]| x * x + y * y).sum::<f32>();
+    let norms = norm(&a) + norm(&b);
+
+    (0..SAMPLES)
+        .map(|shift| {
+            // With the optimal rotation, the residual is the sum of the norms
+            // minus twice the magnitude of the correlation.
+            let (dot, cross) = (0..SAMPLES).fold((0.0, 0.0), |(dot, cross), i| {
+                let [ax, ay] = a[i];
+                let [bx, by] = b[(i + shift) % SAMPLES];
+                (dot + ax * bx + ay * by, cross + bx * ay - by * ax)
+            });
+            let residual = norms - 2.0 * (dot * dot + cross * cross).sqrt();
+            (residual.max(0.0) / SAMPLES as f32).sqrt()
+        })
+        .fold(MAX_SHAPE_DISTANCE, f32::min)
+}
+
+impl Figure {
+    /// Returns the built-in 2D figure whose shape is the most similar to the
+    /// given mesh, along with the shape distance between them.
+    ///
+    /// See `mesh_similarity` for the distance.
+    pub fn closest_builtin(mesh: &impl Mesh) -> (Figure, f32) {
+        [
+            Figure::Triangle,
+            Figure::Pentagon,
+            Figure::Rectangle,
+            Figure::Trapezoid,
+            Figure::Parallelogram,
+            Figure::Circle(64),
+        ]
+        .into_iter()
+        .map(|figure| {
+            let distance = mesh_similarity(&figure, mesh);
+            (figure, distance)
+        })
+        .fold(
+            (Figure::default(), MAX_SHAPE_DISTANCE),
+            |best, candidate| {
+                if candidate.1 < best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use super::{boundary_edges, Vertex};
+
+/// The distance from the XY plane under which a vertex is considered flat.
+const FLAT_EPSILON: f32 = 1e-6;
+
+/// The size of a mesh, to sanity-check generated geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeshStats {
+    /// The number of vertices.
+    pub vertices: usize,
+    /// The number of triangles, with all their indices in range.
+    pub triangles: usize,
+    /// The area of the triangles.
+    ///
+    /// For a flat mesh, the signed areas of the triangles are summed, so the
+    /// triangles wound clockwise cancel those wound counter-clockwise.
+    /// Otherwise the areas are summed whatever the winding.
+    pub area: f32,
+    /// The total length of the boundary edges, as in `boundary_edges`.
+    ///
+    /// A closed mesh has no perimeter.
+    pub perimeter: f32,
+}
+
+impl fmt::Display for MeshStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} vertices, {} triangles, area {:.4}, perimeter {:.4}",
+            self.vertices, self.triangles, self.area, self.perimeter
+        )
+    }
+}
+
+/// Computes the statistics of a triangle mesh.
+///
+/// The triangles with an index out of range are ignored, and degenerate
+/// triangles have no area, so the statistics are always finite for finite
+/// positions.
+pub fn mesh_stats(vertices: &[Vertex], indices: &[u16]) -> MeshStats {
+    let triangles: Vec<[[f32; 3]; 3]> = indices
+        .chunks_exact(3)
+        .filter_map(|triangle| {
+            let corner = |k: usize| vertices.get(triangle[k] as usize).map(|v| v.position);
+            Some([corner(0)?, corner(1)?, corner(2)?])
+        })
+        .collect();
+
+    let flat = vertices
+        .iter()
+        .all(|vertex| vertex.position[2].abs() <= FLAT_EPSILON);
+    let area = if flat {
+        triangles
+            .iter()
+            .map(|[a, b, c]| ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.0)
+            .sum::<f32>()
+            .abs()
+    } else {
+        triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let u: [f32; 3] = std::array::from_fn(|i| b[i] - a[i]);
+                let v: [f32; 3] = std::array::from_fn(|i| c[i] - a[i]);
+                let cross = [
+                    u[1] * v[2] - u[2] * v[1],
+                    u[2] * v[0] - u[0] * v[2],
+                    u[0] * v[1] - u[1] * v[0],
+                ];
+                cross.iter().map(|c| c * c).sum::<f32>().sqrt() / 2.0
+            })
+            .sum()
+    };
+
+    let perimeter = boundary_edges(vertices, indices)
+        .chunks_exact(2)
+        .map(|edge| {
+            let (a, b) = (
+                vertices[edge[0] as usize].position,
+                vertices[edge[1] as usize].position,
+            );
+            (0..3).map(|i| (b[i] - a[i]).powi(2)).sum::<f32>().sqrt()
+        })
+        .sum();
+
+    MeshStats {
+        vertices: vertices.len(),
+        triangles: triangles.len(),
+        area,
+        perimeter,
+    }
+}
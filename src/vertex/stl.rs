@@ -0,0 +1,173 @@
+use std::{fmt, io::Read};
+
+use super::{obj::normalized, weld, Mesh, MeshData, Vertex, FLAT_NORMAL};
+
+/// The size of the header of a binary STL file, before the triangle count.
+const HEADER_SIZE: usize = 80;
+/// The size of a triangle of a binary STL file: the normal, the three
+/// vertices and the attribute byte count.
+const RECORD_SIZE: usize = 50;
+/// The distance under which two vertices are welded, relative to the size
+/// of the mesh.
+const RELATIVE_WELD_EPSILON: f32 = 1e-6;
+
+/// An error while importing an STL file.
+#[derive(Debug)]
+pub enum StlError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The binary file is shorter than its header, or than its triangle
+    /// count announces.
+    Truncated,
+    /// A line of the ASCII file could not be parsed.
+    InvalidLine { line: usize },
+    /// The file has more triangles than `u16` indices can address before
+    /// welding, 3 vertices per triangle.
+    TooManyTriangles(usize),
+}
+
+impl fmt::Display for StlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StlError::Io(e) => write!(f, "Failed to read the STL file: {}", e),
+            StlError::Truncated => write!(f, "The binary STL file is truncated"),
+            StlError::InvalidLine { line } => write!(f, "Line {}: invalid STL line", line),
+            StlError::TooManyTriangles(count) => write!(
+                f,
+                "The STL file has {} triangles, more than the {} supported",
+                count,
+                (u16::MAX as usize + 1) / 3
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StlError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StlError {
+    fn from(e: std::io::Error) -> Self {
+        StlError::Io(e)
+    }
+}
+
+impl MeshData {
+    /// Imports a mesh from a binary or ASCII STL file.
+    ///
+    /// The format is detected from the content: a file starting with
+    /// `solid` is ASCII, unless its size is exactly the one of a binary file
+    /// with the triangle count of its header. The normals of the file are
+    /// ignored.
+    ///
+    /// The vertices shared by several triangles are welded, and the mesh is
+    /// fitted into the [-0.5, 0.5] cube and colored by height, as with
+    /// `parse_obj`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if it has
+    /// more triangles than `u16` indices can address.
+    pub fn from_stl(mut reader: impl Read) -> Result<MeshData, StlError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let triangles = if bytes.starts_with(b"solid") && !is_binary(&bytes) {
+            parse_ascii(&String::from_utf8_lossy(&bytes))?
+        } else {
+            parse_binary(&bytes)?
+        };
+
+        let vertices: Vec<Vertex> = triangles
+            .into_iter()
+            .flatten()
+            .map(|position| Vertex {
+                position,
+                color: [1.0; 4],
+                normal: FLAT_NORMAL,
+                tex_coords: [0.0, 0.0],
+            })
+            .collect();
+        let indices: Vec<u16> = (0..vertices.len() as u32).map(|i| i as u16).collect();
+
+        let unwelded = MeshData { vertices, indices };
+        let (min, max) = unwelded.bounds();
+        let extent = (0..3).map(|i| max[i] - min[i]).fold(0.0, f32::max);
+        let (vertices, indices) = weld(
+            &unwelded.vertices,
+            &unwelded.indices,
+            extent * RELATIVE_WELD_EPSILON,
+        );
+        Ok(normalized(MeshData { vertices, indices }))
+    }
+}
+
+/// Returns whether the size of the file matches the triangle count of a
+/// binary header.
+fn is_binary(bytes: &[u8]) -> bool {
+    triangle_count(bytes)
+        .is_some_and(|count| bytes.len() == HEADER_SIZE + 4 + count as usize * RECORD_SIZE)
+}
+
+fn triangle_count(bytes: &[u8]) -> Option<u32> {
+    let count = bytes.get(HEADER_SIZE..HEADER_SIZE + 4)?;
+    Some(u32::from_le_bytes(count.try_into().unwrap()))
+}
+
+/// Returns an error if the triangles cannot be indexed before welding.
+fn check_count(count: usize) -> Result<(), StlError> {
+    if 3 * count > u16::MAX as usize + 1 {
+        return Err(StlError::TooManyTriangles(count));
+    }
+    Ok(())
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Vec<[[f32; 3]; 3]>, StlError> {
+    let count = triangle_count(bytes).ok_or(StlError::Truncated)?;
+    check_count(count as usize)?;
+    let records = bytes
+        .get(HEADER_SIZE + 4..HEADER_SIZE + 4 + count as usize * RECORD_SIZE)
+        .ok_or(StlError::Truncated)?;
+
+    let float =
+        |record: &[u8], k: usize| f32::from_le_bytes(record[4 * k..4 * k + 4].try_into().unwrap());
+    Ok(records
+        .chunks_exact(RECORD_SIZE)
+        .map(|record| {
+            // Skip the normal, the first 3 floats.
+            std::array::from_fn(|v| std::array::from_fn(|c| float(record, 3 + 3 * v + c)))
+        })
+        .collect())
+}
+
+fn parse_ascii(source: &str) -> Result<Vec<[[f32; 3]; 3]>, StlError> {
+    let mut triangles = Vec::new();
+    let mut facet: Vec<[f32; 3]> = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let invalid = || StlError::InvalidLine { line: i + 1 };
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("vertex") => {
+                let mut coordinates = tokens.map(|token| token.parse::<f32>());
+                let mut next = || coordinates.next().and_then(Result::ok).ok_or_else(invalid);
+                facet.push([next()?, next()?, next()?]);
+            }
+            Some("endloop") => {
+                let triangle: [[f32; 3]; 3] = facet.as_slice().try_into().map_err(|_| invalid())?;
+                triangles.push(triangle);
+                check_count(triangles.len())?;
+                facet.clear();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
@@ -0,0 +1,200 @@
+use std::{collections::HashMap, fmt};
+
+use super::Vertex;
+
+/// An error while subdividing a mesh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubdivisionError {
+    /// The subdivided mesh would have more vertices than 16-bit indices can
+    /// address.
+    TooManyVertices {
+        /// The number of vertices of the subdivided mesh.
+        vertices: usize,
+    },
+}
+
+impl fmt::Display for SubdivisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubdivisionError::TooManyVertices { vertices } => write!(
+                f,
+                "Subdividing would make {} vertices, more than 16-bit indices can address",
+                vertices
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubdivisionError {}
+
+/// An edge of the mesh being subdivided.
+struct Edge {
+    /// The vertices opposite to the edge in the triangles using it.
+    opposite: Vec<u16>,
+    /// The index of the vertex inserted on the edge.
+    midpoint: u16,
+}
+
+/// Smooths a triangle mesh with `iterations` steps of Loop subdivision.
+///
+/// Each step inserts a vertex on every edge and splits every triangle into
+/// four, keeping its winding. The vertices are then moved towards the
+/// average of their neighbours, along the boundary for the boundary
+/// vertices, so that a flat figure stays flat and its outline gets rounder.
+/// The colors, normals and texture coordinates are interpolated with the
+/// same weights as the positions. Triangles with an index out of range are
+/// dropped.
+///
+/// The edges are found by index, so a mesh with vertices repeated at the
+/// same position, for example with different colors, is split along them.
+///
+/// # Errors
+///
+/// Returns an error, instead of wrapping the indices around, if a step
+/// would make more vertices than 16-bit indices can address.
+pub fn subdivide(
+    vertices: &[Vertex],
+    indices: &[u16],
+    iterations: u32,
+) -> Result<(Vec<Vertex>, Vec<u16>), SubdivisionError> {
+    let mut mesh = (vertices.to_vec(), indices.to_vec());
+    for _ in 0..iterations {
+        mesh = subdivide_once(&mesh.0, &mesh.1)?;
+    }
+    Ok(mesh)
+}
+
+/// Runs a single step of Loop subdivision. See `subdivide`.
+fn subdivide_once(
+    vertices: &[Vertex],
+    indices: &[u16],
+) -> Result<(Vec<Vertex>, Vec<u16>), SubdivisionError> {
+    let triangles: Vec<[u16; 3]> = indices
+        .chunks_exact(3)
+        .filter(|triangle| triangle.iter().all(|&i| (i as usize) < vertices.len()))
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    // Number the edges in the order they are found.
+    let key = |a: u16, b: u16| (a.min(b), a.max(b));
+    let mut edges: HashMap<(u16, u16), Edge> = HashMap::new();
+    let mut order = Vec::new();
+    for &[a, b, c] in &triangles {
+        for (u, v, w) in [(a, b, c), (b, c, a), (c, a, b)] {
+            let count = vertices.len() + edges.len();
+            let edge = edges.entry(key(u, v)).or_insert_with(|| {
+                order.push(key(u, v));
+                Edge {
+                    opposite: Vec::new(),
+                    midpoint: count as u16,
+                }
+            });
+            edge.opposite.push(w);
+        }
+    }
+    let count = vertices.len() + edges.len();
+    if count > u16::MAX as usize + 1 {
+        return Err(SubdivisionError::TooManyVertices { vertices: count });
+    }
+
+    // An edge of a single triangle is on the boundary, as is an edge shared
+    // by more than two triangles, to keep the mesh from folding.
+    let is_boundary = |edge: &Edge| edge.opposite.len() != 2;
+    let mut neighbours = vec![Vec::new(); vertices.len()];
+    let mut boundary_neighbours = vec![Vec::new(); vertices.len()];
+    for &(a, b) in &order {
+        neighbours[a as usize].push(b);
+        neighbours[b as usize].push(a);
+        if is_boundary(&edges[&(a, b)]) {
+            boundary_neighbours[a as usize].push(b);
+            boundary_neighbours[b as usize].push(a);
+        }
+    }
+
+    let mut subdivided: Vec<Vertex> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, vertex)| {
+            let (neighbours, boundary) = (&neighbours[i], &boundary_neighbours[i]);
+            let at = |j: &u16| &vertices[*j as usize];
+            match boundary.len() {
+                // An inner vertex moves towards all its neighbours.
+                0 if !neighbours.is_empty() => {
+                    let n = neighbours.len();
+                    let beta = if n == 3 {
+                        3.0 / 16.0
+                    } else {
+                        3.0 / (8.0 * n as f32)
+                    };
+                    let mut weights = vec![(1.0 - n as f32 * beta, vertex)];
+                    weights.extend(neighbours.iter().map(|j| (beta, at(j))));
+                    combine(&weights)
+                }
+                // A boundary vertex only moves along the boundary.
+                2 => combine(&[
+                    (0.75, vertex),
+                    (0.125, at(&boundary[0])),
+                    (0.125, at(&boundary[1])),
+                ]),
+                // The corners and the isolated vertices are kept.
+                _ => *vertex,
+            }
+        })
+        .collect();
+
+    for &(a, b) in &order {
+        let edge = &edges[&(a, b)];
+        let (a, b) = (&vertices[a as usize], &vertices[b as usize]);
+        subdivided.push(if is_boundary(edge) {
+            combine(&[(0.5, a), (0.5, b)])
+        } else {
+            let (c, d) = (
+                &vertices[edge.opposite[0] as usize],
+                &vertices[edge.opposite[1] as usize],
+            );
+            combine(&[(0.375, a), (0.375, b), (0.125, c), (0.125, d)])
+        });
+    }
+
+    let midpoint = |u: u16, v: u16| edges[&key(u, v)].midpoint;
+    let indices = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| {
+            let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+            [a, ab, ca, b, bc, ab, c, ca, bc, ab, bc, ca]
+        })
+        .collect();
+
+    Ok((subdivided, indices))
+}
+
+/// Returns the weighted sum of the attributes of the vertices, the weights
+/// summing to 1. The normal is normalized again, unless it is zero.
+fn combine(weights: &[(f32, &Vertex)]) -> Vertex {
+    let mut vertex = Vertex {
+        position: [0.0; 3],
+        color: [0.0; 4],
+        normal: [0.0; 3],
+        tex_coords: [0.0; 2],
+    };
+    for &(weight, other) in weights {
+        for (sum, c) in vertex.position.iter_mut().zip(other.position) {
+            *sum += weight * c;
+        }
+        for (sum, c) in vertex.color.iter_mut().zip(other.color) {
+            *sum += weight * c;
+        }
+        for (sum, c) in vertex.normal.iter_mut().zip(other.normal) {
+            *sum += weight * c;
+        }
+        for (sum, c) in vertex.tex_coords.iter_mut().zip(other.tex_coords) {
+            *sum += weight * c;
+        }
+    }
+
+    let length = vertex.normal.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if length > 0.0 {
+        vertex.normal = vertex.normal.map(|c| c / length);
+    }
+    vertex
+}
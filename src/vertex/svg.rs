@@ -0,0 +1,79 @@
+use std::fmt::{self, Write};
+
+use super::{boundary_loop, Figure, Mesh};
+
+/// The distance from the XY plane under which a vertex is considered flat.
+const FLAT_EPSILON: f32 = 1e-6;
+
+/// An error while exporting a figure as SVG.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgError {
+    /// The figure has vertices outside the XY plane.
+    NotFlat,
+    /// The boundary of the figure cannot be chained into a loop.
+    NoBoundary,
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::NotFlat => write!(f, "Only flat figures can be exported as SVG"),
+            SvgError::NoBoundary => write!(f, "The figure has no boundary loop"),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+impl Figure {
+    /// Returns the outline of the figure as an SVG document of `size_px`
+    /// pixels wide and high.
+    ///
+    /// The [-0.5, 0.5] square of the XY plane is mapped onto the image, the
+    /// Y axis pointing up. The outline is the boundary loop of the triangles,
+    /// so curved figures are exported as their polygonal approximation. The
+    /// path is filled with the average color of the vertices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the figure is not flat, or if its boundary cannot
+    /// be chained into a single loop.
+    pub fn to_svg(&self, size_px: u32) -> Result<String, SvgError> {
+        let vertices = self.get_vertices();
+        if vertices
+            .iter()
+            .any(|vertex| vertex.position[2].abs() > FLAT_EPSILON)
+        {
+            return Err(SvgError::NotFlat);
+        }
+        let outline = boundary_loop(&vertices, &self.get_indices()).ok_or(SvgError::NoBoundary)?;
+
+        let size = size_px as f32;
+        let mut path = String::new();
+        for (k, &i) in outline.iter().enumerate() {
+            let [x, y, _] = vertices[i as usize].position;
+            let command = if k == 0 { 'M' } else { 'L' };
+            let _ = write!(
+                path,
+                "{}{:.2} {:.2} ",
+                command,
+                (x + 0.5) * size,
+                (0.5 - y) * size
+            );
+        }
+        path.push('Z');
+
+        let mut color = [0.0; 4];
+        for vertex in &vertices {
+            for (sum, c) in color.iter_mut().zip(vertex.color) {
+                *sum += c / vertices.len() as f32;
+            }
+        }
+        let [r, g, b] = [0, 1, 2].map(|k| (color[k].clamp(0.0, 1.0) * 255.0).round() as u8);
+
+        Ok(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size_px}" height="{size_px}" viewBox="0 0 {size_px} {size_px}"><path d="{path}" fill="rgb({r},{g},{b})" fill-opacity="{:.3}"/></svg>"#,
+            color[3].clamp(0.0, 1.0)
+        ))
+    }
+}
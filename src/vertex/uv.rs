@@ -0,0 +1,42 @@
+use super::Vertex;
+
+/// Sets the texture coordinates of every vertex from its XY position, mapping
+/// the bounding box of the vertices onto the whole texture.
+///
+/// The left of the box maps to `u = 0` and its top to `v = 0`, so that a
+/// flat figure shows the texture upright. A box with no width (or no height)
+/// maps to the middle of the texture along that axis.
+pub fn generate_planar_uvs(vertices: &mut [Vertex]) {
+    let Some(first) = vertices.first() else {
+        return;
+    };
+
+    let (min, max) = vertices.iter().fold(
+        (
+            [first.position[0], first.position[1]],
+            [first.position[0], first.position[1]],
+        ),
+        |(min, max), vertex| {
+            let [x, y, _] = vertex.position;
+            (
+                [min[0].min(x), min[1].min(y)],
+                [max[0].max(x), max[1].max(y)],
+            )
+        },
+    );
+    let normalize = |value: f32, min: f32, max: f32| {
+        if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        }
+    };
+
+    for vertex in vertices {
+        let [x, y, _] = vertex.position;
+        vertex.tex_coords = [
+            normalize(x, min[0], max[0]),
+            1.0 - normalize(y, min[1], max[1]),
+        ];
+    }
+}
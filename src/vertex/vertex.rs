@@ -1,42 +1,140 @@
+use std::mem::{offset_of, size_of};
+
 use bytemuck;
 
+use super::{hsv_to_rgb, FLAT_NORMAL};
+
+/// The color of the vertices created by `Vertex::at`.
+const GREY: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
 /// A vertex is a 3D point in space with a color.
 ///
-/// The color is represented as an RGB value, with each component being a
-/// `f32` between 0.0 and 1.0.
+/// The color is represented as an RGBA value, with each component being a
+/// `f32` between 0.0 and 1.0. The alpha blends the figure over the
+/// background, from transparent (0.0) to opaque (1.0).
 ///
 /// The position is represented as a 3D vector, with each component being a
 /// `f32` representing the x, y and z coordinates respectively.
+///
+/// The normal is a unit vector perpendicular to the surface at the vertex,
+/// facing the viewer, along the positive Z axis, for the flat figures.
+///
+/// The texture coordinates range from (0, 0) at the top left corner of a
+/// texture to (1, 1) at its bottom right corner.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex {
     /// The position of the vertex in 3D space.
     pub position: [f32; 3],
     /// The color of the vertex.
-    pub color: [f32; 3],
+    pub color: [f32; 4],
+    /// The normal of the surface at the vertex.
+    pub normal: [f32; 3],
+    /// The coordinates of the vertex in a texture.
+    pub tex_coords: [f32; 2],
 }
 
+// The fields are packed without padding, as described by `Vertex::desc`.
+const _: () =
+    assert!(size_of::<Vertex>() == offset_of!(Vertex, tex_coords) + size_of::<[f32; 2]>());
+const _: () = assert!(size_of::<Vertex>() == size_of::<[f32; 12]>());
+
 impl Vertex {
+    /// Returns an opaque grey vertex at the given position, facing the
+    /// viewer, without texture coordinates.
+    pub fn at(x: f32, y: f32, z: f32) -> Self {
+        Vertex {
+            position: [x, y, z],
+            color: GREY,
+            normal: FLAT_NORMAL,
+            tex_coords: [0.0, 0.0],
+        }
+    }
+
+    /// Returns an opaque vertex of the given color at the given position,
+    /// facing the viewer, without texture coordinates.
+    pub fn at_colored(x: f32, y: f32, z: f32, r: f32, g: f32, b: f32) -> Self {
+        Vertex::at(x, y, z).with_color(r, g, b)
+    }
+
+    /// Returns an opaque vertex of the given HSV color at the given position,
+    /// facing the viewer, without texture coordinates.
+    ///
+    /// The hue is in degrees, the saturation and the value range from 0.0 to
+    /// 1.0, as in `hsv_to_rgb`.
+    pub fn from_hsv(h: f32, s: f32, v: f32, x: f32, y: f32, z: f32) -> Self {
+        let [r, g, b] = hsv_to_rgb(h, s, v);
+        Vertex::at_colored(x, y, z, r, g, b)
+    }
+
+    /// Returns the Euclidean distance between the positions of two vertices.
+    pub fn distance_to(&self, other: &Vertex) -> f32 {
+        (0..3)
+            .map(|i| (other.position[i] - self.position[i]).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Returns the vertex with the given opaque color, its other attributes
+    /// unchanged.
+    pub fn with_color(self, r: f32, g: f32, b: f32) -> Self {
+        Vertex {
+            color: [r, g, b, 1.0],
+            ..self
+        }
+    }
+
     /// Returns the vertex buffer layout for the `Vertex` type.
     ///
     /// The layout is suitable for use with a vertex shader that takes a
-    /// `vec3<f32>` for the position and a `vec3<f32>` for the color.
+    /// `vec3<f32>` for the position, a `vec4<f32>` for the color, a
+    /// `vec3<f32>` for the normal and a `vec2<f32>` for the texture
+    /// coordinates.
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            array_stride: size_of::<Vertex>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Vertex,
             attributes: &[
                 wgpu::VertexAttribute {
-                    offset: 0,
+                    offset: offset_of!(Vertex, position) as wgpu::BufferAddress,
                     shader_location: 0,
                     format: wgpu::VertexFormat::Float32x3,
                 },
                 wgpu::VertexAttribute {
-                    offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                    offset: offset_of!(Vertex, color) as wgpu::BufferAddress,
                     shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+                wgpu::VertexAttribute {
+                    offset: offset_of!(Vertex, normal) as wgpu::BufferAddress,
+                    shader_location: 2,
                     format: wgpu::VertexFormat::Float32x3,
                 },
+                wgpu::VertexAttribute {
+                    offset: offset_of!(Vertex, tex_coords) as wgpu::BufferAddress,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
             ],
         }
     }
+
+    /// Linearly interpolates two vertices, component-wise.
+    ///
+    /// The attributes of `a` are returned for `t = 0`, those of `b` for
+    /// `t = 1`. The interpolated normal is not normalized.
+    pub fn lerp(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+        Vertex {
+            position: lerp_array(a.position, b.position, t),
+            color: lerp_array(a.color, b.color, t),
+            normal: lerp_array(a.normal, b.normal, t),
+            tex_coords: lerp_array(a.tex_coords, b.tex_coords, t),
+        }
+    }
+}
+
+/// Linearly interpolates two arrays, component-wise.
+fn lerp_array<const N: usize>(a: [f32; N], b: [f32; N], t: f32) -> [f32; N] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
 }
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use super::Vertex;
+
+/// The smallest cell of the spatial hash, so that a tiny epsilon does not
+/// scatter the vertices over huge cell coordinates.
+const MIN_CELL_SIZE: f32 = 1e-6;
+
+/// Merges the vertices whose positions and colors are within `epsilon` of
+/// each other, component-wise, and remaps the indices to the merged
+/// vertices.
+///
+/// The first vertex of each group is kept, with its normal and texture
+/// coordinates, and the vertices keep their order. The vertices are hashed
+/// into a grid of cells at least `epsilon` wide, so that each vertex is only
+/// compared to the vertices of the neighboring cells. The triangles are kept
+/// even if welding collapses them; see `repair` to remove them. The indices
+/// out of range stay out of range.
+pub fn weld(vertices: &[Vertex], indices: &[u16], epsilon: f32) -> (Vec<Vertex>, Vec<u16>) {
+    let cell_size = epsilon.max(MIN_CELL_SIZE);
+    let cell_of = |position: [f32; 3]| position.map(|c| (c / cell_size).floor() as i64);
+    let is_close = |a: &Vertex, b: &Vertex| {
+        let close = |x: &[f32], y: &[f32]| x.iter().zip(y).all(|(x, y)| (x - y).abs() <= epsilon);
+        close(&a.position, &b.position) && close(&a.color, &b.color)
+    };
+
+    let mut welded: Vec<Vertex> = Vec::new();
+    let mut cells: HashMap<[i64; 3], Vec<u16>> = HashMap::new();
+    let remap: Vec<u16> = vertices
+        .iter()
+        .map(|vertex| {
+            let [x, y, z] = cell_of(vertex.position);
+            let neighbors = (-1..=1).flat_map(|dx| {
+                (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| [x + dx, y + dy, z + dz]))
+            });
+            let found = neighbors
+                .filter_map(|cell| cells.get(&cell))
+                .flatten()
+                .find(|&&i| is_close(&welded[i as usize], vertex))
+                .copied();
+
+            found.unwrap_or_else(|| {
+                let index = welded.len() as u16;
+                welded.push(*vertex);
+                cells.entry([x, y, z]).or_default().push(index);
+                index
+            })
+        })
+        .collect();
+
+    let merged = (vertices.len() - welded.len()) as u16;
+    let indices = indices
+        .iter()
+        .map(|&i| remap.get(i as usize).copied().unwrap_or_else(|| i - merged))
+        .collect();
+
+    (welded, indices)
+}
@@ -0,0 +1,39 @@
+use wasm_bindgen::prelude::*;
+use winit::{
+    event_loop::EventLoop,
+    platform::web::{EventLoopExtWebSys, WindowExtWebSys},
+    window::Window,
+};
+
+use crate::{control::Command, dragonfly::Dragonfly, error::DragonflyError};
+
+/// Starts the application in the browser, on a canvas appended to the body
+/// of the page.
+///
+/// This is the entry point of the web build, run when the module is loaded,
+/// in place of the `main` function of the native build. The application
+/// runs with its default options, the page having no command line.
+///
+/// # Errors
+///
+/// Returns an error if the event loop cannot be created.
+#[wasm_bindgen(start)]
+pub async fn start() -> Result<(), JsValue> {
+    let event_loop = EventLoop::<Command>::with_user_event()
+        .build()
+        .map_err(|e| JsValue::from_str(&DragonflyError::EventLoop(e.to_string()).to_string()))?;
+    let app = Dragonfly::default().with_proxy(event_loop.create_proxy());
+    event_loop.spawn_app(app);
+    Ok(())
+}
+
+/// Returns the canvas the window draws on.
+///
+/// # Errors
+///
+/// Returns an error if the window has no canvas anymore.
+pub fn canvas(window: &Window) -> Result<web_sys::HtmlCanvasElement, DragonflyError> {
+    window
+        .canvas()
+        .ok_or_else(|| DragonflyError::SurfaceCreation("The window has no canvas".to_string()))
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        animation::{Animation, EasingFn},
+        vertex::{Figure, Mesh},
+    };
+
+    const EASINGS: [EasingFn; 4] = [
+        EasingFn::Linear,
+        EasingFn::EaseIn,
+        EasingFn::EaseOut,
+        EasingFn::EaseInOut,
+    ];
+
+    fn pulse(easing: EasingFn) -> Animation {
+        Animation {
+            figure: Figure::Triangle,
+            duration_secs: 2.0,
+            easing,
+        }
+    }
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in EASINGS {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(-1.0), 0.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+        assert_eq!(EasingFn::EaseIn.apply(0.5), 0.25);
+        assert_eq!(EasingFn::EaseOut.apply(0.5), 0.75);
+        assert_eq!(EasingFn::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(EasingFn::EaseInOut.apply(0.25), 0.125);
+    }
+
+    #[test]
+    fn test_tick_blends_to_white() {
+        let figure = Figure::Triangle.get_vertices();
+        for easing in EASINGS {
+            let animation = pulse(easing);
+            let start = animation.tick(0.0);
+            let end = animation.tick(1.0);
+            for ((vertex, start), end) in figure.iter().zip(&start).zip(&end) {
+                assert_eq!(start.color, vertex.color);
+                assert_eq!(end.color, [1.0; 4]);
+                assert_eq!(end.position, vertex.position);
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_tick_at_midpoint() {
+        let figure = Figure::Triangle.get_vertices();
+        let middle = pulse(EasingFn::Linear).tick(0.5);
+        for (vertex, middle) in figure.iter().zip(&middle) {
+            for channel in 0..3 {
+                let expected = (vertex.color[channel] + 1.0) / 2.0;
+                assert!((middle.color[channel] - expected).abs() < 1e-6);
+            }
+            assert_eq!(middle.color[3], vertex.color[3]);
+        }
+    }
+
+    #[test]
+    fn test_progress_pulses() {
+        let animation = pulse(EasingFn::Linear);
+        for (elapsed, progress) in [(0.0, 0.0), (1.0, 0.5), (2.0, 1.0), (3.0, 0.5), (4.0, 0.0)] {
+            assert!((animation.progress(elapsed) - progress).abs() < 1e-6);
+        }
+
+        let still = Animation {
+            duration_secs: 0.0,
+            ..animation
+        };
+        assert_eq!(still.progress(1.0), 0.0);
+    }
+}
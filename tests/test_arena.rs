@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use dragonfly::{arena::FrameArena, vertex::Vertex};
+
+    /// An allocator counting the allocations of each thread, so that the
+    /// tests running in parallel do not disturb each other.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            System.realloc(ptr, layout, new_size)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(|count| count.get())
+    }
+
+    /// Simulates the per-frame mesh work of an animation, interpolating the
+    /// positions of two meshes into the arena.
+    fn animate_frame(arena: &mut FrameArena, from: &[Vertex], to: &[Vertex], t: f32) {
+        arena.reset();
+        let vertices = arena.alloc_vertices(from.len());
+        for ((vertex, a), b) in vertices.iter_mut().zip(from).zip(to) {
+            for i in 0..3 {
+                vertex.position[i] = a.position[i] + t * (b.position[i] - a.position[i]);
+                vertex.color[i] = a.color[i] + t * (b.color[i] - a.color[i]);
+            }
+        }
+        let indices = arena.alloc_indices(from.len());
+        for (i, index) in indices.iter_mut().enumerate() {
+            *index = i as u16;
+        }
+    }
+
+    #[test]
+    fn test_arena_reset_reuses_memory() {
+        let mut arena = FrameArena::with_capacity(16, 16);
+        arena.alloc_vertices(10)[0].position = [1.0, 2.0, 3.0];
+        arena.alloc_indices(6).copy_from_slice(&[0, 1, 2, 0, 2, 3]);
+        assert_eq!(arena.vertices().len(), 10);
+        assert_eq!(arena.indices(), &[0, 1, 2, 0, 2, 3]);
+
+        arena.reset();
+        assert!(arena.vertices().is_empty());
+        assert!(arena.indices().is_empty());
+        assert_eq!(arena.vertex_capacity(), 16);
+        assert_eq!(arena.index_capacity(), 16);
+
+        // The memory handed out after a reset is zeroed again.
+        assert_eq!(arena.alloc_vertices(1)[0].position, [0.0; 3]);
+        assert_eq!(arena.growths(), 0);
+    }
+
+    #[test]
+    fn test_arena_bump_allocations() {
+        let mut arena = FrameArena::with_capacity(8, 0);
+        arena.alloc_vertices(3).fill(Vertex {
+            position: [1.0; 3],
+            color: [1.0; 4],
+            normal: [0.0, 0.0, 1.0],
+            tex_coords: [0.0, 0.0],
+        });
+        assert_eq!(arena.alloc_vertices(2).len(), 2);
+
+        // Successive allocations are laid out one after the other.
+        let vertices = arena.vertices();
+        assert_eq!(vertices.len(), 5);
+        assert_eq!(vertices[2].position, [1.0; 3]);
+        assert_eq!(vertices[3].position, [0.0; 3]);
+    }
+
+    #[test]
+    fn test_arena_grows_beyond_capacity() {
+        let mut arena = FrameArena::with_capacity(4, 0);
+        arena.alloc_vertices(3);
+        assert_eq!(arena.growths(), 0);
+
+        arena.alloc_vertices(100);
+        assert_eq!(arena.growths(), 1);
+        assert_eq!(arena.vertices().len(), 103);
+        assert!(arena.vertex_capacity() >= 103);
+
+        // The grown capacity is kept for the next frames.
+        arena.reset();
+        arena.alloc_vertices(103);
+        assert_eq!(arena.growths(), 1);
+    }
+
+    #[test]
+    fn test_arena_steady_state_zero_allocations() {
+        let from = vec![
+            Vertex {
+                position: [0.0; 3],
+                color: [1.0, 0.0, 0.0, 1.0],
+                normal: [0.0, 0.0, 1.0],
+                tex_coords: [0.0, 0.0],
+            };
+            64
+        ];
+        let to = vec![
+            Vertex {
+                position: [1.0; 3],
+                color: [0.0, 0.0, 1.0, 1.0],
+                normal: [0.0, 0.0, 1.0],
+                tex_coords: [0.0, 0.0],
+            };
+            64
+        ];
+
+        // The first frame grows the arena to its steady-state size.
+        let mut arena = FrameArena::default();
+        animate_frame(&mut arena, &from, &to, 0.0);
+        let growths = arena.growths();
+
+        let before = allocations();
+        for frame in 1..=100 {
+            animate_frame(&mut arena, &from, &to, frame as f32 / 100.0);
+        }
+        assert_eq!(allocations(), before);
+        assert_eq!(arena.growths(), growths);
+        assert_eq!(arena.vertices()[0].position, [1.0; 3]);
+    }
+}
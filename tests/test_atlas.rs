@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+
+    use std::ops::Range;
+
+    use dragonfly::{
+        atlas::{figure_atlas, FigureAtlas},
+        pool::CachedMesh,
+        vertex::{Figure, Mesh},
+    };
+
+    /// Asserts that the ranges follow each other from 0, without gap or
+    /// overlap, up to the given end.
+    fn assert_contiguous(ranges: &[Range<u32>], end: usize) {
+        let mut next = 0;
+        for range in ranges {
+            assert_eq!(range.start, next, "{:?}", ranges);
+            assert!(range.start <= range.end, "{:?}", range);
+            next = range.end;
+        }
+        assert_eq!(next as usize, end);
+    }
+
+    #[test]
+    fn test_ranges_cover_buffers() {
+        let atlas = figure_atlas();
+        assert_eq!(atlas.ranges.len(), Figure::ALL.len());
+
+        let vertex_ranges: Vec<Range<u32>> = atlas
+            .ranges
+            .iter()
+            .map(|range| range.vertices.clone())
+            .collect();
+        assert_contiguous(&vertex_ranges, atlas.vertices.len());
+
+        // Each figure has its triangles, outline and wireframe in turn.
+        let index_ranges: Vec<Range<u32>> = atlas
+            .ranges
+            .iter()
+            .flat_map(|range| {
+                [
+                    range.indices.clone(),
+                    range.edge_indices.clone(),
+                    range.wireframe_indices.clone(),
+                ]
+            })
+            .collect();
+        assert_contiguous(&index_ranges, atlas.indices.len());
+
+        // The whole set stays small enough to be uploaded once.
+        assert!(
+            atlas.byte_size() < 256 * 1024,
+            "{} bytes",
+            atlas.byte_size()
+        );
+    }
+
+    #[test]
+    fn test_ranges_match_figures() {
+        let atlas = figure_atlas();
+        for (figure, range) in Figure::ALL.iter().zip(&atlas.ranges) {
+            let positions: Vec<[f32; 3]> = atlas
+                .mesh_vertices(range)
+                .iter()
+                .map(|vertex| vertex.position)
+                .collect();
+            let expected: Vec<[f32; 3]> = figure
+                .get_vertices()
+                .iter()
+                .map(|vertex| vertex.position)
+                .collect();
+            assert_eq!(positions, expected, "{}", figure);
+
+            // The indices are relative to the base vertex.
+            let indices = &atlas.indices[range.indices.start as usize..range.indices.end as usize];
+            assert_eq!(indices, figure.get_indices().as_slice(), "{}", figure);
+            assert_eq!(range.base_vertex(), range.vertices.start as i32);
+        }
+    }
+
+    #[test]
+    fn test_empty_atlas() {
+        let atlas = FigureAtlas::new(&[]);
+        assert!(atlas.vertices.is_empty() && atlas.indices.is_empty());
+        assert_eq!(atlas.range(0), None);
+
+        let atlas = FigureAtlas::new(&[CachedMesh::from_mesh(&Figure::Triangle)]);
+        assert_eq!(atlas.range(0).unwrap().indices, 0..3);
+        assert_eq!(atlas.range(0).unwrap().edge_indices, 3..9);
+    }
+}
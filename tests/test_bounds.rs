@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{Figure, Mesh, MeshData, Vertex, FIT_HALF_EXTENT};
+
+    /// Returns one figure of every variant.
+    fn every_figure() -> Vec<Figure> {
+        vec![
+            Figure::Triangle,
+            Figure::Pentagon,
+            Figure::Rectangle,
+            Figure::Trapezoid,
+            Figure::Parallelogram,
+            Figure::Circle(64),
+            Figure::Icosphere(2),
+            Figure::Torus {
+                major_radius: 0.35,
+                minor_radius: 0.15,
+                major_segments: 48,
+                minor_segments: 24,
+            },
+            Figure::Cylinder { segments: 32 },
+            Figure::Cone { segments: 32 },
+            Figure::Pyramid,
+            Figure::Tetrahedron,
+            Figure::Overlap,
+            Figure::Hexagon,
+            Figure::Hexagram,
+            Figure::Diamond,
+            Figure::Cross,
+            Figure::default_arrow(),
+            Figure::RoundedRectangle {
+                width: 0.9,
+                height: 0.6,
+                corner_radius: 0.15,
+                segments: 4,
+            },
+            Figure::Heart { segments: 16 },
+            Figure::Plane { subdivisions: 4 },
+            Figure::Lathe {
+                profile: vec![[0.0, -0.5], [0.5, 0.0], [0.0, 0.5]],
+                segments: 16,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_figures_within_clip_space() {
+        for figure in every_figure() {
+            let (min, max) = figure.bounds();
+            for i in 0..3 {
+                assert!(min[i] <= max[i], "{:?}: {:?} {:?}", figure, min, max);
+                assert!(
+                    min[i] >= -0.55 && max[i] <= 0.55,
+                    "{:?}: {:?} {:?}",
+                    figure,
+                    min,
+                    max
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rectangle_bounds_and_centroid() {
+        let (min, max) = Figure::Rectangle.bounds();
+        assert_eq!(min[2], 0.0);
+        assert_eq!(max[2], 0.0);
+        assert!(min[0] < 0.0 && max[0] > 0.0);
+        assert!((min[0] + max[0]).abs() < 1e-6);
+        assert!((min[1] + max[1]).abs() < 1e-6);
+
+        let centroid = Figure::Rectangle.centroid();
+        assert!(centroid.iter().all(|c| c.abs() < 1e-6), "{:?}", centroid);
+    }
+
+    #[test]
+    fn test_translated_mesh_bounds() {
+        let mesh = Figure::Rectangle.mesh_data().translated([1.0, 2.0, 3.0]);
+        let (min, max) = mesh.bounds();
+        let (rect_min, rect_max) = Figure::Rectangle.bounds();
+        for i in 0..3 {
+            let offset = [1.0, 2.0, 3.0][i];
+            assert!((min[i] - rect_min[i] - offset).abs() < 1e-6);
+            assert!((max[i] - rect_max[i] - offset).abs() < 1e-6);
+        }
+        let centroid = mesh.centroid();
+        assert!((centroid[0] - 1.0).abs() < 1e-6 && (centroid[1] - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_normalized_mesh_fits_view() {
+        // A rectangle from (10, 10) to (20, 15).
+        let vertices = vec![
+            Vertex::at(10.0, 10.0, 0.0),
+            Vertex::at(20.0, 10.0, 0.0),
+            Vertex::at(20.0, 15.0, 0.0),
+            Vertex::at(10.0, 15.0, 0.0),
+        ];
+        let mesh = MeshData::from((vertices, vec![0, 1, 2, 0, 2, 3]));
+
+        let (min, max) = mesh.normalized().bounds();
+        let expected = (
+            [-FIT_HALF_EXTENT, -FIT_HALF_EXTENT / 2.0, 0.0],
+            [FIT_HALF_EXTENT, FIT_HALF_EXTENT / 2.0, 0.0],
+        );
+        for i in 0..3 {
+            assert!((min[i] - expected.0[i]).abs() < 1e-6, "{:?}", min);
+            assert!((max[i] - expected.1[i]).abs() < 1e-6, "{:?}", max);
+        }
+    }
+
+    #[test]
+    fn test_normalized_point_is_translated() {
+        let point = MeshData::from((vec![Vertex::at(10.0, 20.0, 5.0)], Vec::new()));
+        let normalized = point.normalized();
+        assert_eq!(normalized.vertices[0].position, [0.0; 3]);
+
+        // A mesh already fitted is left unchanged.
+        let rectangle = Figure::Rectangle.mesh_data().normalized();
+        let (min, max) = rectangle.normalized().bounds();
+        let (expected_min, expected_max) = rectangle.bounds();
+        for i in 0..3 {
+            assert!((min[i] - expected_min[i]).abs() < 1e-6);
+            assert!((max[i] - expected_max[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_empty_mesh_at_origin() {
+        let empty = MeshData::default();
+        assert_eq!(empty.bounds(), ([0.0; 3], [0.0; 3]));
+        assert_eq!(empty.centroid(), [0.0; 3]);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        snapshot,
+        vertex::{Figure, Mesh, MeshBuilder},
+    };
+
+    const WHITE: [f32; 4] = [1.0; 4];
+
+    /// Pushes a unit quad as two triangles of three vertices each.
+    fn quad_as_triangles(builder: &mut MeshBuilder) {
+        let corners = [
+            [-0.5, -0.5, 0.0],
+            [0.5, -0.5, 0.0],
+            [0.5, 0.5, 0.0],
+            [-0.5, 0.5, 0.0],
+        ];
+        for triangle in [[0, 1, 2], [0, 2, 3]] {
+            let [a, b, c] = triangle.map(|k| builder.push_vertex(corners[k], WHITE));
+            builder.push_triangle(a, b, c);
+        }
+    }
+
+    #[test]
+    fn test_quad_with_and_without_dedup() {
+        let mut builder = MeshBuilder::new();
+        quad_as_triangles(&mut builder);
+        let plain = builder.build();
+
+        let mut builder = MeshBuilder::new().with_dedup(true);
+        quad_as_triangles(&mut builder);
+        let merged = builder.build();
+
+        assert_eq!(plain.vertices.len(), 6);
+        assert_eq!(plain.indices, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(merged.vertices.len(), 4);
+        assert_eq!(merged.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert!(merged.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dedup_distinguishes_colors() {
+        let mut builder = MeshBuilder::new().with_dedup(true);
+        let a = builder.push_vertex([0.0; 3], WHITE);
+        let b = builder.push_vertex([0.0; 3], [1.0, 0.0, 0.0, 1.0]);
+        assert_ne!(a, b);
+        assert_eq!(builder.push_vertex([0.0; 3], WHITE), a);
+        assert_eq!(builder.vertex_count(), 2);
+    }
+
+    #[test]
+    fn test_push_quad() {
+        let mut builder = MeshBuilder::new();
+        let [a, b, c, d] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+            .map(|[x, y]| builder.push_vertex([x, y, 0.0], WHITE));
+        builder.push_quad(a, b, c, d);
+        let quad = builder.build();
+        assert_eq!(quad.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert!((quad.get_area() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_extend_from_mesh() {
+        let mut builder = MeshBuilder::new();
+        builder.extend_from_mesh(&Figure::Triangle);
+        builder.extend_from_mesh(&Figure::Rectangle);
+        let mesh = builder.build();
+
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 3, 4, 6, 4, 5, 6]);
+        assert_eq!(
+            mesh.vertices[3].normal,
+            Figure::Rectangle.get_vertices()[0].normal
+        );
+
+        // The same figure twice shares its vertices when deduplicating.
+        let mut builder = MeshBuilder::new().with_dedup(true);
+        builder.extend_from_mesh(&Figure::Triangle);
+        builder.extend_from_mesh(&Figure::Triangle);
+        let mesh = builder.build();
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many vertices")]
+    fn test_vertex_overflow_panics() {
+        let mut builder = MeshBuilder::new();
+        for i in 0..=u16::MAX as u32 + 1 {
+            builder.push_vertex([i as f32, 0.0, 0.0], WHITE);
+        }
+    }
+
+    #[test]
+    fn test_built_mesh_renders() {
+        let mut builder = MeshBuilder::new();
+        quad_as_triangles(&mut builder);
+        let pixels = snapshot::render_snapshot(&builder.build(), 8, 8, wgpu::Color::BLACK).unwrap();
+        let center = ((4 * 8 + 4) * 4) as usize;
+        assert_eq!(&pixels[center..center + 4], &[255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        camera::{Camera2D, FIT_FRACTION, MAX_ZOOM, MIN_ZOOM, ZOOM_STEP},
+        vertex::{Figure, Mesh},
+    };
+
+    #[test]
+    fn test_default_camera_is_identity() {
+        assert_eq!(
+            Camera2D::default().view_proj(),
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zoom_round_trip() {
+        let mut camera = Camera2D::default();
+        for _ in 0..10 {
+            camera.zoom_by(ZOOM_STEP);
+        }
+        assert!(camera.zoom > 2.5);
+        for _ in 0..10 {
+            camera.zoom_by(1.0 / ZOOM_STEP);
+        }
+        assert!((camera.zoom - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_zoom_clamped() {
+        let mut camera = Camera2D::default();
+        camera.zoom_by(1000.0);
+        assert_eq!(camera.zoom, MAX_ZOOM);
+        assert_eq!(camera.zoom, 50.0);
+        camera.zoom_by(1e-6);
+        assert_eq!(camera.zoom, MIN_ZOOM);
+        assert_eq!(camera.zoom, 0.1);
+    }
+
+    #[test]
+    fn test_view_proj_and_to_world() {
+        let camera = Camera2D {
+            center: [0.5, -0.25],
+            zoom: 2.0,
+        };
+        let matrix = camera.view_proj();
+        assert_eq!(matrix[0][0], 2.0);
+        assert_eq!(matrix[1][1], 2.0);
+        assert_eq!(matrix[3], [-1.0, 0.5, 0.0, 1.0]);
+
+        // The center is shown at the origin, and the corners of the window
+        // half a unit away.
+        assert_eq!(camera.to_world([0.0, 0.0]), [0.5, -0.25]);
+        assert_eq!(camera.to_world([1.0, 1.0]), [1.0, 0.25]);
+    }
+
+    #[test]
+    fn test_zoom_then_pan_matrix() {
+        let mut camera = Camera2D::default();
+        camera.zoom_by(2.0);
+        camera.pan([0.5, 0.0]);
+        assert_eq!(
+            camera.view_proj(),
+            [
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0, 0.0, 0.0, 1.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zoom_at_keeps_point_under_cursor() {
+        let mut camera = Camera2D {
+            center: [0.25, -0.5],
+            zoom: 1.5,
+        };
+        let cursor = [0.6, -0.3];
+        let anchor = camera.to_world(cursor);
+        for factor in [ZOOM_STEP, 3.0, 1.0 / ZOOM_STEP, 1e6] {
+            camera.zoom_at(factor, cursor);
+            let point = camera.to_world(cursor);
+            assert!((point[0] - anchor[0]).abs() < 1e-5, "{:?}", point);
+            assert!((point[1] - anchor[1]).abs() < 1e-5, "{:?}", point);
+        }
+        assert_eq!(camera.zoom, MAX_ZOOM);
+    }
+
+    #[test]
+    fn test_drag_follows_cursor() {
+        let mut camera = Camera2D {
+            center: [1.0, 0.0],
+            zoom: 4.0,
+        };
+        let point = camera.to_world([0.0, 0.0]);
+        camera.drag([0.5, -0.25]);
+        assert_eq!(camera.to_world([0.5, -0.25]), point);
+    }
+
+    #[test]
+    fn test_pan() {
+        let mut camera = Camera2D::default();
+        camera.pan([0.25, -0.5]);
+        camera.pan([0.25, 0.0]);
+        assert_eq!(camera.center, [0.5, -0.5]);
+        assert_eq!(camera.view_proj()[3], [-0.5, 0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_reset_after_pan_and_zoom() {
+        let initial = Camera2D::default().view_proj();
+        let mut camera = Camera2D::default();
+        camera.pan([0.3, -0.7]);
+        camera.zoom_by(ZOOM_STEP);
+        camera.zoom_by(ZOOM_STEP);
+        camera.pan([-1.1, 0.2]);
+        camera.zoom_by(1.0 / 3.0);
+        assert_ne!(camera.view_proj(), initial);
+
+        camera.reset();
+        for (column, expected) in camera.view_proj().iter().zip(&initial) {
+            for (a, b) in column.iter().zip(expected) {
+                assert!((a - b).abs() <= f32::EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_figure() {
+        // The triangle spans from (-0.5, -0.5) to (0.5, 0.5), so it fills 80%
+        // of the clip space with a zoom of 1.6.
+        let camera = Camera2D::fit(Figure::Triangle.bounds(), (800, 600));
+        assert_eq!(camera.center, [0.0, 0.0]);
+        assert!((camera.zoom - 2.0 * FIT_FRACTION).abs() < 1e-6);
+
+        // A wide box is fitted on the narrower axis of the window.
+        let bounds = ([1.0, 0.0, 0.0], [3.0, 0.5, 0.0]);
+        let landscape = Camera2D::fit(bounds, (800, 600));
+        assert_eq!(landscape.center, [2.0, 0.25]);
+        assert!((landscape.zoom - 3.2).abs() < 1e-6);
+        let portrait = Camera2D::fit(bounds, (600, 800));
+        assert!((portrait.zoom - 0.8).abs() < 1e-6);
+
+        // A flat box is fitted on the other axis, a point keeps the zoom.
+        let flat = ([0.0, 0.0, 0.0], [0.0, 4.0, 0.0]);
+        assert!((Camera2D::fit(flat, (600, 800)).zoom - 0.4).abs() < 1e-6);
+        let point = ([1.0, 2.0, 0.0], [1.0, 2.0, 0.0]);
+        assert_eq!(
+            Camera2D::fit(point, (800, 600)),
+            Camera2D {
+                center: [1.0, 2.0],
+                zoom: 1.0
+            }
+        );
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+
+    use std::path::PathBuf;
+
+    use dragonfly::cli::{Args, VerifyArgs};
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_no_arguments() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+    }
+
+    #[test]
+    fn test_control_stdin() {
+        assert!(parse(&["--control", "stdin"]).unwrap().control_stdin);
+        assert!(parse(&["--control", "socket"]).is_err());
+        assert!(parse(&["--control"]).is_err());
+    }
+
+    #[test]
+    fn test_background() {
+        let args = parse(&["--background", "0.1", "0.1", "0.1", "1.0"]).unwrap();
+        assert_eq!(args.background, Some([0.1, 0.1, 0.1, 1.0]));
+    }
+
+    #[test]
+    fn test_background_invalid() {
+        assert!(parse(&["--background", "0.1", "0.1", "0.1"]).is_err());
+        assert!(parse(&["--background", "0.1", "red", "0.1", "1.0"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_argument() {
+        assert!(parse(&["--fullscreen"]).is_err());
+    }
+
+    #[test]
+    fn test_frame_hash() {
+        assert!(parse(&["--frame-hash"]).unwrap().frame_hash);
+        assert!(!parse(&[]).unwrap().frame_hash);
+    }
+
+    #[test]
+    fn test_stats() {
+        assert!(parse(&["--stats"]).unwrap().stats);
+        assert!(!parse(&[]).unwrap().stats);
+    }
+
+    #[test]
+    fn test_repair() {
+        assert!(parse(&["--repair"]).unwrap().repair);
+        assert!(!parse(&[]).unwrap().repair);
+    }
+
+    #[test]
+    fn test_present_mode() {
+        assert_eq!(
+            parse(&["--present-mode", "mailbox"]).unwrap().present_mode,
+            Some(wgpu::PresentMode::Mailbox)
+        );
+        assert_eq!(
+            parse(&["--present-mode", "fifo"]).unwrap().present_mode,
+            Some(wgpu::PresentMode::Fifo)
+        );
+        assert_eq!(
+            parse(&["--present-mode", "immediate"])
+                .unwrap()
+                .present_mode,
+            Some(wgpu::PresentMode::Immediate)
+        );
+        assert_eq!(parse(&[]).unwrap().present_mode, None);
+        assert!(parse(&["--present-mode", "vsync"]).is_err());
+        assert!(parse(&["--present-mode"]).is_err());
+    }
+
+    #[test]
+    fn test_power_preference() {
+        assert_eq!(
+            parse(&["--power", "low"]).unwrap().power_preference,
+            Some(wgpu::PowerPreference::LowPower)
+        );
+        assert_eq!(
+            parse(&["--power", "high"]).unwrap().power_preference,
+            Some(wgpu::PowerPreference::HighPerformance)
+        );
+        assert_eq!(parse(&[]).unwrap().power_preference, None);
+        assert!(parse(&["--power", "medium"]).is_err());
+        assert!(parse(&["--power"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_subcommand() {
+        let args = parse(&["verify", "--expect", "a.txt", "--actual", "b.txt"]).unwrap();
+        assert_eq!(
+            args.verify,
+            Some(VerifyArgs {
+                expect: PathBuf::from("a.txt"),
+                actual: PathBuf::from("b.txt"),
+            })
+        );
+        assert!(parse(&["verify", "--expect", "a.txt"]).is_err());
+        assert!(parse(&["verify", "--expect"]).is_err());
+        assert!(parse(&["verify", "--frame-hash", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_figure() {
+        let args = parse(&["--figure", "star.json"]).unwrap();
+        assert_eq!(args.figure, Some(PathBuf::from("star.json")));
+        assert!(parse(&["--figure"]).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{apply_rainbow, hsv_to_rgb, Figure, Mesh, Vertex};
+
+    fn assert_rgb_eq(actual: [f32; 3], expected: [f32; 3]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| (a - e).abs() < 1e-5),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_hsv_primaries() {
+        assert_rgb_eq(hsv_to_rgb(0.0, 1.0, 1.0), [1.0, 0.0, 0.0]);
+        assert_rgb_eq(hsv_to_rgb(120.0, 1.0, 1.0), [0.0, 1.0, 0.0]);
+        assert_rgb_eq(hsv_to_rgb(240.0, 1.0, 1.0), [0.0, 0.0, 1.0]);
+        assert_rgb_eq(hsv_to_rgb(60.0, 1.0, 1.0), [1.0, 1.0, 0.0]);
+        assert_rgb_eq(hsv_to_rgb(300.0, 1.0, 0.5), [0.5, 0.0, 0.5]);
+        assert_rgb_eq(hsv_to_rgb(200.0, 0.0, 0.25), [0.25; 3]);
+    }
+
+    #[test]
+    fn test_hsv_hue_wraps_around() {
+        for hue in [360.0, 720.0, -360.0] {
+            assert_rgb_eq(hsv_to_rgb(hue, 1.0, 1.0), [1.0, 0.0, 0.0]);
+        }
+        assert_rgb_eq(hsv_to_rgb(-120.0, 1.0, 1.0), hsv_to_rgb(240.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_vertex_from_hsv() {
+        for (hue, rgb) in [
+            (0.0, [1.0, 0.0, 0.0]),
+            (120.0, [0.0, 1.0, 0.0]),
+            (240.0, [0.0, 0.0, 1.0]),
+        ] {
+            let vertex = Vertex::from_hsv(hue, 1.0, 1.0, 0.25, -0.5, 1.0);
+            assert_eq!(vertex.position, [0.25, -0.5, 1.0]);
+            let [r, g, b, a] = vertex.color;
+            assert_rgb_eq([r, g, b], rgb);
+            assert_eq!(a, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_rainbow_by_angle() {
+        let mut vertices = [
+            Vertex::at(0.0, 0.0, 0.0),
+            Vertex::at(1.0, 0.0, 0.0),
+            Vertex::at(0.0, 1.0, 0.0),
+            Vertex::at(-1.0, 0.0, 0.0),
+            Vertex::at(0.0, -1.0, 0.0),
+        ];
+        vertices[1].color[3] = 0.5;
+        apply_rainbow(&mut vertices);
+
+        // The vertex at the centroid has no angle, and is red.
+        let rgb = |vertex: &Vertex| [vertex.color[0], vertex.color[1], vertex.color[2]];
+        assert_rgb_eq(rgb(&vertices[0]), [1.0, 0.0, 0.0]);
+        assert_rgb_eq(rgb(&vertices[1]), [1.0, 0.0, 0.0]);
+        assert_rgb_eq(rgb(&vertices[2]), hsv_to_rgb(90.0, 1.0, 1.0));
+        assert_rgb_eq(rgb(&vertices[3]), [0.0, 1.0, 1.0]);
+        assert_rgb_eq(rgb(&vertices[4]), hsv_to_rgb(270.0, 1.0, 1.0));
+        assert_eq!(vertices[1].color[3], 0.5);
+    }
+
+    #[test]
+    fn test_circle_color_wheel() {
+        // The rim of the circle is colored by its angle, as by
+        // `apply_rainbow`. The seam, repeating the first rim vertex, is left
+        // out so that the centroid stays at the center.
+        let circle = Figure::Circle(12).get_vertices();
+        let (seam, circle) = circle.split_last().unwrap();
+        let mut rainbow = circle.to_vec();
+        apply_rainbow(&mut rainbow);
+        for (vertex, expected) in circle.iter().zip(&rainbow).skip(1) {
+            assert_rgb_eq(
+                [vertex.color[0], vertex.color[1], vertex.color[2]],
+                [expected.color[0], expected.color[1], expected.color[2]],
+            );
+        }
+        assert_eq!(seam.color, circle[1].color);
+    }
+
+    #[test]
+    fn test_rainbow_empty() {
+        apply_rainbow(&mut []);
+    }
+}
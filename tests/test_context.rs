@@ -0,0 +1,391 @@
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+
+    use dragonfly::{
+        config::ContextConfig,
+        context::{
+            choose_sample_count, clamp_surface_size, create_pipeline_layout,
+            create_polygon_line_pipeline, create_uniform_bind_group_layout, point_in_bounds,
+            supported_sample_counts, Context,
+        },
+        snapshot,
+        vertex::{Figure, Mesh},
+    };
+    use winit::{dpi::PhysicalSize, event_loop::EventLoop, window::Window};
+
+    /// Creates a hidden window, or returns `None` if there is no display.
+    ///
+    /// The event loop is returned to keep the window alive.
+    #[allow(deprecated)]
+    fn create_test_window() -> Option<(EventLoop<()>, Arc<Window>)> {
+        let mut builder = EventLoop::builder();
+        #[cfg(target_os = "linux")]
+        {
+            use winit::platform::x11::EventLoopBuilderExtX11;
+            builder.with_any_thread(true);
+        }
+        let event_loop = builder.build().ok()?;
+        let window = event_loop
+            .create_window(Window::default_attributes().with_visible(false))
+            .ok()?;
+        Some((event_loop, Arc::new(window)))
+    }
+
+    #[test]
+    fn test_surface_size_within_limits() {
+        let size = PhysicalSize::new(1920, 1080);
+        assert_eq!(clamp_surface_size(size, 8192), size);
+        assert_eq!(clamp_surface_size(size, 1920), size);
+    }
+
+    #[test]
+    fn test_surface_size_clamped() {
+        assert_eq!(
+            clamp_surface_size(PhysicalSize::new(10000, 1080), 8192),
+            PhysicalSize::new(8192, 1080)
+        );
+        assert_eq!(
+            clamp_surface_size(PhysicalSize::new(10000, 9000), 8192),
+            PhysicalSize::new(8192, 8192)
+        );
+    }
+
+    #[test]
+    fn test_hit_test_triangle_bounds() {
+        // The triangle spans from (-0.5, -0.5) to (0.5, 0.5).
+        let bounds = Figure::Triangle.bounds();
+        assert!(point_in_bounds([0.0, 0.0], bounds));
+        assert!(point_in_bounds([-0.5, -0.5], bounds));
+        assert!(point_in_bounds([0.5, 0.5], bounds));
+        assert!(point_in_bounds([0.4, 0.4], bounds));
+
+        assert!(!point_in_bounds([0.6, 0.0], bounds));
+        assert!(!point_in_bounds([0.0, -0.6], bounds));
+        assert!(!point_in_bounds([-0.9, 0.9], bounds));
+    }
+
+    #[test]
+    fn test_present_modes_reconfigure() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the present mode test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the present mode test");
+            return;
+        };
+
+        // The supported modes are applied, the others are ignored.
+        for mode in [
+            wgpu::PresentMode::Fifo,
+            wgpu::PresentMode::Mailbox,
+            wgpu::PresentMode::Immediate,
+        ] {
+            let previous = context.config.present_mode;
+            context.set_present_mode(mode);
+            let expected = if context.present_modes.contains(&mode) {
+                mode
+            } else {
+                previous
+            };
+            assert_eq!(context.config.present_mode, expected);
+        }
+    }
+
+    #[test]
+    fn test_resize_then_render() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the resize test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the resize test");
+            return;
+        };
+
+        // The depth buffer follows the surface, or the render pass fails
+        // the validation.
+        for (width, height) in [(300, 200), (64, 480), (1, 1)] {
+            context.resize(PhysicalSize::new(width, height));
+            assert_eq!(context.depth_texture.width(), context.config.width);
+            assert_eq!(context.depth_texture.height(), context.config.height);
+            context
+                .device
+                .push_error_scope(wgpu::ErrorFilter::Validation);
+            let _ = context.render();
+            let error = pollster::block_on(context.device.pop_error_scope());
+            assert!(error.is_none(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_num_draw_calls() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the draw calls test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the draw calls test");
+            return;
+        };
+        assert_eq!(context.num_draw_calls(), 0);
+
+        // The figure alone, then with its outline.
+        assert!(context.render().is_ok());
+        assert_eq!(context.num_draw_calls(), 1);
+        context.set_outline_enabled(true);
+        assert!(context.render().is_ok());
+        assert_eq!(context.num_draw_calls(), 2);
+    }
+
+    #[test]
+    fn test_unsupported_sample_count_falls_back() {
+        let supported = [1, 2, 4, 8];
+        assert_eq!(choose_sample_count(4, &supported), 4);
+        assert_eq!(choose_sample_count(3, &supported), 2);
+        assert_eq!(choose_sample_count(64, &supported), 8);
+        assert_eq!(choose_sample_count(0, &supported), 1);
+        assert_eq!(choose_sample_count(4, &[1]), 1);
+        assert_eq!(choose_sample_count(4, &[]), 1);
+    }
+
+    #[test]
+    fn test_supported_sample_counts() {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let Some(adapter) =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+        else {
+            eprintln!("No adapter, skipping the sample counts test");
+            return;
+        };
+        let (device, _queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .unwrap();
+
+        // 4 samples are guaranteed for the usual surface formats.
+        let counts =
+            supported_sample_counts(&adapter, &device, wgpu::TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(&counts[..2], &[1, 4]);
+        assert!(counts.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_polygon_line_pipeline_without_feature() {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let options = wgpu::RequestAdapterOptions {
+            force_fallback_adapter: true,
+            ..Default::default()
+        };
+        let Some(adapter) = pollster::block_on(instance.request_adapter(&options)) else {
+            eprintln!("No fallback adapter, skipping the polygon line test");
+            return;
+        };
+        let (device, _queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .unwrap();
+
+        // The feature is not enabled on the device, so there is no pipeline.
+        let layout = create_pipeline_layout(&device, &create_uniform_bind_group_layout(&device));
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/shader.wgsl"));
+        let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        assert!(create_polygon_line_pipeline(&device, &layout, format, 1, &shader).is_none());
+
+        // With the feature, if the adapter has it, the pipeline is valid.
+        if !adapter
+            .features()
+            .contains(wgpu::Features::POLYGON_MODE_LINE)
+        {
+            return;
+        }
+        let descriptor = wgpu::DeviceDescriptor {
+            required_features: wgpu::Features::POLYGON_MODE_LINE,
+            ..Default::default()
+        };
+        let (device, _queue) =
+            pollster::block_on(adapter.request_device(&descriptor, None)).unwrap();
+        let layout = create_pipeline_layout(&device, &create_uniform_bind_group_layout(&device));
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/shader.wgsl"));
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        assert!(create_polygon_line_pipeline(&device, &layout, format, 1, &shader).is_some());
+        let error = pollster::block_on(device.pop_error_scope());
+        assert!(error.is_none(), "{:?}", error);
+    }
+
+    #[test]
+    fn test_set_sample_count() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the sample count test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the sample count test");
+            return;
+        };
+        assert!(context.sample_counts.contains(&context.sample_count));
+
+        // Every count renders, the unsupported ones with a supported count.
+        for requested in [1, 3, 4, 16, 1] {
+            let sample_count = context.set_sample_count(requested);
+            assert_eq!(sample_count, context.sample_count);
+            assert!(context.sample_counts.contains(&sample_count));
+            assert!(sample_count <= requested);
+            context
+                .device
+                .push_error_scope(wgpu::ErrorFilter::Validation);
+            let _ = context.render();
+            let error = pollster::block_on(context.device.pop_error_scope());
+            assert!(error.is_none(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_figures_drawn_from_atlas() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the atlas test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the atlas test");
+            return;
+        };
+
+        for _ in 0..10 {
+            for fig_idx in [0, 2] {
+                context.set_figure(fig_idx).unwrap();
+                assert_eq!(
+                    context.atlas_range.as_ref(),
+                    context.atlas.range(fig_idx as usize)
+                );
+                let figure = &Figure::ALL[fig_idx as usize];
+                assert_eq!(context.num_indices, figure.get_indices().len() as u32);
+            }
+        }
+
+        // A highlighted figure is copied to its own buffers to be recolored.
+        context.set_highlight(true);
+        assert_eq!(context.atlas_range, None);
+        assert_eq!(
+            context.num_indices,
+            Figure::Rectangle.get_indices().len() as u32
+        );
+    }
+
+    #[test]
+    fn test_screenshot_of_triangle() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the screenshot test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the screenshot test");
+            return;
+        };
+        context.set_mesh(&Figure::Triangle);
+        let (width, height) = (context.config.width, context.config.height);
+
+        let pixels = context.capture_frame().unwrap();
+        assert_eq!(pixels.len(), (4 * width * height) as usize);
+        let background = pixels[..4].to_vec();
+        assert!(pixels.chunks_exact(4).any(|pixel| pixel != background));
+        // Only this frame is captured.
+        assert!(!context.frame_capture);
+
+        let path = std::env::temp_dir().join("dragonfly_test_screenshot.png");
+        context.save_screenshot(&path).unwrap();
+        let png = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(png, snapshot::encode_png(width, height, &pixels));
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_image_of_triangle_round_trip() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the image test");
+            return;
+        };
+        let Ok(mut context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the image test");
+            return;
+        };
+        let image = context.render_headless_to_image(&Figure::Triangle).unwrap();
+        assert_eq!(
+            image.dimensions(),
+            (context.config.width, context.config.height)
+        );
+
+        let path = std::env::temp_dir().join("dragonfly_test_image.png");
+        image.save(&path).unwrap();
+        let reloaded = image::open(&path).unwrap().to_rgba8();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(reloaded, image);
+        // The triangle is drawn over the white background.
+        assert!(reloaded.pixels().any(|pixel| pixel.0 != [255; 4]));
+    }
+
+    #[test]
+    fn test_adapter_info_populated() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the adapter info test");
+            return;
+        };
+        let Ok(context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the adapter info test");
+            return;
+        };
+
+        assert!(!context.adapter_info().name.is_empty());
+    }
+
+    #[test]
+    fn test_low_power_context() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the low power test");
+            return;
+        };
+        // The context falls back to any adapter if none is low-power.
+        let config = ContextConfig {
+            power_preference: wgpu::PowerPreference::LowPower,
+            ..ContextConfig::default()
+        };
+        let Ok(context) = pollster::block_on(Context::new(&window, config)) else {
+            eprintln!("No compatible adapter, skipping the low power test");
+            return;
+        };
+
+        assert_eq!(context.config.present_mode, wgpu::PresentMode::Fifo);
+    }
+
+    #[test]
+    fn test_device_features_and_limits() {
+        let Some((_event_loop, window)) = create_test_window() else {
+            eprintln!("No display, skipping the device features test");
+            return;
+        };
+        let Ok(context) = pollster::block_on(Context::new(&window, ContextConfig::default()))
+        else {
+            eprintln!("No compatible adapter, skipping the device features test");
+            return;
+        };
+
+        assert!(context.device_features().contains(wgpu::Features::empty()));
+        assert!(context.supports_feature(wgpu::Features::empty()));
+        // The device is requested without extra features, so the adapter
+        // supports all of those enabled on it.
+        assert!(context.supports_feature(context.device_features()));
+        assert_eq!(
+            context.device_limits().max_texture_dimension_2d,
+            context.limits.max_texture_dimension_2d
+        );
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{cell::RefCell, io::Cursor};
+
+    use dragonfly::control::{self, Command, CommandSink, Response};
+
+    /// A sink collecting the commands it receives.
+    #[derive(Default)]
+    struct FakeSink {
+        commands: RefCell<Vec<Command>>,
+        capacity: Option<usize>,
+    }
+
+    impl CommandSink for FakeSink {
+        fn send(&self, command: Command) -> bool {
+            let mut commands = self.commands.borrow_mut();
+            if self
+                .capacity
+                .is_some_and(|capacity| commands.len() >= capacity)
+            {
+                return false;
+            }
+            commands.push(command);
+            true
+        }
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            control::parse_command(r#"{"command": "next_figure"}"#),
+            Ok(Command::NextFigure)
+        );
+        assert_eq!(
+            control::parse_command(r#"{"command": "set_figure", "index": 3}"#),
+            Ok(Command::SetFigure { index: 3 })
+        );
+        assert_eq!(
+            control::parse_command(r#"{"command": "quit"}"#),
+            Ok(Command::Quit)
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_command() {
+        let result = control::parse_command(r#"{"command": "dance"}"#);
+        assert!(matches!(result, Err(Response::Error { .. })));
+    }
+
+    #[test]
+    fn test_parse_malformed_command() {
+        let result = control::parse_command(r#"{"command": "set_figure", "index": -1}"#);
+        assert!(matches!(result, Err(Response::Error { .. })));
+
+        let result = control::parse_command("not json");
+        assert!(matches!(result, Err(Response::Error { .. })));
+    }
+
+    #[test]
+    fn test_command_round_trip() {
+        for command in [
+            Command::NextFigure,
+            Command::SetFigure { index: 5 },
+            Command::Quit,
+        ] {
+            let json = serde_json::to_string(&command).unwrap();
+            assert!(json.contains(command.name()));
+            assert_eq!(control::parse_command(&json), Ok(command));
+        }
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let response = Response::Ok {
+            command: "quit".to_string(),
+        };
+        assert_eq!(response.to_json(), r#"{"status":"ok","command":"quit"}"#);
+    }
+
+    #[test]
+    fn test_listen_dispatches_to_sink() {
+        let input = Cursor::new(
+            "{\"command\": \"next_figure\"}\n\n{\"command\": \"dance\"}\n{\"command\": \"quit\"}\n",
+        );
+        let sink = FakeSink::default();
+        let mut output = Vec::new();
+
+        control::listen(input, &sink, &mut output).unwrap();
+
+        assert_eq!(
+            *sink.commands.borrow(),
+            vec![Command::NextFigure, Command::Quit]
+        );
+
+        // Only the unknown command is answered by the listener.
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with(r#"{"status":"error""#));
+    }
+
+    #[test]
+    fn test_listen_stops_when_sink_closed() {
+        let input = Cursor::new("{\"command\": \"next_figure\"}\n{\"command\": \"quit\"}\n");
+        let sink = FakeSink {
+            capacity: Some(1),
+            ..Default::default()
+        };
+
+        control::listen(input, &sink, Vec::new()).unwrap();
+
+        assert_eq!(*sink.commands.borrow(), vec![Command::NextFigure]);
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+    };
+
+    use dragonfly::{
+        control::{Command, CommandSink},
+        dialog::{self, DialogKind, DialogOutcome, FileFormat},
+        dragonfly::Dragonfly,
+        vertex::Figure,
+    };
+
+    const FORMATS: &[FileFormat] = &[
+        FileFormat {
+            name: "Wavefront OBJ",
+            extensions: &["obj"],
+        },
+        FileFormat {
+            name: "Stereolithography",
+            extensions: &["stl", "stla"],
+        },
+    ];
+
+    /// A sink collecting the commands sent from the dialog thread.
+    #[derive(Default, Clone)]
+    struct SharedSink {
+        commands: Arc<Mutex<Vec<Command>>>,
+    }
+
+    impl CommandSink for SharedSink {
+        fn send(&self, command: Command) -> bool {
+            self.commands.lock().unwrap().push(command);
+            true
+        }
+    }
+
+    #[test]
+    fn test_file_filters_per_format() {
+        let filters = dialog::file_filters(FORMATS, false);
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].name, "Wavefront OBJ");
+        assert_eq!(filters[0].extensions, vec!["obj"]);
+        assert_eq!(filters[1].extensions, vec!["stl", "stla"]);
+    }
+
+    #[test]
+    fn test_file_filters_all_supported() {
+        let filters = dialog::file_filters(FORMATS, true);
+        assert_eq!(filters.len(), 3);
+        assert_eq!(filters[0].extensions, vec!["obj", "stl", "stla"]);
+
+        // A single format needs no combined filter.
+        assert_eq!(dialog::file_filters(&FORMATS[..1], true).len(), 1);
+        assert!(dialog::file_filters(&[], true).is_empty());
+    }
+
+    #[test]
+    fn test_format_of_path() {
+        assert_eq!(
+            dialog::format_of(FORMATS, Path::new("model.STL")),
+            Some(&FORMATS[1])
+        );
+        assert_eq!(dialog::format_of(FORMATS, Path::new("model.ply")), None);
+        assert_eq!(dialog::format_of(FORMATS, Path::new("model")), None);
+    }
+
+    #[test]
+    fn test_cancelled_dialog_is_a_no_op() {
+        for kind in [
+            DialogKind::Open,
+            DialogKind::SaveScene,
+            DialogKind::ExportMesh,
+        ] {
+            assert_eq!(
+                dialog::resolve_outcome(kind, DialogOutcome::Cancelled),
+                Ok(None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_failed_and_unsupported_outcomes() {
+        assert_eq!(
+            dialog::resolve_outcome(
+                DialogKind::Open,
+                DialogOutcome::Failed("No portal".to_string())
+            ),
+            Err("No portal".to_string())
+        );
+        assert!(dialog::resolve_outcome(
+            DialogKind::ExportMesh,
+            DialogOutcome::Picked(PathBuf::from("mesh.xyz"))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_dialog_outcome_delivered_to_sink() {
+        let sink = SharedSink::default();
+        let path = PathBuf::from("scene.json");
+
+        let picked = path.clone();
+        dialog::spawn_dialog(DialogKind::SaveScene, sink.clone(), move |_| {
+            DialogOutcome::Picked(picked)
+        })
+        .join()
+        .unwrap();
+        dialog::spawn_dialog(DialogKind::Open, sink.clone(), |_| DialogOutcome::Cancelled)
+            .join()
+            .unwrap();
+
+        assert_eq!(
+            *sink.commands.lock().unwrap(),
+            vec![
+                Command::DialogClosed {
+                    kind: DialogKind::SaveScene,
+                    outcome: DialogOutcome::Picked(path),
+                },
+                Command::DialogClosed {
+                    kind: DialogKind::Open,
+                    outcome: DialogOutcome::Cancelled,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dialog_closed_cannot_be_parsed() {
+        assert!(dragonfly::control::parse_command(r#"{"command": "dialog_closed"}"#).is_err());
+    }
+
+    #[test]
+    fn test_registered_formats() {
+        for (path, format) in [
+            ("model.obj", dialog::OBJ),
+            ("model.STL", dialog::STL),
+            ("model.ply", dialog::PLY),
+            ("figure.json", dialog::JSON),
+        ] {
+            assert_eq!(
+                dialog::resolve_outcome(DialogKind::Open, DialogOutcome::Picked(path.into())),
+                Ok(Some((PathBuf::from(path), format)))
+            );
+        }
+        assert_eq!(DialogKind::SaveScene.formats(), &[dialog::JSON]);
+        assert_eq!(
+            DialogKind::ExportMesh.formats(),
+            &[dialog::SVG, dialog::JSON]
+        );
+        assert_eq!(DialogKind::Open.filters()[0].name, "All supported files");
+    }
+
+    #[test]
+    fn test_picked_obj_is_opened() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/house.obj");
+        let mut app = Dragonfly::default();
+        app.dialog_closed(DialogKind::Open, DialogOutcome::Picked(path.clone()));
+
+        let expected = Figure::from_obj(&path).unwrap();
+        let mesh = app.mesh().expect("The OBJ file is not opened");
+        assert_eq!(mesh.vertices.len(), expected.vertices.len());
+        assert_eq!(mesh.indices, expected.indices);
+        assert_eq!(app.title(), "Dragonfly");
+    }
+
+    #[test]
+    fn test_unreadable_pick_leaves_figure() {
+        let mut app = Dragonfly::default();
+        app.dialog_closed(
+            DialogKind::Open,
+            DialogOutcome::Picked(PathBuf::from("missing/x.obj")),
+        );
+        app.dialog_closed(DialogKind::Open, DialogOutcome::Cancelled);
+        assert!(app.mesh().is_none());
+    }
+
+    #[test]
+    fn test_export_figure_to_svg() {
+        let path = std::env::temp_dir().join("dragonfly_test_export.svg");
+        let _ = std::fs::remove_file(&path);
+
+        let mut app = Dragonfly::default();
+        app.dialog_closed(DialogKind::ExportMesh, DialogOutcome::Picked(path.clone()));
+
+        let svg = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(svg, Figure::Triangle.to_svg(dialog::SVG_SIZE_PX).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_saved_scene_is_opened() {
+        use dragonfly::vertex::Mesh;
+
+        let path = std::env::temp_dir().join("dragonfly_test_scene.json");
+        let mut app = Dragonfly::default();
+        app.dialog_closed(DialogKind::SaveScene, DialogOutcome::Picked(path.clone()));
+        app.dialog_closed(DialogKind::Open, DialogOutcome::Picked(path.clone()));
+
+        let mesh = app.mesh().expect("The scene is not opened");
+        assert_eq!(mesh.indices, Figure::Triangle.get_indices());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use dragonfly::{
+        config::ContextConfig,
+        dragonfly::{spin_angle, Dragonfly, FpsCounter, SPIN_SPEED},
+    };
+
+    #[test]
+    fn test_spin_angle_wraps_around() {
+        use std::f32::consts::TAU;
+
+        assert_eq!(spin_angle(0.0), 0.0);
+        assert!((spin_angle(1.0) - SPIN_SPEED).abs() < 1e-6);
+        // A full turn comes back to the start.
+        let turn = TAU / SPIN_SPEED;
+        assert!((spin_angle(turn + 1.0) - SPIN_SPEED).abs() < 1e-4);
+        for elapsed in [0.5, 3.0, 100.0, 1e4] {
+            assert!((0.0..TAU).contains(&spin_angle(elapsed)));
+        }
+    }
+
+    #[test]
+    fn test_default_frame_rate_unlimited() {
+        let app = Dragonfly::default();
+        assert_eq!(app.frame_duration(), None);
+    }
+
+    #[test]
+    fn test_target_fps_frame_duration() {
+        let app = Dragonfly::default().with_target_fps(60);
+        let frame_duration = app.frame_duration().unwrap();
+        assert!((frame_duration.as_secs_f64() * 1000.0 - 16.67).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_target_fps_zero_unlimited() {
+        let app = Dragonfly::default().with_target_fps(0);
+        assert_eq!(app.frame_duration(), None);
+    }
+
+    #[test]
+    fn test_fps_counter_gated() {
+        assert!(Dragonfly::default().fps_counter().is_none());
+        assert!(Dragonfly::default()
+            .with_fps_counter(true)
+            .fps_counter()
+            .is_some());
+        assert!(Dragonfly::default()
+            .with_fps_counter(false)
+            .fps_counter()
+            .is_none());
+    }
+
+    #[test]
+    fn test_fps_counter_saturates() {
+        let mut fps_counter = FpsCounter::default();
+        for _ in 0..100 {
+            fps_counter.record(Duration::from_millis(10));
+        }
+        assert_eq!(fps_counter.len(), 60);
+    }
+
+    #[test]
+    fn test_fps_counter_average() {
+        let mut fps_counter = FpsCounter::default();
+        assert_eq!(fps_counter.fps(), None);
+
+        // Older samples are dropped from the average.
+        for _ in 0..10 {
+            fps_counter.record(Duration::from_millis(100));
+        }
+        for _ in 0..60 {
+            fps_counter.record(Duration::from_millis(20));
+        }
+        assert!((fps_counter.fps().unwrap() - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_initial_background_in_state() {
+        let app = Dragonfly::default().with_background(wgpu::Color::BLACK);
+        assert_eq!(app.state().background, Some(wgpu::Color::BLACK));
+        assert_eq!(app.state().fig_idx(), 0);
+        assert_eq!(app.view_state(), "figure=0 background=0,0,0,1");
+    }
+
+    #[test]
+    fn test_stats_gated() {
+        assert!(!Dragonfly::default().stats_enabled());
+        assert!(Dragonfly::default().with_stats(true).stats_enabled());
+    }
+
+    #[test]
+    fn test_context_config() {
+        assert_eq!(
+            *Dragonfly::default().context_config(),
+            ContextConfig::default()
+        );
+
+        let app = Dragonfly::default()
+            .with_power_preference(wgpu::PowerPreference::LowPower)
+            .with_present_mode(wgpu::PresentMode::Mailbox);
+        assert_eq!(
+            app.context_config().power_preference,
+            wgpu::PowerPreference::LowPower
+        );
+        assert_eq!(
+            app.context_config().present_mode,
+            wgpu::PresentMode::Mailbox
+        );
+        assert_eq!(app.context_config().msaa_samples, 4);
+        assert_eq!(app.with_msaa_samples(1).context_config().msaa_samples, 1);
+    }
+
+    #[test]
+    fn test_title_names_figure() {
+        assert_eq!(Dragonfly::default().title(), "Dragonfly — triangle");
+
+        let mesh = dragonfly::vertex::Figure::Pyramid;
+        assert_eq!(Dragonfly::default().with_mesh(&mesh).title(), "Dragonfly");
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+
+    use std::error::Error;
+
+    use dragonfly::error::DragonflyError;
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            DragonflyError::SurfaceCreation("no display".to_string()).to_string(),
+            "Failed to create the window surface: no display"
+        );
+        assert_eq!(
+            DragonflyError::AdapterRequest.to_string(),
+            "No compatible graphics adapter was found"
+        );
+        assert_eq!(
+            DragonflyError::SurfaceConfiguration.to_string(),
+            "The window surface supports no texture format"
+        );
+    }
+
+    #[test]
+    fn test_surface_error_conversion() {
+        let error = DragonflyError::from(wgpu::SurfaceError::Timeout);
+        assert!(matches!(
+            error,
+            DragonflyError::RenderError(wgpu::SurfaceError::Timeout)
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("Failed to render the frame: "));
+        assert!(error.source().is_some());
+        assert!(DragonflyError::AdapterRequest.source().is_none());
+    }
+
+    #[test]
+    fn test_shader_error() {
+        let error = DragonflyError::Shader(wgpu::Error::OutOfMemory {
+            source: Box::new(std::fmt::Error),
+        });
+        assert!(error
+            .to_string()
+            .starts_with("Failed to compile the shader: "));
+        assert!(error.source().is_some());
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+
+    use std::process::Command;
+
+    /// The examples built on the public API.
+    const EXAMPLES: &[&str] = &["minimal", "headless_png", "custom_mesh", "scene"];
+
+    #[test]
+    fn test_examples_compile() {
+        let mut command = Command::new(env!("CARGO"));
+        command
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(["build", "--quiet", "--offline"]);
+        for example in EXAMPLES {
+            command.args(["--example", example]);
+        }
+
+        let output = command.output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+
+    use std::process::Command;
+
+    use dragonfly::frame_hash;
+
+    const WIDTH: u32 = 50;
+    const HEIGHT: u32 = 20;
+
+    fn create_test_device() -> (wgpu::Device, wgpu::Queue) {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .unwrap();
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+            .unwrap()
+    }
+
+    /// Renders a frame cleared with the given color to an off-screen texture
+    /// and reads it back.
+    fn render_headless(color: wgpu::Color) -> Vec<u8> {
+        let (device, queue) = create_test_device();
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: WIDTH,
+                height: HEIGHT,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        queue.submit(std::iter::once(encoder.finish()));
+
+        frame_hash::read_texture(&device, &queue, &texture)
+    }
+
+    #[test]
+    fn test_strip_row_padding() {
+        // 50 pixels of 4 bytes are padded to 256 bytes per row.
+        assert_eq!(frame_hash::padded_bytes_per_row(WIDTH), 256);
+        assert_eq!(frame_hash::padded_bytes_per_row(64), 256);
+
+        let mut data = vec![0u8; 256 * 2];
+        data[..200].fill(1);
+        data[256..456].fill(2);
+        let pixels = frame_hash::strip_row_padding(&data, WIDTH, 2);
+        assert_eq!(pixels.len(), 400);
+        assert!(pixels[..200].iter().all(|&b| b == 1));
+        assert!(pixels[200..].iter().all(|&b| b == 2));
+    }
+
+    #[test]
+    fn test_headless_hash_stable() {
+        let first = render_headless(wgpu::Color::BLUE);
+        let second = render_headless(wgpu::Color::BLUE);
+        assert_eq!(first.len(), (WIDTH * HEIGHT * 4) as usize);
+        assert_eq!(&first[..4], &[0, 0, 255, 255]);
+        assert_eq!(
+            frame_hash::hash_pixels(&first),
+            frame_hash::hash_pixels(&second)
+        );
+    }
+
+    #[test]
+    fn test_hash_sensitive_to_single_pixel() {
+        let mut pixels = render_headless(wgpu::Color::WHITE);
+        let hash = frame_hash::hash_pixels(&pixels);
+        pixels[4 * 123] -= 1;
+        assert_ne!(frame_hash::hash_pixels(&pixels), hash);
+    }
+
+    #[test]
+    fn test_hash_line_format() {
+        assert_eq!(
+            frame_hash::format_hash_line(3, 0xabc, "figure=1"),
+            "3 0000000000000abc figure=1"
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let expected = "0 00000000000000aa figure=0\n1 00000000000000bb figure=0\n";
+        assert_eq!(frame_hash::verify(expected, expected), Ok(2));
+        assert!(frame_hash::verify(expected, "0 00000000000000aa figure=0\n").is_err());
+        assert!(frame_hash::verify(
+            expected,
+            "0 00000000000000aa figure=0\n1 00000000000000bc figure=0\n"
+        )
+        .unwrap_err()
+        .starts_with("Frame 1 differs"));
+    }
+
+    #[test]
+    fn test_verify_exit_codes() {
+        let dir = std::env::temp_dir();
+        let expect = dir.join("dragonfly_test_expect.txt");
+        let same = dir.join("dragonfly_test_same.txt");
+        let different = dir.join("dragonfly_test_different.txt");
+        std::fs::write(&expect, "0 00000000000000aa figure=0\n").unwrap();
+        std::fs::write(&same, "0 00000000000000aa figure=0\n").unwrap();
+        std::fs::write(&different, "0 00000000000000ab figure=0\n").unwrap();
+
+        let verify = |actual: &std::path::Path| {
+            Command::new(env!("CARGO_BIN_EXE_dragonfly"))
+                .args(["verify", "--expect"])
+                .arg(&expect)
+                .arg("--actual")
+                .arg(actual)
+                .output()
+                .unwrap()
+                .status
+                .code()
+        };
+        assert_eq!(verify(&same), Some(0));
+        assert_eq!(verify(&different), Some(1));
+        assert_eq!(verify(&dir.join("dragonfly_test_missing.txt")), Some(2));
+
+        for path in [expect, same, different] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::stats::{
+        FrameStats, FrameStatsHistory, GpuTimer, StatsSummary, FRAME_STATS_CAPACITY,
+    };
+
+    fn frame(draw_calls: u32, frame_ms: f32, gpu_ms: Option<f32>) -> FrameStats {
+        FrameStats {
+            draw_calls,
+            total_vertices: 3 * draw_calls,
+            total_indices: 6 * draw_calls,
+            frame_ms,
+            gpu_ms,
+        }
+    }
+
+    #[test]
+    fn test_history_capped() {
+        let mut history = FrameStatsHistory::default();
+        for i in 0..200 {
+            history.push(frame(1, i as f32, None));
+            assert!(history.len() <= FRAME_STATS_CAPACITY);
+        }
+        assert_eq!(history.len(), FRAME_STATS_CAPACITY);
+
+        // The oldest frames are dropped.
+        assert_eq!(history.last().unwrap().frame_ms, 199.0);
+        assert_eq!(history.summary().min.frame_ms, 80.0);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut history = FrameStatsHistory::default();
+        assert_eq!(history.summary(), StatsSummary::default());
+
+        history.push(frame(1, 10.0, None));
+        history.push(frame(2, 20.0, Some(4.0)));
+        history.push(frame(4, 30.0, Some(2.0)));
+        let summary = history.summary();
+        assert_eq!(summary.frames, 3);
+        assert_eq!(summary.min.draw_calls, 1);
+        assert_eq!(summary.max.draw_calls, 4);
+        assert_eq!(summary.avg.draw_calls, 2);
+        assert_eq!(summary.max.total_indices, 24);
+        assert_eq!(
+            (
+                summary.min.frame_ms,
+                summary.avg.frame_ms,
+                summary.max.frame_ms
+            ),
+            (10.0, 20.0, 30.0)
+        );
+
+        // The frames without GPU time are left out of its summary.
+        assert_eq!(
+            (summary.min.gpu_ms, summary.avg.gpu_ms, summary.max.gpu_ms),
+            (Some(2.0), Some(3.0), Some(4.0))
+        );
+        assert_eq!(
+            summary.to_string(),
+            "3 frames: 20.00 ms (min 10.00, max 30.00), GPU 3.00 ms (min 2.00, max 4.00), \
+             2 draw calls, 7 vertices, 14 indices"
+        );
+    }
+
+    #[test]
+    fn test_gpu_timer() {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .unwrap();
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                required_features: adapter.features() & wgpu::Features::TIMESTAMP_QUERY,
+                ..Default::default()
+            },
+            None,
+        ))
+        .unwrap();
+        let Some(timer) = GpuTimer::new(&device, &queue) else {
+            eprintln!("No timestamp queries, skipping the GPU timer test");
+            return;
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: Some(timer.timestamp_writes()),
+        });
+        timer.resolve(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let gpu_ms = timer.read_ms(&device).unwrap();
+        assert!(gpu_ms.is_finite() && gpu_ms >= 0.0, "{}", gpu_ms);
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{apply_gradient, Figure, GradientDirection, Mesh, Vertex};
+
+    const FROM: [f32; 3] = [0.1, 0.2, 0.3];
+    const TO: [f32; 3] = [0.9, 0.7, 0.2];
+
+    fn rgb(vertex: &Vertex) -> [f32; 3] {
+        [vertex.color[0], vertex.color[1], vertex.color[2]]
+    }
+
+    #[test]
+    fn test_gradient_extremes() {
+        for (direction, axis) in [
+            (GradientDirection::Horizontal, 0),
+            (GradientDirection::Vertical, 1),
+        ] {
+            let mut vertices = Figure::Pentagon.get_vertices();
+            apply_gradient(&mut vertices, FROM, TO, direction);
+
+            let lowest = vertices
+                .iter()
+                .min_by(|a, b| a.position[axis].total_cmp(&b.position[axis]))
+                .unwrap();
+            let highest = vertices
+                .iter()
+                .max_by(|a, b| a.position[axis].total_cmp(&b.position[axis]))
+                .unwrap();
+            assert_eq!(rgb(lowest), FROM, "{:?}", direction);
+            assert_eq!(rgb(highest), TO, "{:?}", direction);
+        }
+    }
+
+    #[test]
+    fn test_gradient_radial() {
+        // The center vertex of the circle is the nearest to the center.
+        let mut vertices = Figure::Circle(16).get_vertices();
+        apply_gradient(&mut vertices, FROM, TO, GradientDirection::Radial);
+        let center = vertices
+            .iter()
+            .find(|vertex| vertex.position == [0.0, 0.0, 0.0])
+            .unwrap();
+        assert_eq!(rgb(center), FROM);
+        let edge = vertices
+            .iter()
+            .find(|vertex| vertex.position == [0.5, 0.0, 0.0])
+            .unwrap();
+        assert_eq!(rgb(edge), TO);
+    }
+
+    #[test]
+    fn test_gradient_shared_positions() {
+        // Each corner of the pentagon twice, with different colors.
+        let pentagon = Figure::Pentagon.get_vertices();
+        let recolored = pentagon
+            .iter()
+            .map(|vertex| vertex.with_color(0.0, 0.0, 0.0));
+        let mut vertices: Vec<Vertex> = pentagon.iter().copied().chain(recolored).collect();
+        for direction in [
+            GradientDirection::Horizontal,
+            GradientDirection::Vertical,
+            GradientDirection::Radial,
+        ] {
+            apply_gradient(&mut vertices, FROM, TO, direction);
+            let (first, second) = vertices.split_at(pentagon.len());
+            for (a, b) in first.iter().zip(second) {
+                assert_eq!(a.color, b.color, "{:?}", direction);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_keeps_alpha_and_flat_axis() {
+        let mut vertices = Figure::Overlap.get_vertices();
+        let alphas: Vec<f32> = vertices.iter().map(|vertex| vertex.color[3]).collect();
+        apply_gradient(&mut vertices, FROM, TO, GradientDirection::Horizontal);
+        assert!(vertices
+            .iter()
+            .zip(alphas)
+            .all(|(vertex, alpha)| vertex.color[3] == alpha));
+
+        // The flat figures do not extend along Z, so a single vertex gets the
+        // middle color.
+        let mut vertices = [Vertex::at(0.2, 0.3, 0.0)];
+        apply_gradient(
+            &mut vertices,
+            [0.0; 3],
+            [1.0; 3],
+            GradientDirection::Vertical,
+        );
+        assert_eq!(rgb(&vertices[0]), [0.5; 3]);
+    }
+}
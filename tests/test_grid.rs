@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::grid::Grid;
+
+    fn grid(spacing: f32, subdivisions: u32) -> Grid {
+        Grid {
+            spacing,
+            line_color: [0.2, 0.4, 0.6, 0.5],
+            subdivisions,
+        }
+    }
+
+    #[test]
+    fn test_lines_cover_viewport() {
+        // 5 vertical lines 100 pixels apart across 400 pixels, and 3
+        // horizontal lines across 300 pixels.
+        let (vertices, indices) = grid(100.0, 1).generate_mesh(400.0, 300.0);
+        assert_eq!(vertices.len(), 2 * (5 + 3));
+        assert_eq!(indices, (0..16).collect::<Vec<u16>>());
+
+        let xs: Vec<f32> = vertices[..10]
+            .iter()
+            .step_by(2)
+            .map(|v| v.position[0])
+            .collect();
+        assert_eq!(xs, vec![-1.0, -0.5, 0.0, 0.5, 1.0]);
+        for line in vertices[..10].chunks(2) {
+            assert_eq!(line[0].position[1], -1.0);
+            assert_eq!(line[1].position[1], 1.0);
+        }
+
+        let ys: Vec<f32> = vertices[10..]
+            .iter()
+            .step_by(2)
+            .map(|v| v.position[1])
+            .collect();
+        let third = 100.0 / 150.0;
+        assert_eq!(ys, vec![-third, 0.0, third]);
+        assert!(vertices.iter().all(|v| v.color == [0.2, 0.4, 0.6, 0.5]));
+    }
+
+    #[test]
+    fn test_subdivisions_add_lines() {
+        let (main, _) = grid(100.0, 1).generate_mesh(400.0, 400.0);
+        let (subdivided, _) = grid(100.0, 4).generate_mesh(400.0, 400.0);
+        assert_eq!(main.len(), 2 * 2 * 5);
+        assert_eq!(subdivided.len(), 2 * 2 * 17);
+
+        // No subdivision is the same as one.
+        assert_eq!(
+            grid(100.0, 0).generate_mesh(400.0, 400.0).0.len(),
+            main.len()
+        );
+    }
+
+    #[test]
+    fn test_mesh_regenerated_for_viewport() {
+        let grid = Grid::default();
+        let (small, _) = grid.generate_mesh(200.0, 200.0);
+        let (large, _) = grid.generate_mesh(2000.0, 1000.0);
+        assert!(large.len() > small.len());
+        assert!(grid.generate_mesh(0.0, 0.0).0.is_empty());
+    }
+
+    #[test]
+    fn test_dense_grid_fits_u16_indices() {
+        let (vertices, indices) = grid(0.01, 10).generate_mesh(8192.0, 8192.0);
+        assert!(vertices.len() <= u16::MAX as usize + 1);
+        assert_eq!(indices.len(), vertices.len());
+    }
+}
@@ -0,0 +1,487 @@
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use dragonfly::{
+        camera::ZOOM_STEP,
+        dragonfly::{
+            BACKGROUND_PRESETS, CULL_MODES, GRADIENT_PRESETS, MORPH_DURATION, TRANSITION_DURATION,
+        },
+        input::{InputEvent, Key},
+        state::{AppEvent, AppState},
+        testing::{self, events, Harness},
+        vertex::Figure,
+    };
+    use winit::{event::WindowEvent, keyboard::KeyCode};
+
+    #[test]
+    fn test_sequence_builder() {
+        let inputs = events()
+            .resize(800, 600)
+            .key(Key::Space)
+            .wait_ms(50)
+            .key(Key::Digit(3))
+            .redraw()
+            .build();
+        assert_eq!(
+            inputs,
+            vec![
+                InputEvent::Resized {
+                    width: 800,
+                    height: 600
+                },
+                InputEvent::KeyPressed(Key::Space),
+                InputEvent::KeyReleased(Key::Space),
+                InputEvent::Wait(Duration::from_millis(50)),
+                InputEvent::KeyPressed(Key::Digit(3)),
+                InputEvent::KeyReleased(Key::Digit(3)),
+                InputEvent::Redraw,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_space_cycles_figures() {
+        let harness = testing::run(events().key(Key::Space).key(Key::Space));
+        assert_eq!(harness.state.fig_idx(), 2);
+        assert_eq!(
+            harness.events,
+            vec![
+                AppEvent::RedrawRequested,
+                AppEvent::FigureChanged(1),
+                AppEvent::RedrawRequested,
+                AppEvent::RedrawRequested,
+                AppEvent::FigureChanged(2),
+                AppEvent::RedrawRequested,
+            ]
+        );
+
+        // The last figure wraps around to the first one.
+        let mut sequence = events();
+        for _ in 0..Figure::COUNT {
+            sequence = sequence.key(Key::Space);
+        }
+        assert_eq!(testing::run(sequence).state.fig_idx(), 0);
+    }
+
+    #[test]
+    fn test_shift_space_previous_figure() {
+        // The first figure wraps around to the last one.
+        let harness = testing::run(events().shift_key(Key::Space));
+        assert_eq!(harness.state.fig_idx(), Figure::COUNT - 1);
+
+        let harness = testing::run(
+            events()
+                .key(Key::Space)
+                .key(Key::Space)
+                .shift_key(Key::Space),
+        );
+        assert_eq!(harness.state.fig_idx(), 1);
+    }
+
+    #[test]
+    fn test_background_presets_wrap_around() {
+        let mut harness = Harness::default();
+        assert_eq!(harness.state.bg_idx, 0);
+
+        for (i, preset) in BACKGROUND_PRESETS.iter().enumerate().skip(1) {
+            harness.clear_events().run(events().key(Key::C));
+            assert_eq!(harness.state.bg_idx, i);
+            assert!(harness
+                .events
+                .contains(&AppEvent::BackgroundChanged(*preset)));
+        }
+
+        // The last preset wraps around to the first one.
+        harness.run(events().key(Key::C));
+        assert_eq!(harness.state.bg_idx, 0);
+        assert_eq!(harness.state.background, Some(BACKGROUND_PRESETS[0]));
+    }
+
+    #[test]
+    fn test_shift_g_cycles_gradients() {
+        let mut harness = Harness::default();
+        assert_eq!(harness.state.gradient(), None);
+
+        for preset in GRADIENT_PRESETS {
+            harness.clear_events().run(events().shift_key(Key::G));
+            assert_eq!(harness.state.gradient(), Some(*preset));
+            assert!(harness
+                .events
+                .contains(&AppEvent::GradientChanged(Some(*preset))));
+        }
+        assert!(!harness.state.grid);
+
+        // The last preset wraps around to the own colors of the figures.
+        harness.clear_events().run(events().shift_key(Key::G));
+        assert_eq!(harness.state.gradient_idx, 0);
+        assert!(harness.events.contains(&AppEvent::GradientChanged(None)));
+    }
+
+    #[test]
+    fn test_shift_c_cycles_cull_modes() {
+        let mut harness = Harness::default();
+        assert_eq!(CULL_MODES[harness.state.cull_idx], Some(wgpu::Face::Back));
+
+        for (i, cull_mode) in CULL_MODES.iter().enumerate().skip(1) {
+            harness.clear_events().run(events().shift_key(Key::C));
+            assert_eq!(harness.state.cull_idx, i);
+            assert!(harness
+                .events
+                .contains(&AppEvent::CullModeChanged(*cull_mode)));
+        }
+        assert_eq!(harness.state.bg_idx, 0);
+
+        // The last mode wraps around to the back faces.
+        harness.clear_events().run(events().shift_key(Key::C));
+        assert_eq!(harness.state.cull_idx, 0);
+        assert!(harness
+            .events
+            .contains(&AppEvent::CullModeChanged(Some(wgpu::Face::Back))));
+    }
+
+    #[test]
+    fn test_g_toggles_grid() {
+        let mut harness = testing::run(events().key(Key::G));
+        assert!(harness.state.grid);
+        assert!(harness.events.contains(&AppEvent::GridToggled(true)));
+
+        harness.clear_events().run(events().key(Key::G));
+        assert!(!harness.state.grid);
+        assert!(harness.events.contains(&AppEvent::GridToggled(false)));
+    }
+
+    #[test]
+    fn test_click_at_cursor_in_ndc() {
+        let harness = testing::run(events().resize(800, 600).cursor(600.0, 150.0).click());
+        assert_eq!(harness.state.cursor_pos, [0.5, 0.5]);
+        assert!(!harness.state.mouse_pressed);
+        assert!(harness.events.contains(&AppEvent::Clicked([0.5, 0.5])));
+
+        let harness = testing::run(events().resize(800, 600).cursor(0.0, 600.0));
+        assert_eq!(harness.state.cursor_pos, [-1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_w_toggles_wireframe() {
+        let harness = testing::run(events().key(Key::W));
+        assert!(harness.state.wireframe);
+        assert!(harness.events.contains(&AppEvent::WireframeToggled(true)));
+
+        let harness = testing::run(events().key(Key::W).key(Key::W));
+        assert!(!harness.state.wireframe);
+    }
+
+    #[test]
+    fn test_scroll_zooms() {
+        let harness = testing::run(events().scroll(1.0).scroll(-3.0).scroll(0.0));
+        assert_eq!(
+            harness.events,
+            vec![
+                AppEvent::ZoomRequested {
+                    factor: ZOOM_STEP,
+                    at: [0.0, 0.0],
+                },
+                AppEvent::RedrawRequested,
+                AppEvent::ZoomRequested {
+                    factor: 1.0 / ZOOM_STEP,
+                    at: [0.0, 0.0],
+                },
+                AppEvent::RedrawRequested,
+            ]
+        );
+
+        // The zoom is centered on the cursor.
+        let harness = testing::run(events().resize(800, 600).cursor(600.0, 150.0).scroll(1.0));
+        assert!(harness.events.contains(&AppEvent::ZoomRequested {
+            factor: ZOOM_STEP,
+            at: [0.5, 0.5],
+        }));
+    }
+
+    #[test]
+    fn test_right_drag_pans() {
+        let harness = testing::run(
+            events()
+                .resize(800, 600)
+                .drag([400.0, 300.0], [600.0, 150.0]),
+        );
+        assert!(!harness.state.panning);
+        assert_eq!(
+            harness.events[2..],
+            [AppEvent::Dragged([0.5, 0.5]), AppEvent::RedrawRequested]
+        );
+
+        // Moving the cursor without a button held does not pan.
+        let harness = testing::run(events().resize(800, 600).cursor(600.0, 150.0));
+        assert!(!harness
+            .events
+            .iter()
+            .any(|event| matches!(event, AppEvent::Dragged(_))));
+    }
+
+    #[test]
+    fn test_held_arrows_pan_direction() {
+        let press = |keys: &[Key]| {
+            let mut harness = Harness::default();
+            for &key in keys {
+                harness.state.handle(&InputEvent::KeyPressed(key));
+            }
+            harness
+        };
+
+        assert_eq!(press(&[]).state.pan_direction(), [0.0, 0.0]);
+        assert_eq!(press(&[Key::ArrowRight]).state.pan_direction(), [1.0, 0.0]);
+        assert_eq!(
+            press(&[Key::ArrowLeft, Key::ArrowUp]).state.pan_direction(),
+            [-1.0, 1.0]
+        );
+        assert_eq!(
+            press(&[Key::ArrowUp, Key::ArrowDown]).state.pan_direction(),
+            [0.0, 0.0]
+        );
+
+        // Releasing a key, or losing the focus, stops the panning.
+        let mut harness = press(&[Key::ArrowDown, Key::ArrowLeft]);
+        harness
+            .state
+            .handle(&InputEvent::KeyReleased(Key::ArrowLeft));
+        assert_eq!(harness.state.pan_direction(), [0.0, -1.0]);
+        harness.state.handle(&InputEvent::FocusLost);
+        assert!(harness.state.held_keys.is_empty());
+        assert_eq!(harness.state.pan_direction(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_f_fits_figure() {
+        let harness = testing::run(events().key(Key::F));
+        assert_eq!(
+            harness.events,
+            vec![AppEvent::FitRequested, AppEvent::RedrawRequested]
+        );
+    }
+
+    #[test]
+    fn test_r_resets_view() {
+        let harness = testing::run(events().key(Key::Space).key(Key::Space).key(Key::R));
+        assert_eq!(harness.state.fig_idx(), 0);
+        assert_eq!(
+            &harness.events[harness.events.len() - 3..],
+            &[
+                AppEvent::FigureChanged(0),
+                AppEvent::CameraReset,
+                AppEvent::RedrawRequested
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dialogs")]
+    fn test_dialog_shortcuts() {
+        use dragonfly::dialog::DialogKind;
+
+        let harness = testing::run(
+            events()
+                .ctrl_key(Key::O)
+                .ctrl_key(Key::S)
+                .ctrl_shift_key(Key::S)
+                .key(Key::O)
+                .key(Key::S),
+        );
+        assert_eq!(
+            harness.events,
+            vec![
+                AppEvent::DialogRequested(DialogKind::Open),
+                AppEvent::DialogRequested(DialogKind::SaveScene),
+                AppEvent::DialogRequested(DialogKind::ExportMesh),
+            ]
+        );
+        assert!(!harness.state.ctrl && !harness.state.shift);
+    }
+
+    #[test]
+    #[cfg(not(feature = "dialogs"))]
+    fn test_dialog_shortcuts_unbound() {
+        let harness = testing::run(
+            events()
+                .ctrl_key(Key::O)
+                .ctrl_key(Key::S)
+                .ctrl_shift_key(Key::S),
+        );
+        assert!(harness.events.is_empty());
+        assert!(!harness.state.ctrl && !harness.state.shift);
+    }
+
+    #[test]
+    fn test_resize_wait_and_redraw() {
+        let harness = testing::run(
+            events()
+                .resize(800, 600)
+                .wait_ms(50)
+                .wait_ms(25)
+                .redraw()
+                .redraw(),
+        );
+        assert_eq!(harness.state.size, Some((800, 600)));
+        assert_eq!(harness.state.elapsed, Duration::from_millis(75));
+        assert_eq!(harness.state.frames, 2);
+        assert_eq!(
+            harness.events,
+            vec![
+                AppEvent::Resized {
+                    width: 800,
+                    height: 600
+                },
+                AppEvent::RedrawRequested,
+                AppEvent::Render,
+                AppEvent::Render,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_and_close() {
+        let harness = testing::run(events().key(Key::F10).close());
+        assert_eq!(
+            harness.events,
+            vec![AppEvent::ProfileRequested, AppEvent::Exit]
+        );
+        assert!(harness.state.exiting);
+    }
+
+    #[test]
+    fn test_unhandled_keys_ignored() {
+        let harness = testing::run(events().key(Key::Digit(3)));
+        assert!(harness.events.is_empty());
+        assert_eq!(harness.state, AppState::default());
+    }
+
+    #[test]
+    fn test_set_figure() {
+        let mut state = AppState::default();
+        assert!(state.set_figure(42).is_none());
+        let last = Figure::COUNT - 1;
+        assert_eq!(
+            state.set_figure(last),
+            Some(vec![
+                AppEvent::FigureChanged(last),
+                AppEvent::RedrawRequested
+            ])
+        );
+        assert_eq!(
+            state.next_figure(),
+            vec![AppEvent::FigureChanged(0), AppEvent::RedrawRequested]
+        );
+    }
+
+    #[test]
+    fn test_window_events_normalized() {
+        assert_eq!(
+            InputEvent::from_window_event(&WindowEvent::Resized(winit::dpi::PhysicalSize::new(
+                640, 480
+            ))),
+            Some(InputEvent::Resized {
+                width: 640,
+                height: 480
+            })
+        );
+        assert_eq!(
+            InputEvent::from_window_event(&WindowEvent::CloseRequested),
+            Some(InputEvent::CloseRequested)
+        );
+        assert_eq!(
+            InputEvent::from_window_event(&WindowEvent::RedrawRequested),
+            Some(InputEvent::Redraw)
+        );
+        assert_eq!(
+            InputEvent::from_window_event(&WindowEvent::Focused(true)),
+            None
+        );
+
+        assert_eq!(Key::from_key_code(KeyCode::Space), Some(Key::Space));
+        assert_eq!(Key::from_key_code(KeyCode::Digit3), Some(Key::Digit(3)));
+        assert_eq!(Key::from_key_code(KeyCode::Equal), Some(Key::Plus));
+        assert_eq!(Key::from_key_code(KeyCode::NumpadAdd), Some(Key::Plus));
+        assert_eq!(Key::from_key_code(KeyCode::KeyZ), None);
+    }
+
+    #[test]
+    fn test_plus_subdivides() {
+        let harness = testing::run(events().key(Key::Plus));
+        assert!(harness.events.contains(&AppEvent::SubdivideRequested));
+    }
+
+    #[test]
+    fn test_m_morphs_to_next_figure() {
+        let harness = testing::run(events().key(Key::Space).key(Key::M));
+        assert_eq!(harness.state.fig_idx(), 2);
+        assert_eq!(
+            &harness.events[3..],
+            &[
+                AppEvent::MorphRequested {
+                    from: 1,
+                    to: 2,
+                    duration: MORPH_DURATION,
+                },
+                AppEvent::RedrawRequested,
+            ]
+        );
+
+        // The morph wraps around after the last figure.
+        let mut state = AppState::default();
+        state.set_figure(Figure::COUNT - 1);
+        assert_eq!(
+            state.morph_to_next_figure()[0],
+            AppEvent::MorphRequested {
+                from: Figure::COUNT - 1,
+                to: 0,
+                duration: MORPH_DURATION,
+            }
+        );
+    }
+
+    #[test]
+    fn test_a_toggles_spin() {
+        let harness = testing::run(events().key(Key::A));
+        assert!(harness.state.spin);
+        assert_eq!(harness.events, vec![AppEvent::RedrawRequested]);
+
+        let harness = testing::run(events().key(Key::A).key(Key::A));
+        assert!(!harness.state.spin);
+        assert_eq!(Key::from_key_code(KeyCode::KeyA), Some(Key::A));
+    }
+
+    #[test]
+    fn test_t_toggles_space_transitions() {
+        let harness = testing::run(events().key(Key::T).key(Key::Space));
+        assert!(harness.state.transitions);
+        assert_eq!(harness.state.fig_idx(), 1);
+        assert!(harness.events.contains(&AppEvent::MorphRequested {
+            from: 0,
+            to: 1,
+            duration: TRANSITION_DURATION,
+        }));
+
+        // Shift+Space morphs into the previous figure.
+        let mut state = AppState::default();
+        state.toggle_transitions();
+        state.handle(&InputEvent::ModifiersChanged {
+            ctrl: false,
+            shift: true,
+        });
+        assert_eq!(
+            state.handle(&InputEvent::KeyReleased(Key::Space))[0],
+            AppEvent::MorphRequested {
+                from: 0,
+                to: Figure::COUNT - 1,
+                duration: TRANSITION_DURATION,
+            }
+        );
+
+        // Toggled off, Space switches to the next figure.
+        let harness = testing::run(events().key(Key::T).key(Key::T).key(Key::Space));
+        assert!(!harness.state.transitions);
+        assert!(harness.events.contains(&AppEvent::FigureChanged(1)));
+    }
+}
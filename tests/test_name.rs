@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use dragonfly::vertex::{Figure, ParseFigureError};
+
+    /// Returns one figure of each variant.
+    fn every_figure() -> Vec<Figure> {
+        vec![
+            Figure::Triangle,
+            Figure::Pentagon,
+            Figure::Rectangle,
+            Figure::Trapezoid,
+            Figure::Parallelogram,
+            Figure::Circle(32),
+            Figure::Icosphere(2),
+            Figure::Torus {
+                major_radius: 0.35,
+                minor_radius: 0.15,
+                major_segments: 12,
+                minor_segments: 8,
+            },
+            Figure::Cylinder { segments: 16 },
+            Figure::Cone { segments: 16 },
+            Figure::Pyramid,
+            Figure::Tetrahedron,
+            Figure::Overlap,
+            Figure::Hexagon,
+            Figure::Hexagram,
+            Figure::Diamond,
+            Figure::Cross,
+            Figure::default_arrow(),
+            Figure::RoundedRectangle {
+                width: 0.9,
+                height: 0.6,
+                corner_radius: 0.15,
+                segments: 4,
+            },
+            Figure::Heart { segments: 16 },
+            Figure::Plane { subdivisions: 4 },
+            Figure::Lathe {
+                profile: vec![[0.0, -0.5], [0.4, 0.0], [0.0, 0.5]],
+                segments: 12,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        for figure in every_figure() {
+            let name = figure.to_string();
+            assert_eq!(Figure::from_str(&name), Ok(figure), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Figure::Circle(64).to_string(), "circle:64");
+        assert_eq!(Figure::Pyramid.to_string(), "pyramid");
+        assert_eq!(
+            Figure::Lathe {
+                profile: vec![[0.0, -0.5], [0.4, 0.0]],
+                segments: 12,
+            }
+            .to_string(),
+            "lathe:12:0,-0.5;0.4,0"
+        );
+    }
+
+    #[test]
+    fn test_names_unique() {
+        let names: Vec<&str> = every_figure().iter().map(Figure::name).collect();
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_case_insensitive() {
+        assert_eq!("Circle:12".parse(), Ok(Figure::Circle(12)));
+        assert_eq!(" TETRAHEDRON ".parse(), Ok(Figure::Tetrahedron));
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        // The figures of the space bar default to their parameters there.
+        for (name, index) in [
+            ("circle", 5),
+            ("torus", 6),
+            ("arrow", 13),
+            ("heart", 14),
+            ("rounded_rectangle", 15),
+            ("hexagram", 16),
+        ] {
+            assert_eq!(
+                name.parse::<Figure>().ok(),
+                Figure::try_from_index(index),
+                "{}",
+                name
+            );
+        }
+        // The missing trailing parameters keep their defaults.
+        assert_eq!(
+            "torus:0.4".parse(),
+            Ok(Figure::Torus {
+                major_radius: 0.4,
+                minor_radius: 0.15,
+                major_segments: 48,
+                minor_segments: 24,
+            })
+        );
+        assert!(matches!(
+            "lathe".parse(),
+            Ok(Figure::Lathe { segments: 32, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Figure::from_str("polygon:7"),
+            Err(ParseFigureError::UnknownFigure("polygon:7".to_string()))
+        );
+        assert_eq!(
+            Figure::from_str("circle:many"),
+            Err(ParseFigureError::InvalidParameter {
+                figure: "circle",
+                value: "many".to_string(),
+            })
+        );
+        assert_eq!(
+            Figure::from_str("circle:-3"),
+            Err(ParseFigureError::InvalidParameter {
+                figure: "circle",
+                value: "-3".to_string(),
+            })
+        );
+        assert!(matches!(
+            Figure::from_str("lathe:8:0,1;2"),
+            Err(ParseFigureError::InvalidParameter { .. })
+        ));
+        assert_eq!(
+            Figure::from_str("circle:8:8"),
+            Err(ParseFigureError::TooManyParameters {
+                figure: "circle",
+                count: 1,
+            })
+        );
+        assert_eq!(
+            Figure::from_str("triangle:1").unwrap_err().to_string(),
+            "Too many parameters for the triangle, which takes at most 0"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use dragonfly::vertex::{parse_obj, Figure, Mesh, ObjError};
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_import_house() {
+        let house = Figure::from_obj(&fixture("house.obj")).unwrap();
+        assert_eq!(house.vertices.len(), 9);
+        assert_eq!(house.indices.len(), 14 * 3);
+        assert!(house.validate().is_ok());
+
+        // The quads are fanned around their first vertex, and the relative
+        // indices of the roof refer to the apex and the top corners.
+        assert_eq!(house.indices[..6], [0, 1, 2, 0, 2, 3]);
+        assert_eq!(house.indices[30..33], [7, 6, 8]);
+        assert_eq!(house.indices[39..], [4, 7, 8]);
+    }
+
+    #[test]
+    fn test_import_is_normalized() {
+        let house = Figure::from_obj(&fixture("house.obj")).unwrap();
+
+        // The house is 3 units high and 2 units wide.
+        let (min, max) = house.bounds();
+        let close = |a: [f32; 3], b: [f32; 3]| (0..3).all(|i| (a[i] - b[i]).abs() < 1e-6);
+        assert!(close(min, [-1.0 / 3.0, -0.5, -1.0 / 3.0]));
+        assert!(close(max, [1.0 / 3.0, 0.5, 1.0 / 3.0]));
+
+        // The apex is the highest vertex, so it is the warmest.
+        let apex = house.vertices[8];
+        assert!(house
+            .vertices
+            .iter()
+            .all(|v| v.color[0] <= apex.color[0] && v.color[2] >= apex.color[2]));
+        assert!((apex.normal[1] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_indices() {
+        let triangle = "v 0 0 0\nv 1 0 0\nv 0 1 0\n";
+        assert!(parse_obj(&format!("{}f 1 2 3\nf -3 -2 -1\n", triangle)).is_ok());
+
+        for (face, index) in [("f 1 2 4", 4), ("f 0 1 2", 0), ("f -4 -2 -1", -4)] {
+            match parse_obj(&format!("{}{}\n", triangle, face)) {
+                Err(ObjError::IndexOutOfRange { line: 4, index: i }) => assert_eq!(i, index),
+                other => panic!("{}: {:?}", face, other),
+            }
+        }
+
+        // A relative index only sees the vertices defined before the face.
+        assert!(matches!(
+            parse_obj("v 0 0 0\nv 1 0 0\nf -1 -2 -3\nv 0 1 0\n"),
+            Err(ObjError::IndexOutOfRange { line: 3, index: -3 })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        for source in [
+            "v 0 0\n",
+            "v 0 zero 0\n",
+            "v 0 0 0\nv 1 0 0\nf 1 2\n",
+            "v 0 0 0\nf a b c\n",
+        ] {
+            assert!(
+                matches!(parse_obj(source), Err(ObjError::InvalidLine { .. })),
+                "{:?}",
+                source
+            );
+        }
+
+        let e = Figure::from_obj(&fixture("missing.obj")).unwrap_err();
+        assert!(matches!(e, ObjError::Io(_)));
+    }
+}
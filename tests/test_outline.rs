@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{Figure, Mesh};
+
+    /// Returns the signed area of each triangle, positive when it is
+    /// counter-clockwise.
+    fn signed_areas(vertices: &[dragonfly::vertex::Vertex], indices: &[u16]) -> Vec<f32> {
+        indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [0, 1, 2].map(|k| vertices[t[k] as usize].position);
+                (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rectangle_outline() {
+        let (vertices, indices) = Figure::Rectangle.get_outline(0.1);
+
+        // One quad per side.
+        assert_eq!(vertices.len(), 8);
+        assert_eq!(indices.len(), 4 * 6);
+        assert!(signed_areas(&vertices, &indices).iter().all(|&a| a > 0.0));
+
+        // The corners are mitered: the outer corners are offset diagonally.
+        let mut xs: Vec<f32> = vertices.iter().map(|v| v.position[0].abs()).collect();
+        let mut ys: Vec<f32> = vertices.iter().map(|v| v.position[1].abs()).collect();
+        xs.sort_by(f32::total_cmp);
+        ys.sort_by(f32::total_cmp);
+        assert!((xs[0] - 0.45).abs() < 1e-5 && (xs[7] - 0.55).abs() < 1e-5);
+        assert!((ys[0] - 0.20).abs() < 1e-5 && (ys[7] - 0.30).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_circle_outline_outer_rim_only() {
+        let (vertices, indices) = Figure::Circle(16).get_outline(0.1);
+
+        // The spokes to the center are not part of the outline.
+        assert_eq!(vertices.len(), 2 * 16);
+        assert_eq!(indices.len(), 16 * 6);
+        assert!(signed_areas(&vertices, &indices).iter().all(|&a| a > 0.0));
+        for vertex in &vertices {
+            let [x, y, _] = vertex.position;
+            let radius = (x * x + y * y).sqrt();
+            assert!((0.44..0.56).contains(&radius), "radius {}", radius);
+        }
+    }
+
+    #[test]
+    fn test_closed_mesh_has_no_outline() {
+        let (vertices, indices) = Figure::Icosphere(1).get_outline(0.1);
+        assert!(vertices.is_empty());
+        assert!(indices.is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{fs::File, path::Path};
+
+    use dragonfly::vertex::{Mesh, MeshData, PlyError};
+
+    fn fixture(name: &str) -> File {
+        File::open(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name),
+        )
+        .unwrap()
+    }
+
+    /// A header declaring a vertex element with the given properties,
+    /// followed by the given data.
+    fn ply(properties: &str, data: &str) -> String {
+        format!(
+            "ply\nformat ascii 1.0\nelement vertex 3\n{}\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n{}",
+            properties, data
+        )
+    }
+
+    #[test]
+    fn test_colored_cube() {
+        let cube = MeshData::from_ply(fixture("cube.ply")).unwrap();
+        assert_eq!(cube.vertices.len(), 8);
+        assert_eq!(cube.indices.len(), 12 * 3);
+        assert_eq!(cube.indices[..6], [0, 1, 3, 0, 3, 2]);
+        assert!(cube.validate().is_ok());
+        assert_eq!(cube.bounds(), ([-0.5; 3], [0.5; 3]));
+
+        // The colors follow the declared order of the properties, not the
+        // canonical one.
+        for vertex in &cube.vertices {
+            let [x, y, z] = vertex.position.map(|c| c + 0.5);
+            assert_eq!(vertex.color, [x, y, z, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_gradient_without_colors() {
+        let source = ply(
+            "property float x\nproperty float y\nproperty float z",
+            "0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n",
+        );
+        let triangle = MeshData::from_ply(source.as_bytes()).unwrap();
+        assert_eq!(triangle.indices, vec![0, 1, 2]);
+
+        // The top vertex is warmer than the bottom ones.
+        let [bottom, _, top] = [0, 1, 2].map(|i| triangle.vertices[i].color);
+        assert!(top[0] > bottom[0] && top[2] < bottom[2]);
+
+        // Float colors are read from 0 to 1.
+        let source = ply(
+            "property float x\nproperty float y\nproperty float z\nproperty float red\nproperty float green\nproperty float blue",
+            "0 0 0 1 0 0\n1 0 0 0 0.5 0\n0 1 0 0 0 1\n3 0 1 2\n",
+        );
+        let triangle = MeshData::from_ply(source.as_bytes()).unwrap();
+        assert_eq!(triangle.vertices[1].color, [0.0, 0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let xyz = "property float x\nproperty float y\nproperty float z";
+        let parse = |source: &str| MeshData::from_ply(source.as_bytes());
+
+        assert!(matches!(
+            parse("plx\nend_header\n"),
+            Err(PlyError::InvalidHeader { line: 1 })
+        ));
+        assert!(matches!(
+            parse("ply\nformat binary_little_endian 1.0\nend_header\n"),
+            Err(PlyError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            parse("ply\nformat ascii 1.0\nproperty float x\nend_header\n"),
+            Err(PlyError::InvalidHeader { line: 3 })
+        ));
+        assert!(matches!(
+            parse(&ply("property float x\nproperty float y", "")),
+            Err(PlyError::MissingProperty("z"))
+        ));
+        assert!(matches!(
+            parse(&ply(xyz, "0 0 0\n1 0 0\n0 1 0\n3 0 1 3\n")),
+            Err(PlyError::IndexOutOfRange { line: 13, index: 3 })
+        ));
+        assert!(matches!(
+            parse(&ply(xyz, "0 0 0\n1 0\n0 1 0\n3 0 1 2\n")),
+            Err(PlyError::InvalidLine { line: 11 })
+        ));
+        assert!(matches!(
+            parse(&ply(xyz, "0 0 0\n1 0 0\n0 1 0\n")),
+            Err(PlyError::Truncated)
+        ));
+    }
+}
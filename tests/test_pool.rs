@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use dragonfly::{
+        pool::{figure_pool, CachedMesh, ObjectPool},
+        vertex::{Figure, Mesh},
+    };
+
+    /// The system allocator, counting the allocations of each thread so
+    /// that the tests running in parallel do not interfere.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Returns the number of allocations made by `f` on this thread.
+    fn count_allocations(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_pool_round_robin() {
+        let pool = ObjectPool::new(vec!['a', 'b', 'c']);
+        let taken: Vec<char> = (0..5).map(|_| *pool.get().unwrap()).collect();
+        assert_eq!(taken, ['a', 'b', 'c', 'a', 'b']);
+
+        // Indexing does not advance the round robin.
+        assert_eq!(pool.get_at(0), Some(&'a'));
+        assert_eq!(pool.get(), Some(&'c'));
+        assert_eq!(pool.get_at(3), None);
+        assert_eq!(pool.len(), 3);
+
+        let empty: ObjectPool<char> = ObjectPool::new(Vec::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.get(), None);
+    }
+
+    #[test]
+    fn test_figure_pool_matches_figures() {
+        let pool = figure_pool();
+        assert_eq!(pool.len(), Figure::COUNT as usize);
+        for (i, figure) in Figure::ALL.iter().enumerate() {
+            let mesh = pool.get_at(i).unwrap();
+            assert_eq!(mesh.vertices.len(), figure.get_vertices().len());
+            assert_eq!(&mesh.indices[..], &figure.get_indices()[..]);
+            assert_eq!(&mesh.edge_indices[..], &figure.get_edge_indices()[..]);
+            assert_eq!(
+                &mesh.wireframe_indices[..],
+                &figure.get_wireframe_indices()[..]
+            );
+            assert_eq!(mesh.bounds, figure.bounds());
+        }
+    }
+
+    /// Measures the allocations of a full cycle through the figures.
+    ///
+    /// Without the pool, each switch builds the vertices, the indices, the
+    /// outline and the wireframe of the figure, with dozens of allocations
+    /// for the curved figures. With the pool, a switch only copies the
+    /// vertices, which the context keeps to recolor them: one allocation.
+    #[test]
+    fn test_pool_reduces_allocations() {
+        let uncached = count_allocations(|| {
+            for figure in Figure::ALL {
+                std::hint::black_box(CachedMesh::from_mesh(figure));
+            }
+        });
+
+        let pool = figure_pool();
+        let cached = count_allocations(|| {
+            for i in 0..pool.len() {
+                std::hint::black_box(pool.get_at(i).unwrap().vertices.to_vec());
+            }
+        });
+
+        println!(
+            "Allocations for a cycle of {} figures: {} without the pool, {} with it",
+            Figure::COUNT,
+            uncached,
+            cached
+        );
+        assert_eq!(cached, Figure::COUNT as usize);
+        assert!(uncached > 4 * cached, "{} {}", uncached, cached);
+    }
+}
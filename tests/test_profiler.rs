@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{thread, time::Duration};
+
+    use dragonfly::profiler::{self, ScopeTimer};
+
+    #[test]
+    fn test_nested_scope_accumulation() {
+        profiler::begin_frame();
+        {
+            let _outer = ScopeTimer::new("outer");
+            for _ in 0..2 {
+                let _inner = ScopeTimer::new("inner");
+                thread::sleep(Duration::from_millis(2));
+            }
+        }
+
+        let profile = profiler::current_frame_profile();
+        let outer = profile.get("outer").unwrap();
+        let inner = profile.get("outer/inner").unwrap();
+        assert_eq!(outer.calls, 1);
+        assert_eq!(inner.calls, 2);
+        assert_eq!(inner.depth(), 1);
+        assert_eq!(inner.name(), "inner");
+        assert!(inner.total >= Duration::from_millis(4));
+        assert!(outer.total >= inner.total);
+        assert!(profile.get("inner").is_none());
+    }
+
+    #[test]
+    fn test_begin_frame() {
+        profiler::begin_frame();
+        drop(ScopeTimer::new("first"));
+        profiler::begin_frame();
+        drop(ScopeTimer::new("second"));
+
+        assert!(profiler::last_frame_profile().get("first").is_some());
+        assert!(profiler::last_frame_profile().get("second").is_none());
+        assert!(profiler::current_frame_profile().get("second").is_some());
+    }
+
+    #[test]
+    fn test_breakdown_display() {
+        profiler::begin_frame();
+        {
+            let _frame = ScopeTimer::new("frame");
+            drop(ScopeTimer::new("render"));
+        }
+
+        let breakdown = profiler::current_frame_profile().to_string();
+        let lines: Vec<&str> = breakdown.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("frame "));
+        assert!(lines[1].starts_with("  render "));
+    }
+
+    #[test]
+    fn test_chrome_trace_json() {
+        profiler::enable_tracing();
+        {
+            let _frame = ScopeTimer::new("frame");
+            drop(ScopeTimer::new("upload"));
+        }
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&profiler::chrome_trace_json()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+
+        // Scopes are emitted when they end, innermost first.
+        assert_eq!(events[0]["name"], "upload");
+        assert_eq!(events[1]["name"], "frame");
+        for event in events {
+            assert_eq!(event["ph"], "X");
+            assert!(event["ts"].is_u64());
+            assert!(event["dur"].is_u64());
+            assert!(event["pid"].is_u64());
+            assert!(event["tid"].is_u64());
+        }
+        assert!(events[0]["ts"].as_u64() >= events[1]["ts"].as_u64());
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    #[test]
+    fn test_profile_scope_disabled() {
+        const _: () = assert!(!profiler::ENABLED);
+
+        profiler::begin_frame();
+        {
+            dragonfly::profile_scope!("disabled");
+        }
+        assert!(profiler::current_frame_profile().scopes.is_empty());
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_profile_scope_enabled() {
+        profiler::begin_frame();
+        {
+            dragonfly::profile_scope!("enabled");
+        }
+        assert_eq!(profiler::current_frame_profile().scopes.len(), 1);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        render_queue::RenderQueue,
+        snapshot::ContextHeadless,
+        vertex::{Figure, Indices, Mesh},
+    };
+
+    const IDENTITY: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    /// Returns the row-major matrix translating along X.
+    fn translation_x(x: f32) -> [[f32; 4]; 4] {
+        let mut matrix = IDENTITY;
+        matrix[0][3] = x;
+        matrix
+    }
+
+    #[test]
+    fn test_three_triangles_batched() {
+        let mut queue = RenderQueue::new();
+        assert!(queue.is_empty());
+        for x in [-0.5, 0.0, 0.5] {
+            queue.push(&Figure::Triangle, &translation_x(x));
+        }
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.get_vertices().len(), 9);
+        assert_eq!(queue.get_index_data(), Indices::U16((0..9).collect()));
+
+        // Each triangle is moved by its own transform.
+        let triangle = Figure::Triangle.get_vertices();
+        for (i, x) in [-0.5, 0.0, 0.5].into_iter().enumerate() {
+            for (vertex, original) in queue.vertices[3 * i..3 * i + 3].iter().zip(&triangle) {
+                let [ox, oy, oz] = original.position;
+                assert_eq!(vertex.position, [ox + x, oy, oz]);
+            }
+        }
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.vertices.is_empty() && queue.indices.is_empty());
+    }
+
+    #[test]
+    fn test_large_queue_uses_u32_indices() {
+        let mut queue = RenderQueue::new();
+        let square = Figure::Rectangle.mesh_data();
+        let copies = u16::MAX as usize / square.vertices.len() + 1;
+        for _ in 0..copies {
+            queue.push(&square, &IDENTITY);
+        }
+
+        let indices = queue.get_index_data();
+        assert_eq!(indices.format(), wgpu::IndexFormat::Uint32);
+        assert_eq!(
+            indices.to_u32().into_iter().max(),
+            Some(queue.vertices.len() as u32 - 1)
+        );
+    }
+
+    #[test]
+    fn test_queue_renders_as_merged_mesh() {
+        let mut queue = RenderQueue::new();
+        queue.push(&Figure::Triangle, &IDENTITY);
+        let mut context = ContextHeadless::new(64, 32).unwrap();
+
+        assert_eq!(
+            context.render_figure(&queue),
+            context.render_figure(&Figure::Triangle)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        dialog::{self, DialogKind, DialogOutcome},
+        dragonfly::Dragonfly,
+        vertex::{self, Figure, ImportReport, Mesh, MeshData, MeshError, MeshIssue, Vertex},
+    };
+
+    fn vertex(x: f32, y: f32) -> Vertex {
+        Vertex {
+            position: [x, y, 0.0],
+            color: [1.0, 0.0, 0.0, 1.0],
+            normal: [0.0, 0.0, 1.0],
+            tex_coords: [0.0, 0.0],
+        }
+    }
+
+    /// Returns a unit square made of two counter-clockwise triangles.
+    fn square() -> (Vec<Vertex>, Vec<u16>) {
+        (
+            vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(1.0, 1.0),
+                vertex(0.0, 1.0),
+            ],
+            vec![0, 1, 2, 0, 2, 3],
+        )
+    }
+
+    /// Asserts that the mesh has only the given issue, and that the repaired
+    /// mesh is clean and unchanged by a second repair.
+    fn assert_repaired(vertices: &[Vertex], indices: &[u16], issue: MeshIssue, count: usize) {
+        let report = vertex::validate(vertices, indices);
+        assert_eq!(report.issues, vec![(issue, count)]);
+
+        let (vertices, indices) = vertex::repair(vertices, indices);
+        assert!(vertex::validate(&vertices, &indices).is_clean());
+
+        let (again_vertices, again_indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(again_indices, indices);
+        assert_eq!(
+            bytemuck::cast_slice::<Vertex, f32>(&again_vertices),
+            bytemuck::cast_slice::<Vertex, f32>(&vertices)
+        );
+    }
+
+    #[test]
+    fn test_builtin_figures_are_clean() {
+        for (i, figure) in Figure::ALL.iter().enumerate() {
+            let report = vertex::validate(&figure.get_vertices(), &figure.get_indices());
+            // The circle repeats its first rim vertex at the end, on purpose,
+            // to close its fan.
+            let seams = match figure {
+                Figure::Circle(_) => 1,
+                _ => 0,
+            };
+            assert_eq!(
+                report.count(MeshIssue::DuplicateVertices),
+                seams,
+                "figure {}: {}",
+                i,
+                report
+            );
+            assert!(
+                report
+                    .issues
+                    .iter()
+                    .all(|&(issue, _)| issue == MeshIssue::DuplicateVertices),
+                "figure {}: {}",
+                i,
+                report
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_finite_positions() {
+        let (mut vertices, indices) = square();
+        vertices[2].position[0] = f32::NAN;
+        vertices[3].position[1] = f32::INFINITY;
+        assert_repaired(&vertices, &indices, MeshIssue::NonFinitePositions, 2);
+
+        let (vertices, _) = vertex::repair(&vertices, &indices);
+        assert_eq!(vertices[2].position, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_indices_out_of_range() {
+        let (vertices, mut indices) = square();
+        indices.extend([0, 3, 9]);
+        assert_repaired(&vertices, &indices, MeshIssue::IndicesOutOfRange, 1);
+
+        let (_, indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(indices.len(), 6);
+    }
+
+    #[test]
+    fn test_duplicate_vertices() {
+        let (mut vertices, _) = square();
+        vertices.extend([vertex(0.0, 0.0), vertex(1.0, 1.0)]);
+        let indices = vec![0, 1, 2, 4, 5, 3];
+        assert_repaired(&vertices, &indices, MeshIssue::DuplicateVertices, 2);
+
+        let (vertices, indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_degenerate_triangles() {
+        let (mut vertices, mut indices) = square();
+        vertices.push(vertex(2.0, 0.0));
+        indices.extend([1, 1, 2, 0, 1, 4]);
+        assert_repaired(&vertices, &indices, MeshIssue::DegenerateTriangles, 2);
+
+        let (_, indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_inconsistent_winding() {
+        let (vertices, _) = square();
+        let indices = vec![0, 1, 2, 0, 3, 2];
+        assert_repaired(&vertices, &indices, MeshIssue::InconsistentWinding, 1);
+
+        // The repaired square faces the positive Z axis.
+        let (_, indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_winding_oriented_like_boundary() {
+        // Consistently wound, but clockwise: not an issue, but the repair
+        // faces it towards the positive Z axis.
+        let (vertices, _) = square();
+        let indices = vec![0, 2, 1, 0, 3, 2];
+        assert!(vertex::validate(&vertices, &indices).is_clean());
+
+        let (_, indices) = vertex::repair(&vertices, &indices);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_closed_mesh_oriented_outward() {
+        let figure = Figure::Icosphere(1);
+        let vertices = figure.get_vertices();
+        let inverted: Vec<u16> = figure
+            .get_indices()
+            .chunks(3)
+            .flat_map(|t| [t[0], t[2], t[1]])
+            .collect();
+
+        let (_, indices) = vertex::repair(&vertices, &inverted);
+        assert_eq!(indices, figure.get_indices());
+    }
+
+    #[test]
+    fn test_report_formatting() {
+        let (mut vertices, mut indices) = square();
+        vertices[0].position[2] = f32::NAN;
+        indices.extend([2, 2, 3]);
+
+        let report = vertex::validate(&vertices, &indices);
+        assert_eq!(report.count(MeshIssue::NonFinitePositions), 1);
+        assert_eq!(report.count(MeshIssue::DuplicateVertices), 0);
+        assert_eq!(
+            report.to_string(),
+            "Mesh report: 2 issue(s)\n  non-finite positions: 1\n  degenerate triangles: 1\n"
+        );
+        assert_eq!(
+            ImportReport::default().to_string(),
+            "Mesh report: no issues\n"
+        );
+    }
+
+    #[test]
+    fn test_report_written_next_to_file() {
+        let mesh_path = std::env::temp_dir().join("dragonfly_test_mesh.obj");
+        let report = vertex::validate(&[], &[0, 1, 2]);
+
+        let path = report.write_next_to(&mesh_path).unwrap();
+        assert_eq!(
+            path.file_name().unwrap(),
+            "dragonfly_test_mesh.obj.report.txt"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), report.to_string());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_broken_obj_import_repaired() {
+        // The second face is wound backwards, and the third one is degenerate
+        // because its last vertex duplicates the third one.
+        let path = std::env::temp_dir().join("dragonfly_test_broken.obj");
+        std::fs::write(
+            &path,
+            "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 1 4 3\nf 2 3 5\n",
+        )
+        .unwrap();
+
+        let (mesh, report) = dialog::read_mesh(&path, &dialog::OBJ, false).unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                (MeshIssue::DuplicateVertices, 1),
+                (MeshIssue::DegenerateTriangles, 1),
+                (MeshIssue::InconsistentWinding, 1),
+            ]
+        );
+        assert_eq!(mesh.indices.len(), 9);
+
+        let (repaired, repaired_report) = dialog::read_mesh(&path, &dialog::OBJ, true).unwrap();
+        assert_eq!(repaired_report, report);
+        assert!(vertex::validate(&repaired.vertices, &repaired.indices).is_clean());
+        assert_eq!(repaired.indices.len(), 6);
+
+        let mut app = Dragonfly::default().with_repair(true);
+        app.dialog_closed(DialogKind::Open, DialogOutcome::Picked(path.clone()));
+        let opened = app.mesh().expect("The OBJ file is not opened");
+        assert_eq!(opened.indices, repaired.indices);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mesh_validate_accepts_figures() {
+        for (i, figure) in Figure::ALL.iter().enumerate() {
+            assert_eq!(figure.validate(), Ok(()), "figure {}", i);
+        }
+        assert_eq!(MeshData::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_mesh_validate_errors() {
+        let (vertices, _) = square();
+        let mesh = |indices: Vec<u16>| MeshData::from((vertices.clone(), indices));
+
+        assert_eq!(
+            mesh(vec![0, 1, 2, 0]).validate(),
+            Err(MeshError::IncompleteTriangle { index_count: 4 })
+        );
+        assert_eq!(
+            mesh(vec![0, 1, 2, 0, 2, 7]).validate(),
+            Err(MeshError::IndexOutOfRange {
+                position: 5,
+                index: 7,
+                vertex_count: 4
+            })
+        );
+        assert_eq!(
+            mesh(vec![0, 1, 2, 0, 2, 2]).validate(),
+            Err(MeshError::RepeatedVertex { triangle: 1 })
+        );
+
+        // The middle of the bottom edge is aligned with its corners.
+        let mut vertices = vertices.clone();
+        vertices.push(vertex(0.5, 0.0));
+        let degenerate = MeshData::from((vertices, vec![0, 1, 2, 0, 4, 1]));
+        assert_eq!(
+            degenerate.validate(),
+            Err(MeshError::DegenerateTriangle { triangle: 1 })
+        );
+        assert_eq!(
+            degenerate.validate().unwrap_err().to_string(),
+            "Triangle 1 has no area"
+        );
+    }
+}
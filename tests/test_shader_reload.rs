@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::context::{compile_render_pipeline, create_uniform_bind_group_layout, Context};
+
+    const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    fn create_test_device() -> (wgpu::Device, wgpu::Queue) {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .unwrap();
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compile_builtin_shader() {
+        let (device, _queue) = create_test_device();
+        let layout = create_uniform_bind_group_layout(&device);
+        let source = include_str!("../shaders/shader.wgsl");
+        assert!(compile_render_pipeline(&device, &layout, FORMAT, source).is_ok());
+    }
+
+    #[test]
+    fn test_compile_invalid_shader() {
+        let (device, _queue) = create_test_device();
+        let layout = create_uniform_bind_group_layout(&device);
+
+        for source in [
+            "this is not WGSL",
+            // Valid WGSL, without the entry points of the pipeline.
+            "@fragment fn fs_other() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }",
+        ] {
+            assert!(compile_render_pipeline(&device, &layout, FORMAT, source).is_err());
+        }
+
+        // The errors are caught, so the device stays usable.
+        let source = include_str!("../shaders/shader.wgsl");
+        assert!(compile_render_pipeline(&device, &layout, FORMAT, source).is_ok());
+    }
+
+    #[test]
+    fn test_custom_shader_entry_points() {
+        let (device, _queue) = create_test_device();
+        let source = include_str!("../shaders/shader.wgsl");
+        assert!(Context::with_custom_shader(&device, source).is_ok());
+
+        for entry_point in ["vs_main", "fs_main"] {
+            let renamed = source.replace(
+                &format!("fn {}(", entry_point),
+                &format!("fn {}_renamed(", entry_point),
+            );
+            let error = Context::with_custom_shader(&device, &renamed).unwrap_err();
+            assert!(error.to_string().contains(entry_point), "{}", error);
+        }
+    }
+}
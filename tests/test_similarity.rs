@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{mesh_similarity, Figure, Mesh, Vertex, MAX_SHAPE_DISTANCE};
+
+    /// A 2D outline fan-triangulated from its centroid.
+    struct Outline(Vec<[f32; 2]>);
+
+    impl Outline {
+        /// Returns the outline of a superellipse of radius 0.5.
+        fn superellipse(exponent: f32, segments: u32) -> Self {
+            Outline(
+                (0..segments)
+                    .map(|i| {
+                        let angle = i as f32 * 2.0 * std::f32::consts::PI / segments as f32;
+                        let (sin, cos) = angle.sin_cos();
+                        let r = |c: f32| c.signum() * c.abs().powf(2.0 / exponent);
+                        [0.5 * r(cos), 0.5 * r(sin)]
+                    })
+                    .collect(),
+            )
+        }
+
+        /// Returns the outline rotated by the given angle, in radians.
+        fn rotated(&self, angle: f32) -> Self {
+            let (sin, cos) = angle.sin_cos();
+            Outline(
+                self.0
+                    .iter()
+                    .map(|[x, y]| [x * cos - y * sin, x * sin + y * cos])
+                    .collect(),
+            )
+        }
+    }
+
+    impl Mesh for Outline {
+        fn get_vertices(&self) -> Vec<Vertex> {
+            std::iter::once([0.0, 0.0])
+                .chain(self.0.iter().copied())
+                .map(|[x, y]| Vertex {
+                    position: [x, y, 0.0],
+                    color: [1.0, 1.0, 1.0, 1.0],
+                    normal: [0.0, 0.0, 1.0],
+                    tex_coords: [0.0, 0.0],
+                })
+                .collect()
+        }
+
+        fn get_indices(&self) -> Vec<u16> {
+            let n = self.0.len() as u16;
+            (1..=n).flat_map(|i| [0, i, i % n + 1]).collect()
+        }
+    }
+
+    fn triangle_outline() -> Outline {
+        Outline(
+            Figure::Triangle
+                .get_vertices()
+                .iter()
+                .map(|v| [v.position[0], v.position[1]])
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_self_similarity() {
+        for figure in [Figure::Triangle, Figure::Pentagon, Figure::Circle(64)] {
+            assert!(mesh_similarity(&figure, &figure) < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_circle_superellipse_similarity() {
+        let superellipse = Outline::superellipse(2.0, 256);
+        assert!(mesh_similarity(&Figure::Circle(64), &superellipse) < 0.01);
+    }
+
+    #[test]
+    fn test_triangle_circle_dissimilarity() {
+        let distance = mesh_similarity(&Figure::Triangle, &Figure::Circle(64));
+        assert!(distance > 0.05);
+    }
+
+    #[test]
+    fn test_rotation_invariance() {
+        let triangle = triangle_outline();
+        let rotated = triangle.rotated(1.0);
+        assert!(mesh_similarity(&triangle, &rotated) < 1e-3);
+        assert!(mesh_similarity(&Figure::Triangle, &rotated) < 1e-3);
+    }
+
+    #[test]
+    fn test_degenerate_mesh_distance() {
+        let empty = Outline(vec![]);
+        assert_eq!(
+            mesh_similarity(&Figure::Triangle, &empty),
+            MAX_SHAPE_DISTANCE
+        );
+
+        let point = Outline(vec![[0.1, 0.1]; 3]);
+        assert_eq!(
+            mesh_similarity(&point, &Figure::Triangle),
+            MAX_SHAPE_DISTANCE
+        );
+
+        let sphere = Figure::Icosphere(1);
+        assert_eq!(mesh_similarity(&sphere, &sphere), MAX_SHAPE_DISTANCE);
+    }
+
+    #[test]
+    fn test_closest_builtin() {
+        let (figure, distance) = Figure::closest_builtin(&triangle_outline().rotated(0.5));
+        assert_eq!(figure, Figure::Triangle);
+        assert!(distance < 1e-3);
+
+        let (figure, _) = Figure::closest_builtin(&Outline::superellipse(2.0, 100));
+        assert_eq!(figure, Figure::Circle(64));
+    }
+}
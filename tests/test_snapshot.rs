@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        error::DragonflyError,
+        snapshot::{self, ContextHeadless},
+        vertex::{Figure, Mesh, Vertex},
+    };
+
+    const WIDTH: u32 = 64;
+    const HEIGHT: u32 = 32;
+
+    /// Returns the pixel at the given coordinates, from the top-left.
+    fn pixel(pixels: &[u8], x: u32, y: u32) -> &[u8] {
+        let start = ((y * WIDTH + x) * 4) as usize;
+        &pixels[start..start + 4]
+    }
+
+    /// A mesh with no triangle.
+    struct Empty;
+
+    impl Mesh for Empty {
+        fn get_vertices(&self) -> Vec<Vertex> {
+            Vec::new()
+        }
+
+        fn get_indices(&self) -> Vec<u16> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_headless_renders_every_figure() {
+        let mut context = ContextHeadless::new(WIDTH, HEIGHT).unwrap();
+        assert_eq!(context.size(), (WIDTH, HEIGHT));
+
+        // The figures cycled through, and the others with their default
+        // parameters.
+        let others = ["icosphere", "cylinder", "cone", "plane", "lathe"]
+            .map(|name| name.parse::<Figure>().unwrap());
+        for figure in Figure::ALL.iter().chain(&others) {
+            let pixels = context.render_figure(figure);
+            assert_eq!(pixels.len(), (WIDTH * HEIGHT * 4) as usize, "{}", figure);
+            assert!(
+                pixels.chunks_exact(4).any(|pixel| pixel != [255; 4]),
+                "{} is not drawn",
+                figure
+            );
+        }
+
+        // The texture is cleared between the renders.
+        context.background = wgpu::Color::BLACK;
+        let pixels = context.render_figure(&Empty);
+        assert!(pixels.chunks_exact(4).all(|pixel| pixel == [0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_headless_set_mesh_reuses_buffers() {
+        let mut context = ContextHeadless::new(WIDTH, HEIGHT).unwrap();
+        let figures = [Figure::Triangle, Figure::Circle(64), Figure::Rectangle];
+        for i in 0..100 {
+            let figure = &figures[i % figures.len()];
+            context.set_mesh(figure);
+            assert_eq!(context.num_vertices(), figure.get_vertices().len() as u32);
+            assert_eq!(context.num_indices(), figure.get_indices().len() as u32);
+        }
+
+        // A smaller mesh written over a larger one renders alone.
+        let expected = ContextHeadless::new(WIDTH, HEIGHT)
+            .unwrap()
+            .render_figure(&Figure::Triangle);
+        context.set_mesh(&Figure::Circle(64));
+        context.set_mesh(&Figure::Triangle);
+        assert_eq!(context.render(), expected);
+    }
+
+    #[test]
+    fn test_tuple_mesh_renders_as_figure() {
+        let vertices = Figure::Triangle.get_vertices();
+        let indices = Figure::Triangle.get_indices();
+        let mut context = ContextHeadless::new(WIDTH, HEIGHT).unwrap();
+
+        let expected = context.render_figure(&Figure::Triangle);
+        assert_eq!(
+            context.render_figure(&(&vertices[..], &indices[..])),
+            expected
+        );
+        assert_eq!(context.render_figure(&(vertices, indices)), expected);
+    }
+
+    #[test]
+    fn test_nearer_triangle_hides_farther_one() {
+        // A triangle covering the center of the texture at the given depth.
+        let triangle = |z, [r, g, b]: [f32; 3]| {
+            [
+                Vertex::at_colored(-1.0, -1.0, z, r, g, b),
+                Vertex::at_colored(1.0, -1.0, z, r, g, b),
+                Vertex::at_colored(0.0, 1.0, z, r, g, b),
+            ]
+        };
+        let mut context = ContextHeadless::new(WIDTH, HEIGHT).unwrap();
+
+        // The nearer triangle is drawn whether it comes first or last.
+        for (first, second) in [(0.1, 0.5), (0.5, 0.1)] {
+            let mut vertices = triangle(first, [1.0, 0.0, 0.0]).to_vec();
+            vertices.extend(triangle(second, [0.0, 0.0, 1.0]));
+            let pixels = context.render_figure(&(vertices, vec![0, 1, 2, 3, 4, 5]));
+            let expected = if first < second {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 255, 255]
+            };
+            assert_eq!(pixel(&pixels, WIDTH / 2, HEIGHT / 2), expected);
+        }
+    }
+
+    #[test]
+    fn test_cull_modes() {
+        // A red triangle facing the camera and a blue one facing away, both
+        // covering the center of the texture, the red one nearer.
+        let triangle = |z, [r, g, b]: [f32; 3]| {
+            [
+                Vertex::at_colored(-1.0, -1.0, z, r, g, b),
+                Vertex::at_colored(1.0, -1.0, z, r, g, b),
+                Vertex::at_colored(0.0, 1.0, z, r, g, b),
+            ]
+        };
+        let mut vertices = triangle(0.1, [1.0, 0.0, 0.0]).to_vec();
+        vertices.extend(triangle(0.5, [0.0, 0.0, 1.0]));
+        let mesh = (vertices, vec![0, 1, 2, 3, 5, 4]);
+        let mut context = ContextHeadless::new(WIDTH, HEIGHT).unwrap();
+
+        for (cull_mode, expected) in [
+            (Some(wgpu::Face::Back), [255, 0, 0, 255]),
+            (Some(wgpu::Face::Front), [0, 0, 255, 255]),
+            (None, [255, 0, 0, 255]),
+        ] {
+            context.set_cull_mode(cull_mode);
+            let pixels = context.render_figure(&mesh);
+            assert_eq!(pixel(&pixels, WIDTH / 2, HEIGHT / 2), expected);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_renders_mesh() {
+        let pixels =
+            snapshot::render_snapshot(&Figure::Rectangle, WIDTH, HEIGHT, wgpu::Color::BLACK)
+                .unwrap();
+        assert_eq!(pixels.len(), (WIDTH * HEIGHT * 4) as usize);
+
+        // The rectangle covers the center, not the corners.
+        assert_ne!(pixel(&pixels, WIDTH / 2, HEIGHT / 2), &[0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 0, 0), &[0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, WIDTH - 1, HEIGHT - 1), &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_snapshot_of_empty_mesh_is_cleared() {
+        let pixels = snapshot::render_snapshot(&Empty, WIDTH, HEIGHT, wgpu::Color::WHITE).unwrap();
+        assert!(pixels.iter().all(|&b| b == 255));
+    }
+
+    #[test]
+    fn test_png_structure() {
+        let pixels: Vec<u8> = (0..2 * 3 * 4).map(|i| i as u8).collect();
+        let png = snapshot::encode_png(2, 3, &pixels);
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..20], &2u32.to_be_bytes());
+        assert_eq!(&png[20..24], &3u32.to_be_bytes());
+        assert_eq!(&png[24..29], &[8, 6, 0, 0, 0]);
+        // The CRC of the header chunk.
+        assert_eq!(&png[29..33], &[0xb9, 0xea, 0xde, 0x81]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        // A single stored block with the filtered rows.
+        assert_eq!(&png[37..41], b"IDAT");
+        let stored = &png[41 + 2..];
+        assert_eq!(stored[0], 1);
+        assert_eq!(&stored[1..5], &[27, 0, !27, 0xff]);
+        assert_eq!(stored[5], 0);
+        assert_eq!(&stored[6..14], &pixels[..8]);
+        assert_eq!(stored[14], 0);
+    }
+
+    #[test]
+    fn test_png_written_to_file() {
+        let path = std::env::temp_dir().join("dragonfly_test_snapshot.png");
+        let pixels = vec![128; 4 * 4 * 4];
+
+        snapshot::write_png(&path, 4, 4, &pixels).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            snapshot::encode_png(4, 4, &pixels)
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_with_bundled_shader() {
+        // The bundled source compiled as a custom shader draws the same
+        // pixels as the built-in pipeline.
+        let source = include_str!("../shaders/shader.wgsl");
+        for figure in [Figure::Triangle, Figure::Circle(32), Figure::Overlap] {
+            let built_in =
+                snapshot::render_snapshot(&figure, WIDTH, HEIGHT, wgpu::Color::BLACK).unwrap();
+            let custom = snapshot::render_snapshot_with_shader(
+                &figure,
+                WIDTH,
+                HEIGHT,
+                wgpu::Color::BLACK,
+                source,
+            )
+            .unwrap();
+            assert!(built_in == custom, "{:?}", figure);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_with_custom_shader() {
+        // Every fragment of the figure is drawn in opaque white.
+        let source = include_str!("../shaders/shader.wgsl").replacen(
+            "fn fs_main(",
+            "fn fs_main_original(",
+            1,
+        )
+            + "@fragment fn fs_main() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }";
+        let pixels = snapshot::render_snapshot_with_shader(
+            &Figure::Rectangle,
+            WIDTH,
+            HEIGHT,
+            wgpu::Color::BLACK,
+            &source,
+        )
+        .unwrap();
+        assert_eq!(pixel(&pixels, WIDTH / 2, HEIGHT / 2), [255, 255, 255, 255]);
+        assert_eq!(pixel(&pixels, 0, 0), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_snapshot_with_invalid_shader() {
+        let result = snapshot::render_snapshot_with_shader(
+            &Figure::Triangle,
+            WIDTH,
+            HEIGHT,
+            wgpu::Color::BLACK,
+            "not WGSL",
+        );
+        assert!(matches!(result, Err(DragonflyError::Shader(_))));
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::{
+        spec::{FigureSpec, SceneSpec},
+        vertex::{Figure, Mesh},
+    };
+
+    /// Returns one figure of each variant.
+    fn every_figure() -> Vec<Figure> {
+        vec![
+            Figure::Triangle,
+            Figure::Pentagon,
+            Figure::Rectangle,
+            Figure::Trapezoid,
+            Figure::Parallelogram,
+            Figure::Circle(32),
+            Figure::Icosphere(2),
+            Figure::Torus {
+                major_radius: 0.35,
+                minor_radius: 0.15,
+                major_segments: 12,
+                minor_segments: 8,
+            },
+            Figure::Cylinder { segments: 16 },
+            Figure::Cone { segments: 16 },
+            Figure::Pyramid,
+            Figure::Tetrahedron,
+            Figure::Overlap,
+            Figure::Hexagon,
+            Figure::Hexagram,
+            Figure::Diamond,
+            Figure::Cross,
+            Figure::default_arrow(),
+            Figure::RoundedRectangle {
+                width: 0.9,
+                height: 0.6,
+                corner_radius: 0.15,
+                segments: 4,
+            },
+            Figure::Heart { segments: 16 },
+            Figure::Plane { subdivisions: 4 },
+            Figure::Lathe {
+                profile: vec![[0.0, -0.5], [0.4, 0.0], [0.0, 0.5]],
+                segments: 12,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_figure_spec_round_trip() {
+        for figure in every_figure() {
+            let counts = (figure.get_vertices().len(), figure.get_indices().len());
+            let spec = FigureSpec::from(figure);
+            let figure = Figure::from(spec.clone());
+            assert_eq!(
+                (figure.get_vertices().len(), figure.get_indices().len()),
+                counts
+            );
+            assert_eq!(FigureSpec::from(figure), spec);
+        }
+        assert_eq!(
+            FigureSpec::from(Figure::Circle(64)),
+            FigureSpec::Circle { segments: 64 }
+        );
+        assert_eq!(Figure::from(FigureSpec::Pyramid), Figure::Pyramid);
+    }
+
+    #[test]
+    fn test_scene_spec_default_empty() {
+        assert!(SceneSpec::default().figures.is_empty());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_scene_json_round_trip() {
+        let figures = every_figure();
+        let counts: Vec<(usize, usize)> = figures
+            .iter()
+            .map(|figure| (figure.get_vertices().len(), figure.get_indices().len()))
+            .collect();
+
+        let scene = SceneSpec {
+            figures: figures.into_iter().map(FigureSpec::from).collect(),
+        };
+        let loaded = SceneSpec::from_json(&scene.to_json()).unwrap();
+        assert_eq!(loaded, scene);
+
+        for (spec, count) in loaded.figures.into_iter().zip(counts) {
+            let figure = Figure::from(spec);
+            assert_eq!(
+                (figure.get_vertices().len(), figure.get_indices().len()),
+                count
+            );
+        }
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_scene_json_format() {
+        let scene = SceneSpec::from_json(
+            r#"{"figures": [{"type": "circle", "segments": 8}, {"type": "pyramid"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            scene.figures,
+            vec![FigureSpec::Circle { segments: 8 }, FigureSpec::Pyramid]
+        );
+        assert!(SceneSpec::from_json(r#"{"figures": [{"type": "blob"}]}"#).is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_figure_json_round_trip() {
+        for figure in every_figure() {
+            assert_eq!(Figure::from_json(&figure.to_json()).unwrap(), figure);
+        }
+        assert_eq!(
+            Figure::from_json(&Figure::Circle(17).to_json()).unwrap(),
+            Figure::Circle(17)
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_figure_json_malformed() {
+        assert!(Figure::from_json("").is_err());
+        assert!(Figure::from_json("{\"type\": \"circle\"").is_err());
+        assert!(Figure::from_json(r#"{"type": "circle"}"#).is_err());
+        assert!(Figure::from_json(r#"{"type": "circle", "segments": -1}"#).is_err());
+        assert!(Figure::from_json(r#"{"type": "blob"}"#).is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_mesh_data_json_round_trip() {
+        let mesh = Figure::Pyramid.mesh_data();
+        let json = serde_json::to_string(&mesh).unwrap();
+        let loaded: dragonfly::vertex::MeshData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.indices, mesh.indices);
+        assert_eq!(loaded.vertices.len(), mesh.vertices.len());
+        for (a, b) in loaded.vertices.iter().zip(&mesh.vertices) {
+            assert_eq!(
+                (a.position, a.color, a.normal, a.tex_coords),
+                (b.position, b.color, b.normal, b.tex_coords)
+            );
+        }
+    }
+}
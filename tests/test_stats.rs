@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+
+    use std::f32::consts::PI;
+
+    use dragonfly::vertex::{Figure, Mesh, MeshData, MeshStats, Vertex};
+
+    #[test]
+    fn test_rectangle_stats() {
+        let stats = Figure::Rectangle.stats();
+        assert_eq!(stats.vertices, 4);
+        assert_eq!(stats.triangles, 2);
+        assert!((stats.area - 0.5).abs() < 1e-6, "{}", stats);
+        assert!((stats.perimeter - 3.0).abs() < 1e-6, "{}", stats);
+    }
+
+    #[test]
+    fn test_triangle_area() {
+        let (min, max) = Figure::Triangle.bounds();
+        let (base, height) = (max[0] - min[0], max[1] - min[1]);
+        let area = Figure::Triangle.stats().area;
+        assert!((area - 0.5 * base * height).abs() < 1e-6, "{}", area);
+    }
+
+    #[test]
+    fn test_circle_area_approaches_disk() {
+        let disk = PI * 0.25;
+        let area = Figure::Circle(256).stats().area;
+        assert!((area - disk).abs() / disk < 0.01, "{}", area);
+        assert!(Figure::Circle(8).stats().area < area);
+    }
+
+    #[test]
+    fn test_closed_mesh_stats() {
+        // A closed mesh has no boundary, and its area is the area of its
+        // faces whatever their orientation.
+        let stats = Figure::Icosphere(3).stats();
+        assert_eq!(stats.perimeter, 0.0);
+        assert!((stats.area - PI).abs() / PI < 0.02, "{}", stats);
+    }
+
+    #[test]
+    fn test_degenerate_stats_finite() {
+        assert_eq!(MeshData::default().stats(), MeshStats::default());
+
+        let point = MeshData {
+            vertices: vec![Vertex::at(0.1, 0.2, 0.0); 3],
+            indices: vec![0, 1, 2],
+        };
+        let stats = point.stats();
+        assert_eq!((stats.area, stats.perimeter), (0.0, 0.0));
+
+        // The triangles with an index out of range are ignored.
+        let broken = MeshData {
+            vertices: vec![Vertex::at(0.0, 0.0, 0.0), Vertex::at(1.0, 0.0, 0.0)],
+            indices: vec![0, 1, 7],
+        };
+        let stats = broken.stats();
+        assert_eq!(stats.triangles, 0);
+        assert!(stats.area.is_finite() && stats.perimeter.is_finite());
+    }
+
+    #[test]
+    fn test_stats_display() {
+        assert_eq!(
+            Figure::Rectangle.stats().to_string(),
+            "4 vertices, 2 triangles, area 0.5000, perimeter 3.0000"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{fs::File, path::Path};
+
+    use dragonfly::vertex::{Mesh, MeshData, StlError};
+
+    fn fixture(name: &str) -> File {
+        File::open(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name),
+        )
+        .unwrap()
+    }
+
+    /// Returns a binary STL file with the given header and triangles.
+    fn binary_stl(header: &[u8], triangles: &[[[f32; 3]; 3]]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.resize(80, 0);
+        bytes.extend((triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            bytes.extend([0u8; 12]);
+            for c in triangle.as_flattened() {
+                bytes.extend(c.to_le_bytes());
+            }
+            bytes.extend([0u8; 2]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_binary_fixture() {
+        let tetrahedron = MeshData::from_stl(fixture("tetrahedron.stl")).unwrap();
+        assert_eq!(tetrahedron.indices.len(), 4 * 3);
+        assert_eq!(tetrahedron.vertices.len(), 4);
+        assert!(tetrahedron.validate().is_ok());
+    }
+
+    #[test]
+    fn test_ascii_fixture() {
+        let cube = MeshData::from_stl(fixture("cube.stl")).unwrap();
+        assert_eq!(cube.indices.len(), 12 * 3);
+        assert_eq!(cube.vertices.len(), 8);
+        assert!(cube.validate().is_ok());
+        assert_eq!(cube.bounds(), ([-0.5; 3], [0.5; 3]));
+    }
+
+    #[test]
+    fn test_binary_header_starting_with_solid() {
+        let triangle = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let bytes = binary_stl(b"solid but binary", &[triangle]);
+        let mesh = MeshData::from_stl(bytes.as_slice()).unwrap();
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let triangle = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let mut bytes = binary_stl(b"", &[triangle]);
+        bytes.pop();
+        assert!(matches!(
+            MeshData::from_stl(bytes.as_slice()),
+            Err(StlError::Truncated)
+        ));
+        assert!(matches!(
+            MeshData::from_stl(&b"tiny"[..]),
+            Err(StlError::Truncated)
+        ));
+
+        // 21,846 triangles need more than 65,536 vertices before welding.
+        let mut bytes = binary_stl(b"", &[]);
+        bytes[80..84].copy_from_slice(&21_846u32.to_le_bytes());
+        assert!(matches!(
+            MeshData::from_stl(bytes.as_slice()),
+            Err(StlError::TooManyTriangles(21_846))
+        ));
+
+        let ascii =
+            "solid bad\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nendloop\n";
+        assert!(matches!(
+            MeshData::from_stl(ascii.as_bytes()),
+            Err(StlError::InvalidLine { line: 6 })
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{subdivide, Figure, Mesh, MeshData, SubdivisionError, Vertex};
+
+    /// Returns the smallest and largest distances of the vertices from the
+    /// center of the mesh.
+    fn radius_range(vertices: &[Vertex]) -> (f32, f32) {
+        let center = MeshData {
+            vertices: vertices.to_vec(),
+            indices: Vec::new(),
+        }
+        .centroid();
+        vertices
+            .iter()
+            .map(|vertex| {
+                (0..3)
+                    .map(|i| (vertex.position[i] - center[i]).powi(2))
+                    .sum::<f32>()
+                    .sqrt()
+            })
+            .fold((f32::INFINITY, 0.0), |(min, max), r| {
+                (min.min(r), max.max(r))
+            })
+    }
+
+    #[test]
+    fn test_subdivide_triangle() {
+        let triangle = Figure::Triangle;
+        let (vertices, indices) =
+            subdivide(&triangle.get_vertices(), &triangle.get_indices(), 1).unwrap();
+        assert_eq!(vertices.len(), 6);
+        assert_eq!(indices.len(), 4 * 3);
+
+        let mesh = MeshData { vertices, indices };
+        assert!(mesh.validate().is_ok());
+        // The winding is kept, so the mesh still faces the camera.
+        assert!(mesh.get_signed_area() > 0.0);
+    }
+
+    #[test]
+    fn test_subdivide_interpolates_colors() {
+        let triangle = Figure::Triangle;
+        let (vertices, _) =
+            subdivide(&triangle.get_vertices(), &triangle.get_indices(), 1).unwrap();
+        // The midpoint of the red and green corners is yellow-ish.
+        let [r, g, b, a] = vertices[3].color;
+        assert!(
+            (r - 0.5).abs() < 1e-6 && (g - 0.5).abs() < 1e-6,
+            "{:?}",
+            vertices[3]
+        );
+        assert_eq!((b, a), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_subdivide_zero_iterations() {
+        let pentagon = Figure::Pentagon;
+        let (vertices, indices) =
+            subdivide(&pentagon.get_vertices(), &pentagon.get_indices(), 0).unwrap();
+        assert_eq!(vertices.len(), 5);
+        assert_eq!(indices, pentagon.get_indices());
+    }
+
+    #[test]
+    fn test_subdivide_rounds_icosahedron() {
+        let icosahedron = Figure::Icosphere(0);
+        let (vertices, indices) =
+            subdivide(&icosahedron.get_vertices(), &icosahedron.get_indices(), 2).unwrap();
+        assert_eq!(indices.len(), 16 * icosahedron.get_indices().len());
+
+        // The vertices get closer to a sphere.
+        let (min, max) = radius_range(&vertices);
+        assert!((max - min) / max < 0.05, "{} {}", min, max);
+        assert!(vertices
+            .iter()
+            .all(|vertex| vertex.position.iter().all(|c| c.is_finite())));
+    }
+
+    #[test]
+    fn test_subdivide_flat_stays_flat() {
+        let pentagon = Figure::Pentagon;
+        let (vertices, indices) =
+            subdivide(&pentagon.get_vertices(), &pentagon.get_indices(), 3).unwrap();
+        assert!(vertices.iter().all(|vertex| vertex.position[2] == 0.0));
+        assert_eq!(indices.len(), 64 * pentagon.get_indices().len());
+    }
+
+    #[test]
+    fn test_subdivide_too_many_vertices() {
+        let plane = Figure::Plane { subdivisions: 200 };
+        let result = subdivide(&plane.get_vertices(), &plane.get_indices(), 1);
+        assert!(
+            matches!(result, Err(SubdivisionError::TooManyVertices { vertices }) if vertices > 65536),
+            "{:?}",
+            result.map(|(vertices, _)| vertices.len())
+        );
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::vertex::{Figure, SvgError};
+
+    /// Returns whether the document is well-formed XML: every tag is closed
+    /// in order and every attribute value is quoted.
+    fn is_well_formed(document: &str) -> bool {
+        let mut open: Vec<&str> = Vec::new();
+        let mut rest = document.trim();
+        while let Some(start) = rest.find('<') {
+            if !rest[..start].trim().is_empty() && open.is_empty() {
+                return false;
+            }
+            let Some(end) = rest[start..].find('>') else {
+                return false;
+            };
+            let tag = &rest[start + 1..start + end];
+            rest = &rest[start + end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                if open.pop() != Some(name.trim()) {
+                    return false;
+                }
+                continue;
+            }
+            let (body, self_closing) = match tag.strip_suffix('/') {
+                Some(body) => (body, true),
+                None => (tag, false),
+            };
+            let mut parts = body.splitn(2, char::is_whitespace);
+            let name = parts.next().unwrap_or("");
+            let attributes = parts.next().unwrap_or("");
+            if name.is_empty() || attributes.matches('"').count() % 2 != 0 {
+                return false;
+            }
+            if !self_closing {
+                open.push(name);
+            }
+        }
+        open.is_empty() && rest.trim().is_empty()
+    }
+
+    fn path_of(svg: &str) -> &str {
+        let start = svg.find(" d=\"").unwrap() + 4;
+        let end = start + svg[start..].find('"').unwrap();
+        &svg[start..end]
+    }
+
+    #[test]
+    fn test_rectangle_svg() {
+        let svg = Figure::Rectangle.to_svg(100).unwrap();
+        assert!(is_well_formed(&svg), "{}", svg);
+
+        // The path moves to a corner, draws three lines and closes with the
+        // fourth.
+        let path = path_of(&svg);
+        assert!(path.starts_with('M'), "{}", path);
+        assert_eq!(path.matches('L').count(), 3, "{}", path);
+        assert!(path.ends_with('Z'), "{}", path);
+        for corner in ["0.00 25.00", "100.00 25.00", "100.00 75.00", "0.00 75.00"] {
+            assert!(path.contains(corner), "{} not in {}", corner, path);
+        }
+    }
+
+    #[test]
+    fn test_circle_svg_polygon() {
+        let svg = Figure::Circle(32).to_svg(64).unwrap();
+        assert!(is_well_formed(&svg), "{}", svg);
+        assert_eq!(path_of(&svg).matches('L').count(), 31);
+    }
+
+    #[test]
+    fn test_svg_fill_average_color() {
+        // The triangle has a red, a green and a blue corner.
+        let svg = Figure::Triangle.to_svg(10).unwrap();
+        assert!(svg.contains(r#"fill="rgb(85,85,85)""#), "{}", svg);
+        assert!(svg.contains(r#"width="10""#), "{}", svg);
+    }
+
+    #[test]
+    fn test_svg_flat_figures() {
+        for figure in [
+            Figure::Triangle,
+            Figure::Pentagon,
+            Figure::Hexagon,
+            Figure::Hexagram,
+            Figure::Diamond,
+            Figure::Cross,
+            Figure::default_arrow(),
+            Figure::Trapezoid,
+            Figure::Parallelogram,
+            Figure::Heart { segments: 64 },
+        ] {
+            let svg = figure.to_svg(256).unwrap();
+            assert!(is_well_formed(&svg), "{:?}: {}", figure, svg);
+        }
+    }
+
+    #[test]
+    fn test_svg_3d_figures() {
+        for figure in [Figure::Pyramid, Figure::Tetrahedron, Figure::Icosphere(1)] {
+            assert_eq!(figure.to_svg(100), Err(SvgError::NotFlat), "{:?}", figure);
+        }
+    }
+
+    #[test]
+    fn test_well_formed_checker() {
+        assert!(is_well_formed(r#"<svg a="1"><path d="M0 0Z"/></svg>"#));
+        assert!(!is_well_formed(r#"<svg><path d="M0 0Z"></svg>"#));
+        assert!(!is_well_formed(r#"<svg a="1></svg>"#));
+    }
+}
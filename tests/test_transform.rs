@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+
+    use std::f32::consts::FRAC_PI_2;
+
+    use dragonfly::vertex::{Figure, Mesh, MeshData, Vertex};
+
+    /// Returns the signed area of each triangle, positive when it is
+    /// counter-clockwise.
+    fn signed_areas(mesh: &MeshData) -> Vec<f32> {
+        mesh.indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[t[k] as usize].position);
+                (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_rotated_z_quarter_turn() {
+        let triangle = Figure::Triangle.mesh_data();
+        let rotated = triangle.clone().rotated_z(FRAC_PI_2);
+
+        assert_eq!(rotated.indices, triangle.indices);
+        for (rotated, original) in rotated.vertices.iter().zip(&triangle.vertices) {
+            let [x, y, z] = original.position;
+            assert_close(rotated.position, [-y, x, z]);
+            assert_eq!(rotated.color, original.color);
+        }
+    }
+
+    #[test]
+    fn test_mirror_flips_winding() {
+        let triangle = Figure::Triangle.mesh_data();
+        let mirrored = triangle.clone().scaled_xyz([-1.0, 1.0, 1.0]);
+
+        let [a, b, c] = [0, 1, 2].map(|k| triangle.indices[k]);
+        assert_eq!(mirrored.indices, vec![a, c, b]);
+        assert!(signed_areas(&mirrored).iter().all(|&area| area > 0.0));
+        for (mirrored, original) in mirrored.vertices.iter().zip(&triangle.vertices) {
+            let [x, y, z] = original.position;
+            assert_close(mirrored.position, [-x, y, z]);
+        }
+    }
+
+    #[test]
+    fn test_uniform_negative_scale_keeps_winding() {
+        // A uniform scale of -1 is a half turn in the XY plane.
+        let rectangle = Figure::Rectangle.mesh_data();
+        let scaled = rectangle.clone().scaled(-1.0);
+        assert_eq!(scaled.indices, rectangle.indices);
+        assert!(signed_areas(&scaled).iter().all(|&area| area > 0.0));
+    }
+
+    #[test]
+    fn test_translated_and_scaled() {
+        let mesh = MeshData {
+            vertices: vec![Vertex {
+                position: [1.0, 2.0, 3.0],
+                color: [0.1, 0.2, 0.3, 1.0],
+                normal: [0.0, 0.0, 1.0],
+                tex_coords: [0.0, 0.0],
+            }],
+            indices: Vec::new(),
+        };
+
+        let moved = mesh.clone().scaled(2.0).translated([0.5, -0.5, 1.0]);
+        assert_close(moved.vertices[0].position, [2.5, 3.5, 7.0]);
+        assert_eq!(moved.vertices[0].color, [0.1, 0.2, 0.3, 1.0]);
+
+        let stretched = mesh.scaled_xyz([1.0, 0.5, 0.0]);
+        assert_close(stretched.vertices[0].position, [1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_mesh_data_is_a_mesh() {
+        let circle = Figure::Circle(16);
+        let data = circle.mesh_data();
+        assert_eq!(data.get_indices(), circle.get_indices());
+        assert_eq!(data.get_vertices().len(), circle.get_vertices().len());
+
+        let from_tuple = MeshData::from((data.vertices.clone(), data.indices.clone()));
+        assert_eq!(from_tuple.indices, data.indices);
+    }
+
+    #[test]
+    fn test_transformed_normals() {
+        let close = |a: [f32; 3], b: [f32; 3]| (0..3).all(|i| (a[i] - b[i]).abs() < 1e-5);
+
+        // A flat figure keeps facing the viewer, even mirrored.
+        for mesh in [
+            Figure::Pentagon.mesh_data().rotated_z(1.0),
+            Figure::Pentagon.mesh_data().scaled_xyz([-2.0, 0.5, 1.0]),
+            Figure::Pentagon.mesh_data().translated([1.0, 2.0, 3.0]),
+        ] {
+            assert!(mesh
+                .vertices
+                .iter()
+                .all(|v| close(v.normal, [0.0, 0.0, 1.0])));
+        }
+
+        // A normal along X turns with the rotation, and leans away from a
+        // stretched axis.
+        let mut mesh = Figure::Triangle.mesh_data();
+        mesh.vertices[0].normal = [1.0, 0.0, 0.0];
+        let rotated = mesh.clone().rotated_z(FRAC_PI_2);
+        assert!(close(rotated.vertices[0].normal, [0.0, 1.0, 0.0]));
+
+        mesh.vertices[0].normal = [0.6, 0.8, 0.0];
+        let stretched = mesh.scaled_xyz([2.0, 1.0, 1.0]);
+        let [x, y, _] = stretched.vertices[0].normal;
+        assert!(x < 0.6 && y > 0.8);
+        assert!(((x * x + y * y).sqrt() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_transform_vertices() {
+        let scale = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let scaled = Figure::Triangle.transform_vertices(&scale);
+        assert_eq!(scaled[0].position, [0.0, 1.0, 0.0]);
+        assert_eq!(scaled[1].position, [-1.0, -1.0, 0.0]);
+        assert_eq!(scaled[0].color, Figure::Triangle.get_vertices()[0].color);
+
+        // The translation is in the last column, and the positions are
+        // divided by w.
+        let translate_and_halve = [
+            [1.0, 0.0, 0.0, 0.5],
+            [0.0, 1.0, 0.0, -0.5],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 2.0],
+        ];
+        let moved = Figure::Triangle.transform_vertices(&translate_and_halve);
+        assert_close(moved[0].position, [0.25, 0.0, 0.0]);
+        assert_close(moved[2].position, [0.5, -0.5, 0.0]);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+
+    use dragonfly::uniform::{TimeUniform, TransformUniform};
+    use wgpu::util::DeviceExt;
+
+    fn create_test_device() -> (wgpu::Device, wgpu::Queue) {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .unwrap();
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_time_uniform_size() {
+        assert_eq!(std::mem::size_of::<TimeUniform>() % 16, 0);
+    }
+
+    #[test]
+    fn test_transform_uniform_defaults_to_identity() {
+        assert_eq!(std::mem::size_of::<TransformUniform>(), 64);
+        let transform = TransformUniform::default();
+        assert_eq!(transform.transform, TransformUniform::IDENTITY);
+        for (i, column) in transform.transform.iter().enumerate() {
+            assert_eq!(column[i], 1.0);
+            assert_eq!(column.iter().sum::<f32>(), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_time_uniform_buffer_write() {
+        let (device, queue) = create_test_device();
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let mut time_uniform = TimeUniform::new();
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Time Buffer"),
+            contents: bytemuck::bytes_of(&time_uniform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        time_uniform.update(1.5, 0.016);
+        queue.write_buffer(&buffer, 0, bytemuck::bytes_of(&time_uniform));
+        queue.submit(None);
+
+        let error = pollster::block_on(device.pop_error_scope());
+        assert!(error.is_none(), "{:?}", error);
+    }
+}
@@ -1,7 +1,31 @@
 #[cfg(test)]
 mod tests {
 
-    use dragonfly::vertex::{Figure, Mesh};
+    use dragonfly::vertex::{
+        self, boundary_loop, compute_normals, generate_planar_uvs, Figure, Indices,
+        InvalidFigureIndex, Mesh, MeshData, Vertex, FLAT_NORMAL, MAX_CONE_SEGMENTS,
+        MAX_CYLINDER_SEGMENTS, MAX_HEART_SEGMENTS, MAX_PLANE_SUBDIVISIONS,
+        MAX_ROUNDED_RECTANGLE_SEGMENTS, MAX_TORUS_SEGMENTS,
+    };
+
+    /// Asserts that every triangle of a convex figure centered at the origin
+    /// is wound counter-clockwise when seen from the outside.
+    fn assert_outward_winding(figure: &Figure) {
+        let vertices = figure.get_vertices();
+        for triangle in figure.get_indices().chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| vertices[triangle[k] as usize].position);
+            let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let normal = [
+                ab[1] * ac[2] - ab[2] * ac[1],
+                ab[2] * ac[0] - ab[0] * ac[2],
+                ab[0] * ac[1] - ab[1] * ac[0],
+            ];
+            let center = [0, 1, 2].map(|k| (a[k] + b[k] + c[k]) / 3.0);
+            let dot: f32 = (0..3).map(|k| normal[k] * center[k]).sum();
+            assert!(dot > 0.0, "{:?} is wound inward", triangle);
+        }
+    }
 
     #[test]
     fn test_triangle_vertices_and_indices() {
@@ -56,4 +80,1023 @@ mod tests {
         assert_eq!(vertices.len(), 66);
         assert_eq!(indices.len(), 192);
     }
+
+    #[test]
+    fn test_icosphere_vertices_and_indices() {
+        for level in 0..4 {
+            let figure = Figure::Icosphere(level);
+            let vertices = figure.get_vertices();
+            let indices = figure.get_indices();
+
+            // Euler characteristic: V - E + F = 2 with E = 3F / 2.
+            let faces = 20 * 4_usize.pow(level as u32);
+            let edges = 3 * faces / 2;
+            assert_eq!(vertices.len() + faces - edges, 2);
+            assert_eq!(vertices.len(), 10 * 4_usize.pow(level as u32) + 2);
+            assert_eq!(indices.len(), 3 * faces);
+        }
+    }
+
+    #[test]
+    fn test_icosphere_vertices_on_sphere() {
+        let figure = Figure::Icosphere(3);
+        assert_outward_winding(&figure);
+        for vertex in figure.get_vertices() {
+            let [x, y, z] = vertex.position;
+            let length = (x * x + y * y + z * z).sqrt();
+            assert!((length - 0.5).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_icosphere_level_clamped() {
+        let figure = Figure::Icosphere(7);
+        let vertices = figure.get_vertices();
+        assert_eq!(vertices.len(), 40962);
+        assert!(vertices.len() <= u16::MAX as usize);
+    }
+
+    #[test]
+    fn test_torus_vertices_and_indices() {
+        let figure = Figure::Torus {
+            major_radius: 0.35,
+            minor_radius: 0.15,
+            major_segments: 32,
+            minor_segments: 12,
+        };
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 32 * 12);
+        assert_eq!(indices.len(), 32 * 12 * 6);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn test_torus_segments_clamped() {
+        // Too many segments are clamped so that the indices do not wrap.
+        let figure = Figure::Torus {
+            major_radius: 0.35,
+            minor_radius: 0.15,
+            major_segments: 300,
+            minor_segments: 300,
+        };
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        let max = MAX_TORUS_SEGMENTS as usize;
+        assert_eq!(vertices.len(), max * max);
+        assert_eq!(indices.len(), max * max * 6);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+
+        // The product of huge counts does not overflow.
+        let figure = Figure::Torus {
+            major_radius: 0.35,
+            minor_radius: 0.15,
+            major_segments: u32::MAX,
+            minor_segments: 8,
+        };
+        assert_eq!(figure.get_vertices().len(), max * 8);
+    }
+
+    #[test]
+    fn test_torus_minor_radius_clamped() {
+        let figure = Figure::Torus {
+            major_radius: 0.3,
+            minor_radius: 0.5,
+            major_segments: 16,
+            minor_segments: 8,
+        };
+        for vertex in figure.get_vertices() {
+            // The tube radius must not exceed the ring radius.
+            let [x, y, z] = vertex.position;
+            assert!(z.abs() < 0.3);
+            assert!((x * x + y * y).sqrt() < 0.6);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_vertices_and_indices() {
+        let figure = Figure::Cylinder { segments: 16 };
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 66);
+        assert_eq!(indices.len(), 192);
+        assert_outward_winding(&figure);
+        assert_outward_winding(&figure);
+    }
+
+    #[test]
+    fn test_cone_vertices_and_indices() {
+        let figure = Figure::Cone { segments: 16 };
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 34);
+        assert_eq!(indices.len(), 96);
+        assert_outward_winding(&figure);
+    }
+
+    #[test]
+    fn test_cylinder_and_cone_segments_clamped() {
+        let figure = Figure::Cylinder { segments: 1 };
+        assert_eq!(figure.get_vertices().len(), 14);
+
+        let figure = Figure::Cone { segments: 0 };
+        assert_eq!(figure.get_vertices().len(), 8);
+
+        // Too many segments are clamped so that the indices do not wrap.
+        let figure = Figure::Cylinder { segments: 20_000 };
+        assert_eq!(
+            figure.get_vertices().len(),
+            4 * MAX_CYLINDER_SEGMENTS as usize + 2
+        );
+        assert_eq!(figure.validate(), Ok(()));
+
+        let figure = Figure::Cone { segments: u32::MAX };
+        assert_eq!(figure.get_vertices().len(), u16::MAX as usize + 1);
+        assert_eq!(figure.get_indices().len(), 6 * MAX_CONE_SEGMENTS as usize);
+        assert_eq!(figure.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_pyramid_vertices_and_indices() {
+        let figure = Figure::Pyramid;
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 16);
+        assert_eq!(indices.len(), 18);
+        assert_outward_winding(&figure);
+    }
+
+    #[test]
+    fn test_tetrahedron_vertices_and_indices() {
+        let figure = Figure::Tetrahedron;
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 12);
+        assert_eq!(indices.len(), 12);
+        assert_outward_winding(&figure);
+    }
+
+    /// Asserts that every triangle of a flat figure is wound
+    /// counter-clockwise when seen from the positive Z axis.
+    fn assert_front_facing(figure: &Figure) {
+        let vertices = figure.get_vertices();
+        for triangle in figure.get_indices().chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| vertices[triangle[k] as usize].position);
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+            assert!(area > 0.0, "triangle {:?} is not front-facing", triangle);
+        }
+    }
+
+    #[test]
+    fn test_plane_without_subdivisions_matches_rectangle() {
+        let plane = Figure::Plane { subdivisions: 0 };
+        let rectangle = Figure::Rectangle;
+        let vertices = plane.get_vertices();
+        let indices = plane.get_indices();
+        assert_eq!(vertices.len(), rectangle.get_vertices().len());
+        assert_eq!(indices.len(), rectangle.get_indices().len());
+        assert_eq!(
+            boundary_loop(&vertices, &indices).unwrap().len(),
+            boundary_loop(&rectangle.get_vertices(), &rectangle.get_indices())
+                .unwrap()
+                .len()
+        );
+        assert_front_facing(&plane);
+        assert_front_facing(&rectangle);
+    }
+
+    #[test]
+    fn test_plane_subdivisions() {
+        let figure = Figure::Plane { subdivisions: 16 };
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 18 * 18);
+        assert_eq!(indices.len(), 17 * 17 * 6);
+        assert_front_facing(&figure);
+
+        // The vertices lie on a regular lattice, row by row, and their color
+        // encodes their grid coordinates.
+        for (k, vertex) in vertices.iter().enumerate() {
+            let (i, j) = (k / 18, k % 18);
+            let [x, y, z] = vertex.position;
+            assert!((x - (j as f32 / 17.0 - 0.5)).abs() < 1e-6);
+            assert!((y - (0.5 - i as f32 / 17.0)).abs() < 1e-6);
+            assert_eq!(z, 0.0);
+            assert_eq!(vertex.color, [j as f32 / 17.0, i as f32 / 17.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn test_plane_subdivisions_clamped() {
+        let figure = Figure::Plane {
+            subdivisions: u32::MAX,
+        };
+        let vertices = figure.get_vertices();
+        let grid = MAX_PLANE_SUBDIVISIONS as usize + 2;
+        assert_eq!(vertices.len(), grid * grid);
+        assert_eq!(vertices.len(), u16::MAX as usize + 1);
+        assert!(figure
+            .get_indices()
+            .iter()
+            .all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn test_try_from_index() {
+        for i in 0..Figure::COUNT {
+            assert!(Figure::try_from_index(i).is_some(), "index {}", i);
+        }
+        assert_eq!(Figure::try_from_index(Figure::COUNT), None);
+        assert_eq!(Figure::try_from_index(255), None);
+    }
+
+    #[test]
+    fn test_all_figures_cycle() {
+        assert_eq!(Figure::COUNT as usize, Figure::ALL.len());
+        for (i, figure) in Figure::ALL.iter().enumerate() {
+            assert_eq!(Figure::try_from_index(i as u8).as_ref(), Some(figure));
+            assert_eq!(figure.index(), Some(i as u8));
+            assert_eq!(&figure.next().prev(), figure);
+        }
+
+        // Cycling through every figure returns to the first one.
+        let mut figure = Figure::ALL[0].clone();
+        for _ in 0..Figure::COUNT {
+            figure = figure.next();
+        }
+        assert_eq!(figure, Figure::ALL[0]);
+        assert_eq!(Figure::ALL[0].prev(), Figure::ALL[Figure::ALL.len() - 1]);
+    }
+
+    #[test]
+    fn test_circle_for_resolution() {
+        let segments = |diameter: u32| match Figure::circle_for_resolution(diameter) {
+            Figure::Circle(segments) => segments,
+            figure => panic!("Expected a circle, got {:?}", figure),
+        };
+
+        // Larger circles never get fewer segments.
+        let mut previous = 0;
+        for diameter in (0..10_000).step_by(7).chain([1 << 16, 1 << 20, 1 << 24]) {
+            let current = segments(diameter);
+            assert!(
+                current >= previous,
+                "{} < {} at {}",
+                current,
+                previous,
+                diameter
+            );
+            previous = current;
+        }
+
+        // The chord error stays under half a pixel.
+        for diameter in [50, 500, 4000] {
+            let n = segments(diameter) as f64;
+            let error = diameter as f64 / 2.0 * (1.0 - (std::f64::consts::PI / n).cos());
+            assert!(error < 0.5, "{} at {}", error, diameter);
+        }
+        // A 64-segment circle is enough for a small window.
+        assert!(segments(200) <= 64);
+
+        // The count is clamped at both ends.
+        assert_eq!(segments(0), Figure::MIN_CIRCLE_SEGMENTS);
+        assert_eq!(segments(1), Figure::MIN_CIRCLE_SEGMENTS);
+        assert_eq!(segments(u32::MAX), Figure::MAX_CIRCLE_SEGMENTS);
+        let circle = Figure::circle_for_resolution(u32::MAX);
+        assert!(circle.get_vertices().len() <= u16::MAX as usize + 1);
+        assert!(circle.validate().is_ok());
+    }
+
+    #[test]
+    fn test_next_figure_not_cycled() {
+        let circle = Figure::Circle(7);
+        assert_eq!(circle.index(), None);
+        assert_eq!(circle.next(), Figure::ALL[0]);
+        assert_eq!(circle.prev(), Figure::ALL[Figure::ALL.len() - 1]);
+    }
+
+    #[test]
+    fn test_try_from_u8() {
+        for (i, figure) in Figure::ALL.iter().enumerate() {
+            assert_eq!(Figure::try_from(i as u8).as_ref(), Ok(figure));
+        }
+        assert_eq!(Figure::try_from(2), Ok(Figure::Rectangle));
+        assert_eq!(
+            Figure::try_from(Figure::COUNT),
+            Err(InvalidFigureIndex(Figure::COUNT))
+        );
+        assert_eq!(Figure::try_from(u8::MAX), Err(InvalidFigureIndex(u8::MAX)));
+        assert_eq!(
+            InvalidFigureIndex(17).to_string(),
+            "No figure at index 17, the figures range from 0 to 16"
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_get_figure_falls_back_to_triangle() {
+        assert_eq!(Figure::get_figure(1), Figure::Pentagon);
+        assert_eq!(Figure::get_figure(255), Figure::Triangle);
+    }
+
+    #[test]
+    fn test_lathe_semicircle_sphere() {
+        // A semicircle from the south pole to the north pole, in 8 stacks.
+        let stacks = 8;
+        let profile: Vec<[f32; 2]> = (0..=stacks)
+            .map(|s| {
+                let angle = s as f32 * std::f32::consts::PI / stacks as f32;
+                [0.5 * angle.sin(), -0.5 * angle.cos()]
+            })
+            .collect();
+        let figure = Figure::Lathe {
+            profile,
+            segments: 16,
+        };
+
+        // A UV sphere: one vertex per pole, a ring per inner stack, and a
+        // triangle fan at each pole.
+        let vertices = figure.get_vertices();
+        let indices = figure.get_indices();
+        assert_eq!(vertices.len(), 16 * (stacks - 1) + 2);
+        assert_eq!(indices.len(), 6 * 16 * (stacks - 1));
+        assert!(vertex::validate(&vertices, &indices).is_clean());
+        assert_outward_winding(&figure);
+    }
+
+    #[test]
+    fn test_lathe_vertical_profile_cylinder_wall() {
+        let (vertices, indices) = vertex::lathe(&[[0.5, -0.5], [0.5, 0.5]], 16);
+        let cylinder = Figure::Cylinder { segments: 16 };
+        assert_eq!(vertices.len(), 32);
+        assert_eq!(indices.len(), 96);
+
+        // Same wall as the side of the cylinder.
+        let cylinder_vertices = cylinder.get_vertices();
+        for (vertex, expected) in vertices.iter().zip(&cylinder_vertices) {
+            assert_eq!(vertex.position, expected.position);
+        }
+        assert_eq!(indices[..], cylinder.get_indices()[..96]);
+    }
+
+    #[test]
+    fn test_lathe_axis_points_collapse() {
+        // Consecutive points on the axis emit nothing between them.
+        let (vertices, indices) = vertex::lathe(&[[0.0, -0.5], [0.0, 0.0], [0.5, 0.5]], 3);
+        assert_eq!(vertices.len(), 2 + 3);
+        assert_eq!(indices.len(), 3 * 3);
+    }
+
+    #[test]
+    fn test_lathe_fits_u16_indices() {
+        // Too many segments are clamped so that the indices do not wrap.
+        let (vertices, indices) = vertex::lathe(&[[0.0, -0.5], [0.5, 0.0], [0.0, 0.5]], u32::MAX);
+        assert_eq!(vertices.len(), u16::MAX as usize + 1);
+        assert!(vertex::validate(&vertices, &indices).is_clean());
+
+        // A profile too long for 3 segments keeps the rings that fit.
+        let profile: Vec<[f32; 2]> = (0..30_000).map(|i| [0.5, i as f32 * 1e-4]).collect();
+        let (vertices, indices) = vertex::lathe(&profile, 3);
+        assert_eq!(vertices.len(), 3 * 21_845);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn test_merge_offsets_indices() {
+        let (vertices, indices) =
+            vertex::merge(&[&Figure::Triangle, &Figure::Triangle, &Figure::Triangle]).unwrap();
+        assert_eq!(vertices.len(), 9);
+        assert_eq!(indices, (0..9).collect::<Vec<u16>>());
+
+        // The vertices are concatenated in order.
+        let triangle = Figure::Triangle.get_vertices();
+        for (i, vertex) in vertices.iter().enumerate() {
+            assert_eq!(vertex.position, triangle[i % 3].position);
+        }
+    }
+
+    #[test]
+    fn test_merge_mixed_figures() {
+        let circle = Figure::Circle(16);
+        let (vertices, indices) = vertex::merge(&[&Figure::Triangle, &circle]).unwrap();
+        assert_eq!(vertices.len(), 3 + circle.get_vertices().len());
+        assert_eq!(&indices[..3], &[0, 1, 2]);
+        assert!(indices[3..]
+            .iter()
+            .zip(circle.get_indices())
+            .all(|(&merged, i)| merged == i + 3));
+
+        let (vertices, indices) = vertex::merge(&[]).unwrap();
+        assert!(vertices.is_empty() && indices.is_empty());
+    }
+
+    #[test]
+    fn test_merge_overflow_rejected() {
+        // Each circle has 1026 vertices, 64 of them exceed the u16 indices.
+        let circle = Figure::Circle(1024);
+        let circles: Vec<&dyn Mesh> = vec![&circle; 64];
+        assert!(vertex::merge(&circles).is_none());
+        assert!(vertex::merge(&circles[..63]).is_some());
+    }
+
+    #[test]
+    fn test_large_circle_indices() {
+        // Every fan triangle goes from the center to two consecutive
+        // vertices, none of the indices being clipped or wrapped.
+        let assert_fan = |indices: Vec<u32>, num_segments: u32| {
+            assert_eq!(indices.len(), 3 * num_segments as usize);
+            for (i, triangle) in indices.chunks(3).enumerate() {
+                let i = i as u32 + 1;
+                assert_eq!(triangle, [0, i, i + 1]);
+            }
+        };
+
+        // 30,000 segments still fit in 16-bit indices.
+        let indices = Figure::Circle(30_000).get_index_data();
+        assert_eq!(indices.format(), wgpu::IndexFormat::Uint16);
+        assert_fan(indices.to_u32(), 30_000);
+
+        // 70,000 segments need 32-bit indices.
+        let circle = Figure::Circle(70_000);
+        let indices = circle.get_index_data();
+        assert!(matches!(indices, Indices::U32(_)));
+        assert_eq!(indices.format(), wgpu::IndexFormat::Uint32);
+        assert_eq!(indices.as_bytes().len(), 4 * indices.len());
+        assert_eq!(
+            indices.to_u32().into_iter().max(),
+            Some(circle.get_vertices().len() as u32 - 1)
+        );
+        assert_fan(indices.to_u32(), 70_000);
+    }
+
+    #[test]
+    fn test_small_figures_use_u16_indices() {
+        for figure in [Figure::Triangle, Figure::Circle(64), Figure::Icosphere(2)] {
+            assert_eq!(figure.get_index_data(), Indices::U16(figure.get_indices()));
+        }
+    }
+
+    #[test]
+    fn test_weld_circle_seam() {
+        let circle = Figure::Circle(64);
+        let (vertices, indices) = vertex::weld(&circle.get_vertices(), &circle.get_indices(), 1e-5);
+        assert_eq!(circle.get_vertices().len(), 66);
+        assert_eq!(vertices.len(), 65);
+
+        // The last triangle closes the fan on the first rim vertex.
+        assert_eq!(indices.len(), circle.get_indices().len());
+        assert_eq!(&indices[indices.len() - 3..], &[0, 64, 1]);
+        assert!(vertex::validate(&vertices, &indices).is_clean());
+    }
+
+    #[test]
+    fn test_weld_keeps_distinct_colors() {
+        let mut vertices = Figure::Triangle.get_vertices();
+        let mut twin = vertices[0];
+        twin.position[0] += 1e-7;
+        vertices.push(twin);
+        twin.color = [0.0, 0.0, 0.0, 1.0];
+        vertices.push(twin);
+
+        let (welded, indices) = vertex::weld(&vertices, &[0, 1, 2, 3, 2, 4, 9], 1e-5);
+        assert_eq!(welded.len(), 4);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3, 8]);
+    }
+
+    #[test]
+    fn test_weld_large_mesh() {
+        // Two copies of a plane of 29,584 vertices, too many to compare
+        // every pair.
+        let plane = Figure::Plane { subdivisions: 170 };
+        let copies: Vec<&dyn Mesh> = vec![&plane, &plane];
+        let (vertices, indices) = vertex::merge(&copies).unwrap();
+        assert_eq!(vertices.len(), 2 * 172 * 172);
+
+        let (welded, welded_indices) = vertex::weld(&vertices, &indices, 1e-5);
+        assert_eq!(welded.len(), 172 * 172);
+        let half = indices.len() / 2;
+        assert_eq!(welded_indices[..half], welded_indices[half..]);
+        assert_eq!(welded_indices[..half], plane.get_indices()[..]);
+    }
+
+    #[test]
+    fn test_vertex_lerp() {
+        let a = Vertex {
+            position: [0.0, 1.0, -1.0],
+            color: [1.0, 0.0, 0.0, 1.0],
+            normal: [0.0, 0.0, 1.0],
+            tex_coords: [0.0, 0.0],
+        };
+        let b = Vertex {
+            position: [1.0, 3.0, 1.0],
+            color: [0.0, 0.0, 1.0, 1.0],
+            normal: [0.0, 0.0, 1.0],
+            tex_coords: [0.0, 0.0],
+        };
+
+        assert_eq!(Vertex::lerp(&a, &b, 0.0).position, a.position);
+        assert_eq!(Vertex::lerp(&a, &b, 1.0).color, b.color);
+        let middle = Vertex::lerp(&a, &b, 0.5);
+        assert_eq!(middle.position, [0.5, 2.0, 0.0]);
+        assert_eq!(middle.color, [0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_morph_same_vertex_count() {
+        let (from, to) = (Figure::Rectangle, Figure::Trapezoid);
+        let (vertices, indices) = Figure::morph(&from, &to, 0.25);
+        assert_eq!(indices, from.get_indices());
+
+        for ((vertex, a), b) in vertices
+            .iter()
+            .zip(from.get_vertices())
+            .zip(to.get_vertices())
+        {
+            assert_eq!(vertex.position, Vertex::lerp(&a, &b, 0.25).position);
+        }
+
+        let (vertices, indices) = Figure::morph(&from, &to, 1.0);
+        assert_eq!(indices, to.get_indices());
+        for (vertex, b) in vertices.iter().zip(to.get_vertices()) {
+            assert_eq!(vertex.position, b.position);
+        }
+    }
+
+    #[test]
+    fn test_morph_different_vertex_count_switches_halfway() {
+        let (from, to) = (Figure::Triangle, Figure::Pentagon);
+
+        let (vertices, indices) = Figure::morph(&from, &to, 0.49);
+        assert_eq!(vertices.len(), from.get_vertices().len());
+        assert_eq!(indices, from.get_indices());
+
+        let (vertices, indices) = Figure::morph(&from, &to, 0.5);
+        assert_eq!(vertices.len(), to.get_vertices().len());
+        assert_eq!(indices, to.get_indices());
+    }
+
+    #[test]
+    fn test_resampled_morph_endpoints() {
+        let (from, to) = (Figure::Triangle.mesh_data(), Figure::Pentagon.mesh_data());
+
+        let positions = |mesh: &MeshData| -> Vec<[f32; 3]> {
+            mesh.vertices.iter().map(|vertex| vertex.position).collect()
+        };
+        let start = vertex::morph(&from, &to, 0.0);
+        assert_eq!(start.indices, from.indices);
+        assert_eq!(positions(&start), positions(&from));
+        let end = vertex::morph(&from, &to, 1.0);
+        assert_eq!(end.indices, to.indices);
+        assert_eq!(positions(&end), positions(&to));
+
+        // The parameter is clamped.
+        assert_eq!(vertex::morph(&from, &to, -1.0).indices, from.indices);
+        assert_eq!(vertex::morph(&from, &to, 2.0).indices, to.indices);
+    }
+
+    #[test]
+    fn test_resampled_morph_steps_validate() {
+        for from in Figure::ALL {
+            for to in Figure::ALL {
+                let (a, b) = (from.mesh_data(), to.mesh_data());
+                let steps = [0.25, 0.5, 0.75].map(|t| vertex::morph(&a, &b, t));
+                for step in &steps {
+                    assert_eq!(step.validate(), Ok(()), "{} to {}", from, to);
+                }
+                // The steps between flat figures share their indices, to be
+                // written in place.
+                let flat = |mesh: &MeshData| boundary_loop(&mesh.vertices, &mesh.indices).is_some();
+                if flat(&a) && flat(&b) {
+                    assert_eq!(steps[0].indices, steps[2].indices, "{} to {}", from, to);
+                }
+            }
+        }
+
+        // The outlines are interpolated halfway.
+        let (square, diamond) = (Figure::Rectangle.mesh_data(), Figure::Diamond.mesh_data());
+        let middle = vertex::morph(&square, &diamond, 0.5);
+        assert_eq!(middle.vertices.len(), 5);
+        assert_eq!(middle.indices.len(), 12);
+    }
+
+    #[test]
+    fn test_triangle_edge_indices() {
+        let edges = Figure::Triangle.get_edge_indices();
+        assert_eq!(edges.len(), 6);
+        assert_eq!(edges, vec![0, 1, 1, 2, 2, 0]);
+    }
+
+    #[test]
+    fn test_edge_indices_outline_only() {
+        // The diagonal of the rectangle is shared by its two triangles.
+        let edges = Figure::Rectangle.get_edge_indices();
+        assert_eq!(edges.len(), 4 * 2);
+        assert!(edges.chunks(2).all(|edge| edge != [1, 3] && edge != [3, 1]));
+
+        // The spokes of the circle are left out.
+        let edges = Figure::Circle(16).get_edge_indices();
+        assert_eq!(edges.len(), 16 * 2);
+        assert!(!edges.contains(&0));
+    }
+
+    #[test]
+    fn test_wireframe_indices_deduplicated() {
+        // 4 border edges and the shared diagonal.
+        let rectangle = Figure::Rectangle.get_wireframe_indices();
+        assert_eq!(rectangle.len(), 2 * 5);
+
+        // 9 spokes, the seam being duplicated, and 8 rim edges.
+        let circle = Figure::Circle(8).get_wireframe_indices();
+        assert_eq!(circle.len(), 2 * 17);
+
+        let mut edges: Vec<(u16, u16)> = circle
+            .chunks_exact(2)
+            .map(|edge| (edge[0].min(edge[1]), edge[0].max(edge[1])))
+            .collect();
+        edges.sort();
+        edges.dedup();
+        assert_eq!(edges.len(), 17);
+    }
+
+    #[test]
+    fn test_vertex_layout() {
+        let layout = Vertex::desc();
+        assert_eq!(std::mem::size_of::<Vertex>(), 48);
+        assert_eq!(layout.array_stride, 48);
+
+        let offsets: Vec<(u64, u32)> = layout
+            .attributes
+            .iter()
+            .map(|attribute| (attribute.offset, attribute.shader_location))
+            .collect();
+        assert_eq!(offsets, vec![(0, 0), (12, 1), (28, 2), (40, 3)]);
+
+        // The vertices are cast to bytes without padding.
+        let vertices = Figure::Triangle.get_vertices();
+        assert_eq!(bytemuck::cast_slice::<Vertex, u8>(&vertices).len(), 3 * 48);
+    }
+
+    #[test]
+    fn test_bounding_box_and_centroid() {
+        assert_eq!(
+            Figure::Triangle.get_bounding_box(),
+            ([-0.5, -0.5], [0.5, 0.5])
+        );
+
+        // The apex is at the top, the two other vertices at the bottom.
+        let [x, y] = Figure::Triangle.get_centroid();
+        assert_eq!(x, 0.0);
+        assert!((y + 1.0 / 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_area() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+
+        // The rectangle is 1.0 wide and 0.5 high, the triangle has a base
+        // and a height of 1.0.
+        assert!(close(Figure::Rectangle.get_area(), 0.5));
+        assert!(close(Figure::Triangle.get_area(), 0.5));
+        assert!(close(Figure::Triangle.get_signed_area(), 0.5));
+
+        // A reversed figure is wound clockwise.
+        let mut clockwise = Figure::Triangle.mesh_data();
+        clockwise.indices.reverse();
+        assert!(close(clockwise.get_signed_area(), -0.5));
+        assert!(close(clockwise.get_area(), 0.5));
+
+        // The circle converges toward pi * 0.5^2.
+        let disk = std::f32::consts::PI * 0.25;
+        let errors: Vec<f32> = [16, 64, 256]
+            .map(|n| disk - Figure::Circle(n).get_area())
+            .to_vec();
+        assert!(errors.iter().all(|&e| e > 0.0));
+        assert!(errors.windows(2).all(|w| w[1] < w[0]));
+        assert!(errors[2] < 1e-3);
+
+        // A closed mesh has no boundary, hence no area.
+        assert_eq!(Figure::Icosphere(1).get_area(), 0.0);
+    }
+
+    #[test]
+    fn test_hexagon() {
+        let hexagon = Figure::try_from_index(10).unwrap();
+        assert_eq!(hexagon, Figure::Hexagon);
+
+        let vertices = hexagon.get_vertices();
+        assert_eq!(vertices.len(), 7);
+        assert_eq!(hexagon.get_indices().len(), 18);
+        for vertex in &vertices[1..] {
+            let [x, y, _] = vertex.position;
+            assert!(((x * x + y * y).sqrt() - 0.5).abs() < 1e-6);
+        }
+        assert_eq!(vertices[1].position, [0.5, 0.0, 0.0]);
+        assert_eq!(vertices[1].color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(vertices[6].color, [1.0, 0.0, 1.0, 1.0]);
+        assert_front_facing(&hexagon);
+    }
+
+    #[test]
+    fn test_average_edge_length() {
+        let a = Vertex::at(0.0, 0.5, 0.0);
+        let b = Vertex::at(-0.5, -0.5, 0.0);
+        assert!((a.distance_to(&b) - 1.25_f32.sqrt()).abs() < 1e-6);
+        assert_eq!(b.distance_to(&a), a.distance_to(&b));
+        assert_eq!(a.distance_to(&a), 0.0);
+
+        // Two slanted sides of length √1.25 and a base of length 1.
+        let expected = (2.0 * 1.25_f32.sqrt() + 1.0) / 3.0;
+        assert!((Figure::Triangle.average_edge_length() - expected).abs() < 1e-5);
+
+        let empty = MeshData {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+        assert_eq!(empty.average_edge_length(), 0.0);
+    }
+
+    #[test]
+    fn test_hexagram() {
+        let hexagram = Figure::try_from_index(16).unwrap();
+        assert_eq!(hexagram, Figure::Hexagram);
+
+        let vertices = hexagram.get_vertices();
+        let indices = hexagram.get_indices();
+        assert_eq!(vertices.len(), 13);
+        assert_eq!(indices.len(), 36);
+
+        // The tips and the inner corners alternate, from the top tip.
+        let inner_radius = 0.5 / 3.0f32.sqrt();
+        for (i, vertex) in vertices[1..].iter().enumerate() {
+            let [x, y, _] = vertex.position;
+            let radius = if i % 2 == 0 { 0.5 } else { inner_radius };
+            assert!(((x * x + y * y).sqrt() - radius).abs() < 1e-5, "{}", i);
+        }
+        assert!((vertices[1].position[1] - 0.5).abs() < 1e-6);
+
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| vertices[triangle[k] as usize].position);
+            let area = ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.0;
+            assert!(area.abs() > 1e-6, "{:?}", triangle);
+        }
+        assert_front_facing(&hexagram);
+    }
+
+    #[test]
+    fn test_diamond() {
+        let diamond = Figure::try_from_index(11).unwrap();
+        assert_eq!(diamond, Figure::Diamond);
+        assert_eq!(diamond.get_vertices().len(), 4);
+        assert_eq!(diamond.get_indices(), vec![0, 1, 3, 1, 2, 3]);
+        assert_eq!(diamond.get_bounding_box(), ([-0.5, -0.5], [0.5, 0.5]));
+        assert!((diamond.get_signed_area() - 0.5).abs() < 1e-6);
+        assert_front_facing(&diamond);
+    }
+
+    #[test]
+    fn test_cross() {
+        let cross = Figure::try_from_index(12).unwrap();
+        assert_eq!(cross, Figure::Cross);
+
+        // A center square and 4 arms, as 10 triangles.
+        let vertices = cross.get_vertices();
+        assert_eq!(vertices.len(), 12);
+        assert_eq!(cross.get_indices().len(), 30);
+        assert!(vertices.iter().all(|v| {
+            let [x, y, _] = v.position;
+            x.abs() <= 0.5 && y.abs() <= 0.5
+        }));
+        assert_eq!(cross.get_bounding_box(), ([-0.5, -0.5], [0.5, 0.5]));
+
+        // A center square of 0.2, and 4 arms of 0.4 by 0.2.
+        assert!((cross.get_signed_area() - 0.36).abs() < 1e-6);
+        assert_front_facing(&cross);
+    }
+
+    #[test]
+    fn test_arrow() {
+        let arrow = Figure::try_from_index(13).unwrap();
+        assert_eq!(arrow, Figure::default_arrow());
+
+        // The shaft and the head, as 5 triangles.
+        let vertices = arrow.get_vertices();
+        assert_eq!(vertices.len(), 7);
+        assert_eq!(arrow.get_indices().len(), 15);
+        assert_front_facing(&arrow);
+        assert!(arrow.validate().is_ok());
+
+        let tip = vertices
+            .iter()
+            .max_by(|a, b| a.position[0].total_cmp(&b.position[0]))
+            .unwrap();
+        assert!((tip.position[0] - 0.5).abs() < 1e-6);
+        assert_eq!(tip.position[1..], [0.0, 0.0]);
+
+        // The shaft is 0.6 by 0.2, the head 0.4 long and 0.5 wide.
+        assert!((arrow.get_signed_area() - (0.12 + 0.1)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_arrow_proportions() {
+        assert!(Figure::arrow(0.5, 0.1, 0.3, 0.5).is_some());
+        assert!(Figure::arrow(0.7, 0.1, 0.3, 0.5).is_none());
+        assert!(Figure::arrow(0.5, 0.3, 0.3, 0.2).is_none());
+        assert!(Figure::arrow(0.5, 0.1, 1.5, 0.2).is_none());
+        assert!(Figure::arrow(0.0, 0.1, 0.3, 0.2).is_none());
+
+        // A shorter arrow is centered on the origin.
+        let (min, max) = Figure::arrow(0.3, 0.1, 0.3, 0.2)
+            .unwrap()
+            .get_bounding_box();
+        assert!((min[0] + 0.25).abs() < 1e-6 && (max[0] - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rounded_rectangle() {
+        let rounded = |corner_radius: f32, segments: u32| Figure::RoundedRectangle {
+            width: 0.8,
+            height: 0.4,
+            corner_radius,
+            segments,
+        };
+
+        for (segments, count) in [(4, 17), (8, 33)] {
+            let figure = rounded(0.1, segments);
+            assert_eq!(figure.get_vertices().len(), count);
+            assert_eq!(figure.get_indices().len(), 3 * (count - 1));
+            assert_front_facing(&figure);
+            assert!(figure.validate().is_ok());
+        }
+
+        // Each corner lies on a quarter circle around its own center.
+        let vertices = rounded(0.1, 8).get_vertices();
+        let corners = [[0.3, -0.1], [0.3, 0.1], [-0.3, 0.1], [-0.3, -0.1]];
+        for (corner, arc) in corners.iter().zip(vertices[1..].chunks(8)) {
+            for vertex in arc {
+                let [x, y, _] = vertex.position;
+                let distance = ((x - corner[0]).powi(2) + (y - corner[1]).powi(2)).sqrt();
+                assert!((distance - 0.1).abs() < 1e-6);
+            }
+        }
+
+        // The radius is clamped to half the height, the collapsed triangles
+        // being skipped.
+        for figure in [rounded(1.0, 4), rounded(0.0, 4)] {
+            assert_eq!(figure.get_vertices().len(), 17);
+            assert!(figure.validate().is_ok());
+        }
+        let (_, max) = rounded(1.0, 4).get_bounding_box();
+        assert!((max[1] - 0.2).abs() < 1e-6);
+
+        // Too many segments are clamped so that the indices do not wrap.
+        let figure = rounded(0.1, 20_000);
+        let (vertices, indices) = (figure.get_vertices(), figure.get_indices());
+        assert_eq!(
+            vertices.len(),
+            4 * MAX_ROUNDED_RECTANGLE_SEGMENTS as usize + 1
+        );
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn test_overlap_is_translucent() {
+        let vertices = Figure::Overlap.get_vertices();
+        assert_eq!(vertices.len(), 8);
+        assert!(vertices.iter().all(|v| v.color[3] == 0.5));
+        assert_eq!(Figure::Overlap.get_indices().len(), 12);
+    }
+
+    #[test]
+    fn test_planar_uvs() {
+        // The triangle spans the whole texture, its apex at the top middle.
+        let vertices = Figure::Triangle.get_vertices();
+        let uvs: Vec<[f32; 2]> = vertices.iter().map(|v| v.tex_coords).collect();
+        assert_eq!(uvs, vec![[0.5, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+
+        for figure in [
+            Figure::Pentagon,
+            Figure::Circle(16),
+            Figure::Torus {
+                major_radius: 0.3,
+                minor_radius: 0.1,
+                major_segments: 8,
+                minor_segments: 4,
+            },
+        ] {
+            assert!(figure
+                .get_vertices()
+                .iter()
+                .all(|v| v.tex_coords.iter().all(|c| (0.0..=1.0).contains(c))));
+        }
+
+        // A vertical segment has no width.
+        let mut vertices = Figure::Triangle.get_vertices();
+        vertices[2].position = [0.0, -0.5, 0.0];
+        vertices[1].position = [0.0, 0.0, 0.0];
+        generate_planar_uvs(&mut vertices);
+        assert_eq!(vertices[1].tex_coords, [0.5, 0.5]);
+
+        generate_planar_uvs(&mut []);
+    }
+
+    #[test]
+    fn test_flat_figures_face_viewer() {
+        for figure in [Figure::Triangle, Figure::Pentagon, Figure::Circle(16)] {
+            for vertex in figure.get_vertices() {
+                assert_eq!(vertex.normal, FLAT_NORMAL, "{:?}", figure);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_normals_outward() {
+        // Every vertex of a closed figure centered on the origin has a unit
+        // normal pointing away from the center.
+        for figure in [Figure::Tetrahedron, Figure::Icosphere(1)] {
+            for vertex in figure.get_vertices() {
+                let [x, y, z] = vertex.normal;
+                assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 1e-5);
+                let [px, py, pz] = vertex.position;
+                assert!(x * px + y * py + z * pz > 0.0, "{:?}", figure);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_normals_unused_vertex() {
+        let mut vertices = Figure::Triangle.get_vertices();
+        for vertex in &mut vertices {
+            vertex.normal = [0.0; 3];
+        }
+        vertices[2].position[2] = 1.0;
+        compute_normals(&mut vertices, &[0, 1, 2, 0, 1, 9]);
+
+        // The triangle out of range is ignored.
+        assert!(vertices.iter().all(|vertex| vertex.normal[2] > 0.0));
+        assert_ne!(vertices[0].normal, FLAT_NORMAL);
+
+        let mut vertices = Figure::Triangle.get_vertices();
+        compute_normals(&mut vertices, &[]);
+        assert!(vertices.iter().all(|vertex| vertex.normal == FLAT_NORMAL));
+    }
+
+    #[test]
+    fn test_vertex_at() {
+        let vertex = Vertex::at(0.1, -0.2, 0.3);
+        assert_eq!(vertex.position, [0.1, -0.2, 0.3]);
+        assert_eq!(vertex.color, [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(vertex.normal, FLAT_NORMAL);
+        assert_eq!(vertex.tex_coords, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_vertex_at_colored() {
+        let vertex = Vertex::at_colored(0.1, -0.2, 0.3, 1.0, 0.5, 0.0);
+        assert_eq!(vertex.position, [0.1, -0.2, 0.3]);
+        assert_eq!(vertex.color, [1.0, 0.5, 0.0, 1.0]);
+        assert_eq!(vertex.normal, FLAT_NORMAL);
+        assert_eq!(vertex.tex_coords, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_vertex_with_color() {
+        let vertex = Vertex {
+            tex_coords: [0.25, 0.75],
+            ..Vertex::at(1.0, 2.0, 3.0)
+        };
+        let colored = vertex.with_color(0.0, 1.0, 0.0);
+        assert_eq!(colored.color, [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(colored.position, vertex.position);
+        assert_eq!(colored.tex_coords, [0.25, 0.75]);
+        assert_eq!(vertex.color, [0.5, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_heart() {
+        let figure = Figure::Heart { segments: 64 };
+        let vertices = figure.get_vertices();
+        assert_eq!(vertices.len(), 65);
+        assert_eq!(figure.get_indices().len(), 64 * 3);
+        for vertex in &vertices {
+            assert!(vertex.position.iter().all(|c| !c.is_nan()));
+            assert!(vertex.color.iter().all(|c| !c.is_nan()));
+            for c in vertex.position {
+                assert!((-0.55..=0.55).contains(&c), "{:?}", vertex.position);
+            }
+        }
+        assert_front_facing(&figure);
+        assert_eq!(Figure::get_figure(14), figure);
+
+        // The segments are clamped to a triangle.
+        assert_eq!(Figure::Heart { segments: 0 }.get_vertices().len(), 4);
+
+        // Too many segments are clamped so that the indices do not wrap.
+        let figure = Figure::Heart { segments: 65_536 };
+        assert_eq!(figure.get_vertices().len(), MAX_HEART_SEGMENTS as usize + 1);
+        assert_eq!(
+            figure.get_indices().into_iter().max(),
+            Some(MAX_HEART_SEGMENTS as u16)
+        );
+    }
 }